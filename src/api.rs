@@ -1,26 +1,250 @@
 //! 网络请求模块
 
 use crate::constants::{
-    API_URL, CONNECT_TIMEOUT, HEADER_HOST, HEADER_ORIGIN, MAX_RETRIES, RETRY_BASE_MS, SUCCESS_CODE,
-    TIMEOUT, UA_LIST,
+    API_URL, CONNECT_TIMEOUT, MAX_BACKOFF_MS, MAX_RETRIES, RETRIES_LIMIT, RETRY_BASE_MS,
+    SUCCESS_CODE, TIMEOUT, UA_LIST,
 };
-use crate::models::{ApiResponse, OrderData};
-use anyhow::Result;
-use anyhow::{Context, anyhow, bail};
-use once_cell::sync::OnceCell;
+use crate::error::{Error, Result};
+use crate::models::{ApiResponse, BattleMode, HeroSet, Order, OrderData};
+use crate::parser::parse_raw_order;
 use rand::prelude::*;
+use reqwest::Url;
 use reqwest::header::{
     ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, CONNECTION, CONTENT_TYPE, HOST, HeaderMap,
     HeaderName, HeaderValue, ORIGIN, REFERER,
 };
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
 use std::thread::sleep;
 use std::time::Duration;
 
-/// 复用的阻塞版 HTTP 客户端（单例）
-static CLIENT: OnceCell<reqwest::blocking::Client> = OnceCell::new();
+/// HTTP 请求配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// 接口地址
+    pub endpoint: String,
+    /// 连接超时时间
+    pub connect_timeout: Duration,
+    /// 请求超时时间
+    pub timeout: Duration,
+    /// 请求失败时的最大重试次数（超过 [`RETRIES_LIMIT`] 时按上限处理）
+    pub max_retries: u32,
+    /// 严格模式：接口数据格式与预期不一致时返回 [`Error::Schema`]，而不是只记录在 [`Order::warnings`] 中
+    pub strict: bool,
+}
+
+impl Default for HttpConfig {
+    /// 使用 [`constants`](crate::constants) 中的默认值
+    fn default() -> Self {
+        Self {
+            endpoint: API_URL.to_string(),
+            connect_timeout: CONNECT_TIMEOUT,
+            timeout: TIMEOUT,
+            max_retries: MAX_RETRIES,
+            strict: false,
+        }
+    }
+}
+
+/// 订单系统客户端
+///
+/// 内部复用同一个阻塞版 HTTP 客户端，可以在多个线程之间共享。
+#[derive(Debug, Clone)]
+pub struct OrderClient {
+    http: reqwest::blocking::Client,
+    endpoint: Url,
+    max_retries: u32,
+    strict: bool,
+}
+
+impl OrderClient {
+    /// 使用默认配置创建客户端
+    pub fn new() -> Result<Self> {
+        Self::with_config(&HttpConfig::default())
+    }
+
+    /// 使用指定的 HTTP 请求配置创建客户端
+    pub fn with_config(config: &HttpConfig) -> Result<Self> {
+        let endpoint = parse_endpoint(&config.endpoint)?;
+
+        Ok(Self {
+            http: build_client(config, &endpoint)?,
+            endpoint,
+            max_retries: config.max_retries.min(RETRIES_LIMIT),
+            strict: config.strict,
+        })
+    }
+
+    /// 发送表单
+    fn send_form<T: serde::Serialize + ?Sized, D: DeserializeOwned>(
+        &self,
+        form: &T,
+    ) -> Result<ApiResponse<D>> {
+        let max_retries = self.max_retries;
+
+        for attempt in 0..=max_retries {
+            let req = self.http.post(self.endpoint.clone()).form(form);
+
+            match req.send() {
+                Ok(resp) => match resp.error_for_status() {
+                    Ok(ok_resp) => {
+                        let bytes = ok_resp.bytes().map_err(Error::Read)?;
+                        let api_response: ApiResponse<D> =
+                            serde_json::from_slice(&bytes).map_err(Error::Json)?;
+                        return Ok(api_response);
+                    }
+                    Err(e) => {
+                        if attempt < max_retries {
+                            sleep(backoff_delay(attempt));
+                            continue;
+                        }
+                        return Err(Error::Status(e));
+                    }
+                },
+                Err(e) => {
+                    if attempt < max_retries {
+                        sleep(backoff_delay(attempt));
+                        continue;
+                    }
+                    return Err(Error::Network(e));
+                }
+            }
+        }
+
+        unreachable!();
+    }
+
+    /// 发送订单操作表单
+    fn send_setting(&self, form: &[(&str, &str)]) -> Result<()> {
+        let api_response: ApiResponse = self.send_form(form)?;
+        check_code(&api_response)
+    }
+
+    /// 获取订单数据
+    ///
+    /// `order_id` 可以是订单号，也可以是订单编号（oid）。
+    pub fn fetch_order_data(&self, order_id: &str) -> Result<OrderData> {
+        self.fetch_data(order_id)
+    }
+
+    /// 获取未经解析的订单数据
+    ///
+    /// 保留接口返回的全部字段（包括 [`OrderData`] 中没有的新字段），`config` 和 `dldata` 仍为 JSON 字符串。
+    pub fn fetch_raw_order_data(&self, order_id: &str) -> Result<Map<String, Value>> {
+        self.fetch_data(order_id)
+    }
+
+    /// 获取订单数据并反序列化为指定类型
+    fn fetch_data<D: DeserializeOwned>(&self, order_id: &str) -> Result<D> {
+        let api_response: ApiResponse<D> = self.send_form(&[("key", order_id)])?;
+        check_code(&api_response)?;
+
+        match api_response.data {
+            Some(mut data) if !data.is_empty() => Ok(data.remove(0)),
+            _ => Err(Error::EmptyData),
+        }
+    }
+
+    /// 获取、校验并解析订单数据
+    ///
+    /// `order_id` 可以是订单号，也可以是订单编号（oid）。数据格式与预期不一致的地方记录在 [`Order::warnings`] 中，
+    /// 严格模式下返回 [`Error::Schema`]。
+    pub fn fetch_order(&self, order_id: &str) -> Result<Order> {
+        let order = parse_raw_order(self.fetch_raw_order_data(order_id)?)?;
+
+        if self.strict && !order.warnings.is_empty() {
+            return Err(Error::Schema(order.warnings));
+        }
+
+        Ok(order)
+    }
+
+    /// 并发获取并解析多个订单的数据
+    ///
+    /// 每个订单在单独的线程中查询，返回结果与 `order_ids` 一一对应；单个订单查询失败不影响其它订单。
+    pub fn fetch_orders<S: AsRef<str> + Sync>(&self, order_ids: &[S]) -> Vec<Result<Order>> {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = order_ids
+                .iter()
+                .map(|order_id| scope.spawn(move || self.fetch_order(order_id.as_ref())))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("订单查询线程异常退出"))
+                .collect()
+        })
+    }
+
+    /// 设置对战模式
+    pub fn set_battle_mode(&self, oid: &str, bnetpwd: &str, mode: BattleMode) -> Result<()> {
+        let battlemode = mode.code().ok_or(Error::UnknownBattleMode)?;
+
+        self.send_setting(&[
+            ("battlemode", battlemode),
+            ("oid", oid),
+            ("bnetpwd", bnetpwd),
+        ])
+    }
+
+    /// 设置对战英雄
+    pub fn set_battle_heroes(&self, oid: &str, bnetpwd: &str, heroes: HeroSet) -> Result<()> {
+        self.send_setting(&[
+            ("battleheroes", &heroes.bits().to_string()),
+            ("oid", oid),
+            ("bnetpwd", bnetpwd),
+        ])
+    }
+
+    /// 设置是否自动领取奖励
+    pub fn set_auto_claim(&self, oid: &str, bnetpwd: &str, auto_claim: bool) -> Result<()> {
+        self.send_setting(&[
+            ("auto", switch_value(auto_claim)),
+            ("oid", oid),
+            ("bnetpwd", bnetpwd),
+        ])
+    }
+
+    /// 设置是否暂停上号
+    pub fn set_pause(&self, oid: &str, bnetpwd: &str, pause: bool) -> Result<()> {
+        self.send_setting(&[
+            ("pause", switch_value(pause)),
+            ("oid", oid),
+            ("bnetpwd", bnetpwd),
+        ])
+    }
+}
+
+/// 解析接口地址，只支持 http 和 https
+fn parse_endpoint(endpoint: &str) -> Result<Url> {
+    let url =
+        Url::parse(endpoint).map_err(|e| Error::InvalidEndpoint(format!("{}: {}", e, endpoint)))?;
+
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(Error::InvalidEndpoint(endpoint.to_string()));
+    }
+
+    Ok(url)
+}
+
+/// 由接口地址得出 Host 和 Origin 请求头
+fn host_and_origin(endpoint: &Url) -> (String, String) {
+    let host = endpoint.host_str().unwrap_or_default();
+    let host = match endpoint.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+
+    (host, endpoint.origin().ascii_serialization())
+}
 
 /// 构建 HTTP 客户端
-fn build_client() -> Result<reqwest::blocking::Client> {
+fn build_client(config: &HttpConfig, endpoint: &Url) -> Result<reqwest::blocking::Client> {
+    let (host, origin) = host_and_origin(endpoint);
+    let header_value = |value: &str| {
+        HeaderValue::from_str(value).map_err(|_| Error::InvalidEndpoint(endpoint.to_string()))
+    };
+
     let mut headers = HeaderMap::new();
     let x_requested_with = HeaderName::from_static("x-requested-with");
 
@@ -31,9 +255,9 @@ fn build_client() -> Result<reqwest::blocking::Client> {
         HeaderValue::from_static("en-US,en;q=0.9,zh-CN;q=0.8,zh;q=0.7"),
     );
     headers.insert(CONNECTION, HeaderValue::from_static("keep-alive"));
-    headers.insert(HOST, HeaderValue::from_static(HEADER_HOST));
-    headers.insert(ORIGIN, HeaderValue::from_static(HEADER_ORIGIN));
-    headers.insert(REFERER, HeaderValue::from_static(API_URL));
+    headers.insert(HOST, header_value(&host)?);
+    headers.insert(ORIGIN, header_value(&origin)?);
+    headers.insert(REFERER, header_value(endpoint.as_str())?);
     headers.insert(
         CONTENT_TYPE,
         HeaderValue::from_static("application/x-www-form-urlencoded; charset=UTF-8"),
@@ -43,111 +267,87 @@ fn build_client() -> Result<reqwest::blocking::Client> {
     let mut rng = rand::rng();
     let ua = *UA_LIST.choose(&mut rng).unwrap();
 
-    let client = reqwest::blocking::Client::builder()
-        .connect_timeout(CONNECT_TIMEOUT)
-        .timeout(TIMEOUT)
+    reqwest::blocking::Client::builder()
+        .connect_timeout(config.connect_timeout)
+        .timeout(config.timeout)
         .default_headers(headers)
         .user_agent(ua)
         .build()
-        .context("构建 HTTP 客户端失败")?;
-
-    Ok(client)
+        .map_err(Error::Client)
 }
 
-/// 获取全局 HTTP 客户端实例
-fn get_client() -> Result<&'static reqwest::blocking::Client> {
-    CLIENT.get_or_try_init(build_client)
+/// 第 `attempt` 次（从 0 开始）请求失败后的等待时间：按指数退避，不超过 [`MAX_BACKOFF_MS`]
+fn backoff_delay(attempt: u32) -> Duration {
+    let delay = 1u64
+        .checked_shl(attempt)
+        .and_then(|factor| RETRY_BASE_MS.checked_mul(factor))
+        .map_or(MAX_BACKOFF_MS, |delay| delay.min(MAX_BACKOFF_MS));
+    Duration::from_millis(delay)
 }
 
-/// 发送表单
-fn send_form<T: serde::Serialize>(form: &T) -> Result<ApiResponse> {
-    let client = get_client()?;
-
-    for attempt in 0..=MAX_RETRIES {
-        let req = client.post(API_URL).form(form);
-
-        match req.send() {
-            Ok(resp) => match resp.error_for_status() {
-                Ok(ok_resp) => {
-                    let bytes = ok_resp.bytes().context("读取响应失败")?;
-                    let api_response: ApiResponse =
-                        serde_json::from_slice(&bytes).context("解析响应 JSON 失败")?;
-                    return Ok(api_response);
-                }
-                Err(e) => {
-                    if attempt < MAX_RETRIES {
-                        let delay = RETRY_BASE_MS * (1 << attempt) as u64;
-                        sleep(Duration::from_millis(delay));
-                        continue;
-                    }
-                    return Err(anyhow!("请求超过最大重试次数: {}", e));
-                }
-            },
-            Err(e) => {
-                if attempt < MAX_RETRIES {
-                    let delay = RETRY_BASE_MS * (1 << attempt) as u64;
-                    sleep(Duration::from_millis(delay));
-                    continue;
-                }
-                return Err(anyhow!("网络请求失败: {}", e));
-            }
-        }
-    }
-
-    unreachable!();
-}
-
-/// 获取订单数据
-pub fn fetch_order_data(order_id: &str) -> Result<OrderData> {
-    let api_response = send_form(&[("key", order_id)])?;
-
-    if api_response.code != SUCCESS_CODE {
-        bail!("API错误({}): {}", api_response.code, api_response.error);
-    }
-
-    match api_response.data {
-        Some(mut data) if !data.is_empty() => Ok(data.remove(0)),
-        _ => bail!("API返回空数据"),
-    }
+/// 获取开关类设置项的取值
+fn switch_value(on: bool) -> &'static str {
+    if on { "1" } else { "0" }
 }
 
-/// 设置对战模式
-pub fn set_battle_mode(oid: &str, bnetpwd: &str, battlemode: &str) -> Result<()> {
-    let api_response = send_form(&[
-        ("battlemode", battlemode),
-        ("oid", oid),
-        ("bnetpwd", bnetpwd),
-    ])?;
-
+/// 检查响应代码
+fn check_code<D>(api_response: &ApiResponse<D>) -> Result<()> {
     if api_response.code != SUCCESS_CODE {
-        bail!("API错误({}): {}", api_response.code, api_response.error);
+        return Err(Error::Api {
+            code: api_response.code,
+            message: api_response.error.clone(),
+        });
     }
 
     Ok(())
 }
 
-/// 设置对战英雄
-pub fn set_battle_heroes(oid: &str, bnetpwd: &str, battleheroes: &str) -> Result<()> {
-    let api_response = send_form(&[
-        ("battleheroes", battleheroes),
-        ("oid", oid),
-        ("bnetpwd", bnetpwd),
-    ])?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if api_response.code != SUCCESS_CODE {
-        bail!("API错误({}): {}", api_response.code, api_response.error);
+    #[test]
+    fn test_backoff_delay_capped() {
+        assert_eq!(backoff_delay(0), Duration::from_millis(RETRY_BASE_MS));
+        assert_eq!(backoff_delay(2), Duration::from_millis(RETRY_BASE_MS * 4));
+        assert_eq!(backoff_delay(10), Duration::from_millis(MAX_BACKOFF_MS));
+        assert_eq!(backoff_delay(40), Duration::from_millis(MAX_BACKOFF_MS));
+        assert_eq!(
+            backoff_delay(u32::MAX),
+            Duration::from_millis(MAX_BACKOFF_MS)
+        );
     }
 
-    Ok(())
-}
-
-/// 设置是否自动领取奖励
-pub fn set_auto_claim(oid: &str, bnetpwd: &str, auto: &str) -> Result<()> {
-    let api_response = send_form(&[("auto", auto), ("oid", oid), ("bnetpwd", bnetpwd)])?;
+    #[test]
+    fn test_host_and_origin_default() {
+        let url = parse_endpoint(API_URL).unwrap();
+        assert_eq!(
+            host_and_origin(&url),
+            (
+                "139.155.71.163:1000".to_string(),
+                "http://139.155.71.163:1000".to_string()
+            )
+        );
+    }
 
-    if api_response.code != SUCCESS_CODE {
-        bail!("API错误({}): {}", api_response.code, api_response.error);
+    #[test]
+    fn test_host_and_origin_default_port() {
+        let url = parse_endpoint("https://example.com/training/hs.php").unwrap();
+        assert_eq!(
+            host_and_origin(&url),
+            ("example.com".to_string(), "https://example.com".to_string())
+        );
     }
 
-    Ok(())
+    #[test]
+    fn test_parse_endpoint_err() {
+        assert!(matches!(
+            parse_endpoint("ftp://example.com/hs.php"),
+            Err(Error::InvalidEndpoint(_))
+        ));
+        assert!(matches!(
+            parse_endpoint("hs.php"),
+            Err(Error::InvalidEndpoint(_))
+        ));
+    }
 }
@@ -0,0 +1,53 @@
+//! 模拟服务器：在本地实现订单系统的表单接口，用于离线测试
+//!
+//! 启动后输出接口地址，可以通过 `hs-order-cli --endpoint <地址>` 或环境变量 `HS_ORDER_ENDPOINT` 使用。
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use hs_order::mock::{MockOrder, MockServer};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "mock-server", version, about = "订单系统模拟服务器")]
+struct Cli {
+    #[arg(
+        short,
+        long,
+        default_value = "127.0.0.1:8080",
+        help = "监听地址",
+        long_help = "监听地址，端口为 0 时随机选择可用端口"
+    )]
+    listen: String,
+
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "样例订单文件",
+        long_help = "样例订单文件（JSON 数组），每项包含 order_id（订单号）、pwd（战网密码前4位）和 data（查询接口返回的订单原始数据）\n\n未传入时使用内置的样例订单：订单号 1234567890123456789，战网密码前4位 zwmm"
+    )]
+    fixtures: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let orders: Vec<MockOrder> = match &cli.fixtures {
+        Some(path) => {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("读取样例订单文件 {} 失败", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("解析样例订单文件 {} 失败", path.display()))?
+        }
+        None => vec![MockOrder::sample()],
+    };
+
+    let server = MockServer::bind(&cli.listen, orders)
+        .with_context(|| format!("监听 {} 失败", cli.listen))?;
+
+    println!("模拟服务器已启动: {}", server.endpoint());
+    server.wait();
+
+    Ok(())
+}
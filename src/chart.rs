@@ -0,0 +1,265 @@
+//! 字符图表模块
+//!
+//! 将经验和金币记录整理为随时间变化的累计曲线、将对战记录整理为每天的对局数，并绘制为 Unicode 盲文折线图和迷你柱状图，
+//! 在终端中一眼就能看出代练进度是否停滞。
+
+use crate::models::{BattleRecord, ExpRecord, GoldRecord, Timestamp};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+/// 迷你柱状图使用的字符（从低到高）
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// 迷你柱状图中没有对局的日期使用的字符
+const SPARK_EMPTY: char = '·';
+/// 盲文字符中各点对应的位（按点阵的行、列）
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+/// 空白盲文字符
+const BRAILLE_BLANK: u32 = 0x2800;
+/// 绘图区的最小列数
+const MIN_PLOT_WIDTH: usize = 10;
+
+/// 曲线上的一个点：时间和值
+pub type Point = (Timestamp, i64);
+
+/// 总经验随时间变化的曲线（取经验记录中的总经验，按时间排列，没有时间的记录不计入）
+pub fn exp_curve(records: &[ExpRecord]) -> Vec<Point> {
+    let mut points: Vec<Point> = records
+        .iter()
+        .filter_map(|r| r.time.map(|time| (time, r.total_exp)))
+        .collect();
+    points.sort_by_key(|(time, _)| *time);
+    points
+}
+
+/// 累计金币变化随时间变化的曲线（从 0 开始按时间累加金币变化，没有时间的记录不计入）
+pub fn gold_curve(records: &[GoldRecord]) -> Vec<Point> {
+    let mut changes: Vec<Point> = records
+        .iter()
+        .filter_map(|r| r.time.map(|time| (time, r.gold_change)))
+        .collect();
+    changes.sort_by_key(|(time, _)| *time);
+
+    let mut total = 0;
+    changes
+        .into_iter()
+        .map(|(time, change)| {
+            total += change;
+            (time, total)
+        })
+        .collect()
+}
+
+/// 每天（北京时间）的对局数，从最早一天到最近一天，没有对局的日期为 0
+///
+/// 与 [`daily_stats`](crate::report::daily_stats) 一致，对局按开始时间计入当天，没有时间的记录不计入。
+pub fn daily_battles(records: &[BattleRecord]) -> Vec<(NaiveDate, usize)> {
+    let mut counts = BTreeMap::new();
+    for time in records.iter().filter_map(|r| r.time) {
+        *counts.entry(time.date_naive()).or_insert(0) += 1;
+    }
+
+    let (Some((&first, _)), Some((&last, _))) = (counts.first_key_value(), counts.last_key_value())
+    else {
+        return Vec::new();
+    };
+
+    first
+        .iter_days()
+        .take_while(|date| *date <= last)
+        .map(|date| (date, counts.get(&date).copied().unwrap_or(0)))
+        .collect()
+}
+
+/// 绘制折线图，返回各行文本
+///
+/// 左侧为纵轴，标出最大值和最小值；底部为横轴以及第一个点和最后一个点的时间。`width` 为包括纵轴在内的总列数，
+/// `height` 为绘图区的行数（至少 2 行）。横轴按时间等比例排列，每个字符包含 2×4 个点。没有点时返回空列表。
+pub fn line_chart(points: &[Point], width: usize, height: usize) -> Vec<String> {
+    let (Some(&(start, _)), Some(&(end, _))) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let height = height.max(2);
+    let min = points.iter().map(|(_, v)| *v).min().unwrap_or(0);
+    let max = points.iter().map(|(_, v)| *v).max().unwrap_or(0);
+
+    let (max_label, min_label) = (max.to_string(), min.to_string());
+    let label_width = max_label.len().max(min_label.len());
+    let plot_width = width.saturating_sub(label_width + 2).max(MIN_PLOT_WIDTH);
+    let (dots_x, dots_y) = (plot_width * 2 - 1, height * 4 - 1);
+
+    let span = (end - start).num_seconds();
+    let to_dot = |&(time, value): &Point| {
+        let x = if span > 0 {
+            ((time - start).num_seconds() as f64 / span as f64 * dots_x as f64).round() as usize
+        } else {
+            0
+        };
+        let y = if max > min {
+            ((value - min) as f64 / (max - min) as f64 * dots_y as f64).round() as usize
+        } else {
+            0
+        };
+        (x, dots_y - y)
+    };
+
+    let mut grid = vec![vec![0u32; plot_width]; height];
+    let mut plot = |(x, y): (usize, usize)| grid[y / 4][x / 2] |= BRAILLE_DOTS[y % 4][x % 2];
+
+    let dots: Vec<(usize, usize)> = points.iter().map(to_dot).collect();
+    plot(dots[0]);
+    for pair in dots.windows(2) {
+        draw_line(pair[0], pair[1], &mut plot);
+    }
+
+    let mut lines: Vec<String> = grid
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let (label, axis) = match i {
+                0 if max > min => (max_label.as_str(), '┤'),
+                _ if i == height - 1 => (min_label.as_str(), '┤'),
+                _ => ("", '│'),
+            };
+            let cells: String = row
+                .iter()
+                .map(|&bits| char::from_u32(BRAILLE_BLANK + bits).unwrap_or(' '))
+                .collect();
+            format!("{:>w$} {}{}", label, axis, cells, w = label_width)
+        })
+        .collect();
+
+    lines.push(format!(
+        "{:>w$} └{}",
+        "",
+        "─".repeat(plot_width),
+        w = label_width
+    ));
+
+    let (start_label, end_label) = (format_label(&start), format_label(&end));
+    let padding = plot_width.saturating_sub(start_label.len() + end_label.len());
+    let times = if span > 0 && padding > 0 {
+        format!("{}{}{}", start_label, " ".repeat(padding), end_label)
+    } else {
+        start_label
+    };
+    lines.push(format!("{:>w$}  {}", "", times, w = label_width));
+
+    lines
+}
+
+/// 绘制迷你柱状图：每个值一个字符，按与最大值的比例选择高度，0 显示为 `·`
+pub fn sparkline(values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| match value {
+            0 => SPARK_EMPTY,
+            _ => SPARK_CHARS[(value * SPARK_CHARS.len()).div_ceil(max) - 1],
+        })
+        .collect()
+}
+
+/// 在两个点之间画线（包括两端）
+fn draw_line(from: (usize, usize), to: (usize, usize), plot: &mut impl FnMut((usize, usize))) {
+    let (dx, dy) = (
+        to.0 as isize - from.0 as isize,
+        to.1 as isize - from.1 as isize,
+    );
+    let steps = dx.abs().max(dy.abs()).max(1);
+    for step in 0..=steps {
+        let x = from.0 as f64 + dx as f64 * step as f64 / steps as f64;
+        let y = from.1 as f64 + dy as f64 * step as f64 / steps as f64;
+        plot((x.round() as usize, y.round() as usize));
+    }
+}
+
+/// 横轴上的时间标签
+fn format_label(time: &Timestamp) -> String {
+    time.format("%m-%d %H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BattleResult;
+    use crate::utils::to_shanghai_time;
+
+    fn exp(time: i64, total_exp: i64) -> ExpRecord {
+        ExpRecord {
+            time: to_shanghai_time(time),
+            exp_change: 0,
+            level: 43,
+            total_exp,
+            current_level_exp: 0,
+        }
+    }
+
+    #[test]
+    fn test_curves() {
+        // 记录按时间倒序，与接口一致
+        let points = exp_curve(&[exp(1762928742, 31043), exp(1762920000, 30000)]);
+        assert_eq!(
+            points.iter().map(|p| p.1).collect::<Vec<_>>(),
+            [30000, 31043]
+        );
+
+        let gold = [(1762928000, 50), (1762920279, -20), (1762924000, 30)].map(|(time, change)| {
+            GoldRecord {
+                time: to_shanghai_time(time),
+                gold_change: change,
+                pack_change: 0,
+            }
+        });
+        let points = gold_curve(&gold);
+        assert_eq!(
+            points.iter().map(|p| p.1).collect::<Vec<_>>(),
+            [-20, 10, 60]
+        );
+    }
+
+    #[test]
+    fn test_daily_battles_fills_gaps() {
+        // 2025-11-10 00:30、2025-11-10 23:00 和 2025-11-12 10:00（北京时间）
+        let battles = [1762705800, 1762786800, 1762912800].map(|time| BattleRecord {
+            time: to_shanghai_time(time),
+            end_time: None,
+            result: BattleResult::Win,
+            exp: 0,
+        });
+        let days = daily_battles(&battles);
+        assert_eq!(
+            days.iter().map(|(_, count)| *count).collect::<Vec<_>>(),
+            [2, 0, 1]
+        );
+        assert_eq!(days[0].0, NaiveDate::from_ymd_opt(2025, 11, 10).unwrap());
+        assert_eq!(sparkline(&[2, 0, 1, 8]), "▂·▁█");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_line_chart() {
+        let points = exp_curve(&[exp(1762920000, 30000), exp(1762928742, 31043)]);
+        let lines = line_chart(&points, 40, 3);
+
+        // 3 行绘图区、横轴和时间
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|line| line.chars().count() <= 40));
+        assert!(lines[0].starts_with("31043 ┤"));
+        assert!(lines[1].starts_with("      │"));
+        assert!(lines[2].starts_with("30000 ┤"));
+        assert!(lines[4].trim_start().starts_with("11-12 12:00"));
+        assert!(lines[4].ends_with("11-12 14:25"));
+
+        // 直线从左下角到右上角
+        assert!(lines[0].ends_with('⠉'));
+        assert_eq!(lines[2].chars().nth(7), Some('⣀'));
+
+        assert_eq!(line_chart(&[], 40, 3), Vec::<String>::new());
+
+        // 只有一个点时画在左下角，纵轴只标出一个值
+        let single = line_chart(&points[..1], 40, 3);
+        assert_eq!(single.len(), 5);
+        assert!(single[0].starts_with("      │"));
+        assert!(single[2].starts_with("30000 ┤⡀"));
+    }
+}
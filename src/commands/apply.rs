@@ -0,0 +1,238 @@
+//! apply 子命令：按期望状态文件调整订单配置
+//!
+//! 期望状态文件为 TOML 格式，`[orders."<订单号或 @别名>"]` 中描述订单期望的对战模式、对战英雄、
+//! 是否自动领取奖励和是否暂停上号，未写出的项保持不变。每个订单只发送与当前配置不一致的项，重复执行不会产生额外的请求。
+
+use crate::config::{Config, OrderTarget};
+use crate::display::{display_config_diff, print_header};
+use crate::pwd::PwdResolver;
+use crate::settings::{ConfigChange, DesiredState, diff_configs};
+use crate::{apply_change, exit_code, parse_order_id, print_error, verify_settings};
+use anyhow::{Context, Result, anyhow};
+use clap::Args;
+use colored::Colorize;
+use hs_order::models::Order;
+use hs_order::{HttpConfig, OrderClient};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct ApplyArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "期望状态文件",
+        long_help = "期望状态文件（TOML）\n\n示例:\n[orders.\"@main\"]\nmode = \"酒馆战棋\"\nheroes = [\"法师\", \"术士\"]\nauto = true\npause = false"
+    )]
+    file: PathBuf,
+
+    #[arg(long, help = "只显示需要修改的项，不发送设置请求")]
+    dry_run: bool,
+
+    #[arg(long, help = "设置后不重新查询确认是否生效")]
+    no_verify: bool,
+}
+
+/// 期望状态文件内容
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StateFile {
+    /// 订单号或别名到期望状态的映射
+    orders: BTreeMap<String, DesiredState>,
+}
+
+/// 读取并解析期望状态文件，返回各订单及其期望的配置
+fn load_state(path: &Path, config: &Config) -> Result<Vec<(OrderTarget, ConfigChange)>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("读取期望状态文件 {} 失败", path.display()))?;
+    let state = parse_state(&content)
+        .with_context(|| format!("解析期望状态文件 {} 失败", path.display()))?;
+
+    state
+        .orders
+        .iter()
+        .map(|(input, desired)| {
+            parse_order_id(input).map_err(|e| anyhow!("{}: {}", input, e))?;
+            let change = desired
+                .to_change()
+                .map_err(|e| anyhow!("订单 {}: {}", input, e))?;
+            Ok((config.resolve(input)?, change))
+        })
+        .collect()
+}
+
+/// 解析期望状态文件内容
+fn parse_state(content: &str) -> Result<StateFile> {
+    Ok(toml::from_str(content)?)
+}
+
+/// 执行 apply 子命令
+pub fn run(args: &ApplyArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    let orders = load_state(&args.file, config)?;
+
+    print_header();
+
+    if orders.is_empty() {
+        println!("期望状态文件中没有订单");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let client = OrderClient::with_config(http).context("初始化订单客户端失败")?;
+    let mut resolver = PwdResolver::new(None);
+    let mut failed = 0;
+
+    for (target, desired) in &orders {
+        if !apply_order(
+            &client,
+            &mut resolver,
+            target,
+            desired,
+            args.dry_run,
+            args.no_verify,
+        ) {
+            failed += 1;
+        }
+        println!();
+    }
+
+    Ok(exit_code(failed, orders.len()))
+}
+
+/// 将单个订单调整为期望状态，成功时返回 `true`
+///
+/// `dry_run` 为 `true` 时只显示需要修改的项；`no_verify` 为 `true` 时设置后不重新查询确认。
+pub fn apply_order(
+    client: &OrderClient,
+    resolver: &mut PwdResolver,
+    target: &OrderTarget,
+    desired: &ConfigChange,
+    dry_run: bool,
+    no_verify: bool,
+) -> bool {
+    println!("订单 {}", target.input.bright_cyan());
+
+    let key = target.oid.as_deref().unwrap_or(&target.order_id);
+    let order = match fetch(client, key) {
+        Ok(order) => order,
+        Err(e) => {
+            print_error("查询订单数据失败", &e);
+            return false;
+        }
+    };
+
+    let before = order.config;
+    let change = desired.changes_from(&before);
+
+    if change.is_empty() {
+        println!("{}", "已是期望状态，无需修改".bright_green());
+        return true;
+    }
+
+    println!(
+        "{}",
+        if dry_run {
+            "预览（不会发送设置请求），需要修改:"
+        } else {
+            "需要修改:"
+        }
+        .bright_yellow()
+    );
+    display_config_diff(&diff_configs(&before, &change.apply(&before)));
+
+    if dry_run {
+        return true;
+    }
+
+    let pwd4 = match resolver.resolve(target) {
+        Ok(pwd4) => pwd4,
+        Err(e) => {
+            print_error("获取战网密码前4位失败", &e);
+            return false;
+        }
+    };
+
+    let (applied, mut ok) = apply_change(client, &order.oid, &change, &pwd4);
+
+    if !no_verify && !applied.is_empty() {
+        let result = fetch(client, &order.oid)
+            .and_then(|after| verify_settings(&applied, Some(&before), &after.config));
+
+        if let Err(e) = result {
+            print_error("确认设置结果失败", &e);
+            ok = false;
+        }
+    }
+
+    ok
+}
+
+/// 查询订单数据并保存到本地历史记录
+fn fetch(client: &OrderClient, key: &str) -> Result<Order> {
+    let order = client.fetch_order(key).context("查询订单数据失败")?;
+
+    if let Err(e) = super::history::save(&order) {
+        print_error("保存历史记录失败", &e);
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hs_order::models::{BattleMode, HeroSet};
+
+    #[test]
+    fn test_parse_state() {
+        let state = parse_state(
+            r#"
+[orders."@main"]
+mode = "酒馆战棋"
+heroes = ["法师", "术士"]
+auto = true
+
+[orders."1234567890123456789"]
+heroes = 192
+pause = false
+"#,
+        )
+        .unwrap();
+
+        let main = state.orders["@main"].to_change().unwrap();
+        assert_eq!(main.mode, Some(BattleMode::Battlegrounds));
+        assert_eq!(main.heroes, Some(HeroSet::MAGE | HeroSet::WARLOCK));
+        assert_eq!(main.auto_claim, Some(true));
+        assert_eq!(main.pause, None);
+
+        let plain = state.orders["1234567890123456789"].to_change().unwrap();
+        assert_eq!(plain.mode, None);
+        assert_eq!(plain.heroes, Some(HeroSet::MAGE | HeroSet::WARLOCK));
+        assert_eq!(plain.pause, Some(false));
+    }
+
+    #[test]
+    fn test_parse_state_err() {
+        assert!(parse_state("[orders.\"@main\"]\nmodes = \"wild\"\n").is_err());
+
+        let state =
+            parse_state("[orders.\"@main\"]\nmode = \"arena\"\nheroes = \"all\"\n").unwrap();
+        assert_eq!(
+            state.orders["@main"].to_change().unwrap_err(),
+            "未知对战模式: arena"
+        );
+
+        let state = parse_state("[orders.\"@main\"]\nheroes = [\"神谕者\"]\n").unwrap();
+        assert!(state.orders["@main"].to_change().is_err());
+
+        // 空列表会得到掩码 0，与 --hero 一样不允许
+        let state = parse_state("[orders.\"@main\"]\nheroes = []\n").unwrap();
+        assert_eq!(
+            state.orders["@main"].to_change().unwrap_err(),
+            "对战英雄不能为空"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! chart 子命令：以字符图表显示订单的本地历史记录
+
+use crate::commands::history::query_history;
+use crate::config::Config;
+use crate::display::{display_charts, print_header};
+use crate::{now, parse_order_id};
+use anyhow::Result;
+use chrono::Days;
+use clap::Args;
+use hs_order::{HttpConfig, Timestamp};
+use std::process::ExitCode;
+use terminal_size::{Width, terminal_size};
+
+/// 无法获取终端宽度时的图表宽度
+const DEFAULT_WIDTH: usize = 80;
+
+#[derive(Args)]
+pub struct ChartArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(
+        long,
+        value_name = "NUM",
+        help = "只显示最近的若干天",
+        long_help = "只显示最近若干天（北京时间的自然日，包括今天）的记录，至少为 1",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    days: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "COLS",
+        help = "图表宽度",
+        long_help = "图表宽度（列数），默认为终端宽度，输出不是终端时为 80",
+        value_parser = clap::value_parser!(u16).range(20..)
+    )]
+    width: Option<u16>,
+
+    #[arg(
+        long,
+        value_name = "ROWS",
+        default_value_t = 10,
+        help = "折线图高度",
+        long_help = "折线图绘图区的行数，默认值为 10",
+        value_parser = clap::value_parser!(u16).range(2..)
+    )]
+    height: u16,
+
+    #[arg(
+        short,
+        long,
+        help = "跳过查询订单数据",
+        long_help = "跳过查询订单数据，直接将传入的订单号（或订单别名中配置的订单编号）作为订单编号读取本地历史记录\n\n默认会先查询一次订单数据并保存到本地历史记录"
+    )]
+    skip_query: bool,
+}
+
+/// 执行 chart 子命令
+pub fn run(args: &ChartArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    print_header();
+
+    let Some(mut history) = query_history(&args.order_id, args.skip_query, config, http, true)?
+    else {
+        return Ok(ExitCode::from(1));
+    };
+
+    if let Some(days) = args.days {
+        let since = now().date_naive().checked_sub_days(Days::new(days - 1));
+        let recent = |time: &Option<Timestamp>| {
+            time.is_some_and(|time| since.is_none_or(|since| time.date_naive() >= since))
+        };
+        history.gold_records.retain(|r| recent(&r.time));
+        history.exp_records.retain(|r| recent(&r.time));
+        history.battle_records.retain(|r| recent(&r.time));
+    }
+
+    let width = args.width.map_or_else(
+        || terminal_size().map_or(DEFAULT_WIDTH, |(Width(w), _)| usize::from(w)),
+        usize::from,
+    );
+
+    display_charts(
+        &history.oid,
+        &history.gold_records,
+        &history.exp_records,
+        &history.battle_records,
+        width,
+        usize::from(args.height),
+    );
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,216 @@
+//! configure 子命令：通过交互式向导调整订单配置
+//!
+//! 查询订单的当前配置，依次选择对战模式、勾选对战英雄、设置是否自动领取奖励和是否暂停上号（默认值均为当前配置），
+//! 然后与 apply 子命令一样只发送有变化的项，不需要记住英雄名称或掩码的写法。
+
+use crate::commands::apply::apply_order;
+use crate::commands::history;
+use crate::config::Config;
+use crate::display::{battle_mode_name, print_header};
+use crate::pwd::{PwdResolver, can_prompt};
+use crate::settings::ConfigChange;
+use crate::{exit_code, parse_order_id, print_error};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Args;
+use colored::Colorize;
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, MultiSelect, Select};
+use hs_order::constants::HERO_NAMES;
+use hs_order::models::{BattleMode, HeroSet, OrderConfig};
+use hs_order::{HttpConfig, OrderClient};
+use std::process::ExitCode;
+
+/// 当前对战模式未知时额外提供的选项
+const KEEP_MODE: &str = "不修改（当前为未知模式）";
+
+#[derive(Args)]
+pub struct ConfigureArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(long, help = "只显示需要修改的项，不发送设置请求")]
+    dry_run: bool,
+
+    #[arg(long, help = "设置后不重新查询确认是否生效")]
+    no_verify: bool,
+}
+
+/// 执行 configure 子命令
+pub fn run(args: &ConfigureArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    if !can_prompt() {
+        bail!(
+            "configure 子命令需要在终端中交互式运行；脚本中请使用 --mode、--hero 等选项或 apply 子命令"
+        );
+    }
+
+    let target = config.resolve(&args.order_id)?;
+
+    print_header();
+    println!("正在查询订单: {}\n", target.order_id.bright_cyan());
+
+    let client = OrderClient::with_config(http).context("初始化订单客户端失败")?;
+    let key = target.oid.as_deref().unwrap_or(&target.order_id);
+    let order = match client.fetch_order(key) {
+        Ok(order) => order,
+        Err(e) => {
+            print_error("查询失败", &anyhow!("{}", e).context("获取订单数据失败"));
+            return Ok(ExitCode::from(1));
+        }
+    };
+    if let Err(e) = history::save(&order) {
+        print_error("保存历史记录失败", &e);
+    }
+
+    println!(
+        "当前配置: {}\n",
+        ConfigChange::from(&order.config).describe()
+    );
+
+    let Some(desired) = prompt_change(&order.config)? else {
+        println!("{}", "已取消，没有修改任何配置".bright_yellow());
+        return Ok(ExitCode::SUCCESS);
+    };
+    println!();
+
+    if desired.changes_from(&order.config).is_empty() {
+        println!("{}", "配置没有变化，无需修改".bright_green());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut resolver = PwdResolver::new(None);
+    let ok = apply_order(
+        &client,
+        &mut resolver,
+        &target,
+        &desired,
+        args.dry_run,
+        args.no_verify,
+    );
+
+    Ok(exit_code(usize::from(!ok), 1))
+}
+
+/// 依次询问对战模式、对战英雄、自动领取奖励和暂停上号，按 Esc 或 q 取消时返回 `None`
+///
+/// 没有勾选任何英雄时重新询问，避免发送掩码 0。
+fn prompt_change(current: &OrderConfig) -> Result<Option<ConfigChange>> {
+    let theme = ColorfulTheme::default();
+
+    let (modes, default) = mode_items(current.mode);
+    let Some(index) = Select::with_theme(&theme)
+        .with_prompt("对战模式（↑↓ 选择，回车确认）")
+        .items(&modes)
+        .default(default)
+        .interact_opt()
+        .context("读取输入失败")?
+    else {
+        return Ok(None);
+    };
+    let mode = BattleMode::ALL.get(index).copied();
+
+    let defaults = hero_defaults(current.heroes);
+    let heroes = loop {
+        let Some(selected) = MultiSelect::with_theme(&theme)
+            .with_prompt("对战英雄（空格勾选，回车确认）")
+            .items(HERO_NAMES)
+            .defaults(&defaults)
+            .interact_opt()
+            .context("读取输入失败")?
+        else {
+            return Ok(None);
+        };
+
+        match selected_heroes(&selected) {
+            Some(heroes) => break heroes,
+            None => eprintln!("{}", "至少需要选择一个英雄".bright_red()),
+        }
+    };
+
+    let Some(auto_claim) = Confirm::with_theme(&theme)
+        .with_prompt("自动领取奖励")
+        .default(current.auto_claim)
+        .interact_opt()
+        .context("读取输入失败")?
+    else {
+        return Ok(None);
+    };
+
+    let Some(pause) = Confirm::with_theme(&theme)
+        .with_prompt("暂停上号")
+        .default(current.pause)
+        .interact_opt()
+        .context("读取输入失败")?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(ConfigChange {
+        mode,
+        heroes: Some(heroes),
+        auto_claim: Some(auto_claim),
+        pause: Some(pause),
+    }))
+}
+
+/// 对战模式选项和默认选中的下标
+///
+/// 当前模式未知时在最后增加“不修改”并默认选中，避免直接回车时误改为第一个模式。
+fn mode_items(current: BattleMode) -> (Vec<&'static str>, usize) {
+    let mut items: Vec<&str> = BattleMode::ALL.map(battle_mode_name).to_vec();
+    let default = match BattleMode::ALL.iter().position(|&mode| mode == current) {
+        Some(index) => index,
+        None => {
+            items.push(KEEP_MODE);
+            items.len() - 1
+        }
+    };
+    (items, default)
+}
+
+/// 各英雄是否默认勾选（与 [`HERO_NAMES`] 的顺序一致）
+fn hero_defaults(heroes: HeroSet) -> Vec<bool> {
+    (0..HERO_NAMES.len())
+        .map(|i| heroes.bits() & (1 << i) != 0)
+        .collect()
+}
+
+/// 由勾选的英雄下标得到英雄集合，没有勾选任何英雄时返回 `None`
+fn selected_heroes(indices: &[usize]) -> Option<HeroSet> {
+    let heroes = indices.iter().fold(HeroSet::empty(), |set, &i| {
+        set | HeroSet::from_bits_retain(1 << i)
+    });
+    (!heroes.is_empty()).then_some(heroes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_items() {
+        let (items, default) = mode_items(BattleMode::Wild);
+        assert_eq!(items.len(), BattleMode::ALL.len());
+        assert_eq!(items[default], "狂野模式");
+
+        let (items, default) = mode_items(BattleMode::Unknown);
+        assert_eq!(items[default], KEEP_MODE);
+        assert_eq!(BattleMode::ALL.get(default), None);
+    }
+
+    #[test]
+    fn test_hero_selection_roundtrip() {
+        let heroes = HeroSet::MAGE | HeroSet::WARLOCK;
+        let defaults = hero_defaults(heroes);
+        assert_eq!(defaults.len(), HERO_NAMES.len());
+        assert_eq!(defaults.iter().filter(|&&checked| checked).count(), 2);
+
+        let indices: Vec<usize> = (0..defaults.len()).filter(|&i| defaults[i]).collect();
+        assert_eq!(selected_heroes(&indices), Some(heroes));
+        assert_eq!(selected_heroes(&[]), None);
+        assert!(
+            selected_heroes(&(0..HERO_NAMES.len()).collect::<Vec<_>>())
+                .unwrap()
+                .is_all()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! export 子命令：将订单的本地历史记录导出为 CSV、TSV 或 Markdown 文件
+
+use crate::commands::history::query_history;
+use crate::config::Config;
+use crate::display::print_header;
+use crate::parse_order_id;
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use hs_order::HttpConfig;
+use hs_order::export::{ExportFormat, Sheet};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum FormatArg {
+    Csv,
+    Tsv,
+    #[value(alias = "md")]
+    Markdown,
+}
+
+impl From<FormatArg> for ExportFormat {
+    fn from(format: FormatArg) -> Self {
+        match format {
+            FormatArg::Csv => Self::Csv,
+            FormatArg::Tsv => Self::Tsv,
+            FormatArg::Markdown => Self::Markdown,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(
+        short = 'F',
+        long,
+        value_name = "FORMAT",
+        default_value = "csv",
+        help = "文件格式",
+        long_help = "文件格式\n\ncsv: 逗号分隔（默认）\ntsv: 制表符分隔，带 UTF-8 BOM，可以直接用 Excel 打开\nmarkdown: Markdown 表格（别名 md）"
+    )]
+    format: FormatArg,
+
+    #[arg(
+        short,
+        long,
+        value_name = "DIR",
+        default_value = ".",
+        help = "导出目录",
+        long_help = "导出目录，不存在时自动创建，同名文件会被覆盖，默认为当前目录"
+    )]
+    dir: PathBuf,
+
+    #[arg(
+        long,
+        help = "合并为一个表格",
+        long_help = "把金币、经验和对战记录合并为一个表格（<oid>-records.<ext>），按时间倒序排列，type 列为 gold、exp 或 battle\n\n默认每类记录导出为一个文件：<oid>-gold.<ext>、<oid>-exp.<ext>、<oid>-battle.<ext>"
+    )]
+    combined: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "跳过查询订单数据",
+        long_help = "跳过查询订单数据，直接将传入的订单号（或订单别名中配置的订单编号）作为订单编号读取本地历史记录\n\n默认会先查询一次订单数据并保存到本地历史记录"
+    )]
+    skip_query: bool,
+}
+
+/// 执行 export 子命令
+pub fn run(args: &ExportArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    print_header();
+
+    let Some(history) = query_history(&args.order_id, args.skip_query, config, http, true)? else {
+        return Ok(ExitCode::from(1));
+    };
+
+    let sheets = if args.combined {
+        vec![(
+            "records",
+            "全部记录",
+            Sheet::combined(
+                &history.gold_records,
+                &history.exp_records,
+                &history.battle_records,
+            ),
+        )]
+    } else {
+        vec![
+            ("gold", "金币记录", Sheet::gold(&history.gold_records)),
+            ("exp", "经验记录", Sheet::exp(&history.exp_records)),
+            ("battle", "对战记录", Sheet::battle(&history.battle_records)),
+        ]
+    };
+
+    fs::create_dir_all(&args.dir)
+        .with_context(|| format!("创建导出目录 {} 失败", args.dir.display()))?;
+
+    let format = ExportFormat::from(args.format);
+    for (name, title, sheet) in sheets {
+        let path = args.dir.join(format.file_name(&history.oid, name));
+        fs::write(&path, sheet.render(format))
+            .with_context(|| format!("写入文件 {} 失败", path.display()))?;
+        println!(
+            "已导出{} {} 条: {}",
+            title,
+            sheet.rows.len().to_string().bright_white(),
+            path.display().to_string().bright_cyan()
+        );
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
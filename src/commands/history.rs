@@ -0,0 +1,143 @@
+//! history 子命令：查看订单的本地历史记录
+
+use crate::config::Config;
+use crate::display::{display_history, print_header};
+use crate::paths;
+use crate::{OutputFormat, parse_order_id, parse_table_size, print_error, serialize_output};
+use anyhow::{Context, Result, anyhow};
+use clap::Args;
+use colored::Colorize;
+use hs_order::models::Order;
+use hs_order::{History, HistoryStore, HttpConfig, OrderClient};
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(
+        short,
+        long,
+        help = "跳过查询订单数据",
+        long_help = "跳过查询订单数据，直接将传入的订单号（或订单别名中配置的订单编号）作为订单编号读取本地历史记录\n\n默认会先查询一次订单数据并保存到本地历史记录"
+    )]
+    skip_query: bool,
+
+    #[arg(
+        short = 't',
+        long = "table-size",
+        value_name = "NUM",
+        default_value = "all",
+        help = "每个表格显示的最大记录条数",
+        long_help = "每个表格显示的最大记录条数，默认显示所有记录\n\n0 表示不显示表格",
+        value_parser = parse_table_size
+    )]
+    table_size: usize,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        help = "输出格式",
+        long_help = "输出格式\n\ntext: 彩色文本和表格（默认）\njson: JSON，字段名为英文且保持稳定，适合脚本处理\nyaml: YAML，字段与 JSON 相同"
+    )]
+    output: OutputFormat,
+}
+
+/// 打开本地数据目录下的历史记录存储
+pub fn open_store() -> Result<HistoryStore> {
+    Ok(HistoryStore::new(paths::data_dir()?.join("history")))
+}
+
+/// 将订单记录保存到本地历史记录
+pub fn save(order: &Order) -> Result<()> {
+    open_store()?
+        .save(order)
+        .with_context(|| format!("订单编号 {}", order.oid))?;
+    Ok(())
+}
+
+/// 执行 history 子命令
+pub fn run(args: &HistoryArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    let text = args.output == OutputFormat::Text;
+
+    if text {
+        print_header();
+    }
+
+    let Some(history) = query_history(&args.order_id, args.skip_query, config, http, text)? else {
+        return Ok(ExitCode::from(1));
+    };
+
+    if text {
+        display_history(&history, args.table_size);
+    } else {
+        print!("{}", serialize_output(&history, args.output)?);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 查询订单数据并保存后，读取订单的完整历史记录
+///
+/// `skip_query` 为 `true` 时直接读取本地历史记录。查询失败或没有历史记录时输出错误信息并返回 `None`；
+/// `text` 为 `false` 时错误信息输出到标准错误。
+pub fn query_history(
+    input: &str,
+    skip_query: bool,
+    config: &Config,
+    http: &HttpConfig,
+    text: bool,
+) -> Result<Option<History>> {
+    let target = config.resolve(input)?;
+
+    let oid = if skip_query {
+        target.oid.unwrap_or(target.order_id)
+    } else {
+        if text {
+            println!("正在查询订单: {}\n", target.order_id.bright_cyan());
+        }
+
+        let client = OrderClient::with_config(http).context("初始化订单客户端失败")?;
+        let order = match client.fetch_order(&target.order_id) {
+            Ok(order) => order,
+            Err(e) => {
+                let e = anyhow!("{}", e).context("获取订单数据失败");
+                if text {
+                    print_error("查询失败", &e);
+                } else {
+                    eprintln!("{:#}", e);
+                }
+                return Ok(None);
+            }
+        };
+
+        if let Err(e) = save(&order) {
+            if text {
+                print_error("保存历史记录失败", &e);
+            } else {
+                eprintln!("保存历史记录失败: {:#}", e);
+            }
+        }
+
+        order.oid
+    };
+
+    let history = open_store()?
+        .load(&oid)
+        .with_context(|| format!("读取订单编号 {} 的历史记录失败", oid))?;
+
+    if history.is_empty() {
+        let message = format!("本地没有订单编号 {} 的历史记录", oid);
+        if text {
+            println!("{}", message.bright_red());
+        } else {
+            eprintln!("{}", message);
+        }
+        return Ok(None);
+    }
+
+    Ok(Some(history))
+}
@@ -0,0 +1,112 @@
+//! inspect 子命令：查看接口返回的原始订单数据
+
+use crate::commands::history::open_store;
+use crate::config::Config;
+use crate::display::{display_raw_fields, print_header};
+use crate::{OutputFormat, now, parse_order_id, print_error, serialize_output};
+use anyhow::{Context, Result, anyhow};
+use clap::{ArgGroup, Args};
+use colored::Colorize;
+use hs_order::raw::{RawField, RawSnapshot, annotate};
+use hs_order::{HttpConfig, OrderClient, Timestamp};
+use serde::Serialize;
+use std::process::ExitCode;
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("view").required(true)))]
+pub struct InspectArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(
+        long,
+        group = "view",
+        help = "逐项显示接口返回的原始数据",
+        long_help = "逐项显示接口返回的原始数据，config 按字段、dldata 按下标展开，并注明已知的含义\n\n与上一次保存的原始数据相比有变化的值会被高亮并显示上一次的值，显示后保存本次的原始数据，用于分析接口新增或变化的字段"
+    )]
+    raw: bool,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        help = "输出格式",
+        long_help = "输出格式\n\ntext: 彩色文本（默认）\njson: JSON，字段名为英文且保持稳定，适合脚本处理\nyaml: YAML，字段与 JSON 相同"
+    )]
+    output: OutputFormat,
+}
+
+/// 原始数据报告
+#[derive(Serialize)]
+struct RawReport<'a> {
+    /// 订单编号
+    oid: &'a str,
+    /// 上一次保存原始数据的时间，没有保存过时为空
+    baseline: Option<&'a Timestamp>,
+    /// 逐项展开的原始数据
+    fields: &'a [RawField],
+}
+
+/// 执行 inspect 子命令
+pub fn run(args: &InspectArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    let text = args.output == OutputFormat::Text;
+    let target = config.resolve(&args.order_id)?;
+
+    if text {
+        print_header();
+        println!("正在查询订单: {}\n", target.order_id.bright_cyan());
+    }
+
+    let client = OrderClient::with_config(http).context("初始化订单客户端失败")?;
+    let data = match client.fetch_raw_order_data(&target.order_id) {
+        Ok(data) => data,
+        Err(e) => {
+            let e = anyhow!("{}", e).context("获取订单数据失败");
+            if text {
+                print_error("查询失败", &e);
+            } else {
+                eprintln!("{:#}", e);
+            }
+            return Ok(ExitCode::from(1));
+        }
+    };
+
+    let oid = data
+        .get("oid")
+        .and_then(|oid| oid.as_str())
+        .unwrap_or(&target.order_id)
+        .to_string();
+    let store = open_store()?;
+    let previous = store
+        .load_raw(&oid)
+        .with_context(|| format!("读取订单编号 {} 的原始数据失败", oid))?;
+    let fields = annotate(&data, previous.as_ref().map(|previous| &previous.data));
+    let baseline = previous.as_ref().map(|previous| &previous.recorded_at);
+
+    if text {
+        display_raw_fields(&oid, baseline, &fields);
+    } else {
+        let report = RawReport {
+            oid: &oid,
+            baseline,
+            fields: &fields,
+        };
+        print!("{}", serialize_output(&report, args.output)?);
+    }
+
+    let snapshot = RawSnapshot {
+        recorded_at: now(),
+        data,
+    };
+    if let Err(e) = store.save_raw(&oid, &snapshot) {
+        let e = anyhow!("{}", e).context(format!("订单编号 {}", oid));
+        if text {
+            print_error("保存原始数据失败", &e);
+        } else {
+            eprintln!("保存原始数据失败: {:#}", e);
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,13 @@
+//! 子命令模块
+
+pub mod apply;
+pub mod chart;
+pub mod configure;
+pub mod export;
+pub mod history;
+pub mod inspect;
+pub mod pwd;
+pub mod report;
+pub mod schedule;
+#[cfg(feature = "tui")]
+pub mod tui;
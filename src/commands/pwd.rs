@@ -0,0 +1,115 @@
+//! pwd 子命令：管理保存的战网密码前4位
+
+use crate::config::Config;
+use crate::parse_order_id;
+use crate::pwd::{self, PWD_ENV, keyring_store};
+use crate::vault::Vault;
+use anyhow::{Result, bail};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::env;
+use std::process::ExitCode;
+
+#[derive(Args)]
+pub struct PwdArgs {
+    #[command(subcommand)]
+    action: PwdAction,
+}
+
+#[derive(Subcommand)]
+enum PwdAction {
+    /// 保存订单的战网密码前4位（从环境变量 HS_ORDER_PWD 读取或交互式输入）
+    Set {
+        #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+        order_id: String,
+
+        #[arg(
+            short,
+            long,
+            help = "保存到系统密钥环",
+            long_help = "保存到系统密钥环（Secret Service、macOS 钥匙串或 Windows 凭据管理器），需要使用 --features keyring 编译\n\n默认保存到本地加密密码库"
+        )]
+        keyring: bool,
+    },
+    /// 删除保存的战网密码前4位
+    Remove {
+        #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+        order_id: String,
+
+        #[arg(short, long, help = "从系统密钥环中删除")]
+        keyring: bool,
+    },
+    /// 列出密码库中保存了密码的订单号
+    List,
+}
+
+/// 执行 pwd 子命令
+pub fn run(args: &PwdArgs, config: &Config) -> Result<ExitCode> {
+    match &args.action {
+        PwdAction::Set { order_id, keyring } => {
+            let order_id = config.resolve(order_id)?.order_id;
+            let pwd4 = match env::var(PWD_ENV) {
+                Ok(pwd4) => pwd::validate(&pwd4, &format!("环境变量 {}", PWD_ENV))?,
+                Err(_) if pwd::can_prompt() => pwd::prompt_pwd4(&format!("订单 {} ", order_id))?,
+                Err(_) => bail!("请通过环境变量 {} 或交互式输入提供战网密码前4位", PWD_ENV),
+            };
+
+            if *keyring {
+                keyring_store::set(&order_id, &pwd4)?;
+            } else {
+                let path = Vault::default_path()?;
+                let password = pwd::vault_password(!path.exists())?;
+                let mut vault = Vault::open(path, &password)?;
+                vault.set(&order_id, &pwd4);
+                vault.save()?;
+            }
+
+            println!(
+                "{} {}",
+                "已保存战网密码前4位，订单号".bright_green(),
+                order_id.bright_cyan()
+            );
+        }
+        PwdAction::Remove { order_id, keyring } => {
+            let order_id = config.resolve(order_id)?.order_id;
+
+            let removed = if *keyring {
+                keyring_store::remove(&order_id)?
+            } else {
+                let path = Vault::default_path()?;
+                if !path.exists() {
+                    bail!("密码库不存在");
+                }
+
+                let mut vault = Vault::open(path, &pwd::vault_password(false)?)?;
+                let removed = vault.remove(&order_id);
+                vault.save()?;
+                removed
+            };
+
+            if !removed {
+                println!("没有保存订单 {} 的战网密码前4位", order_id.bright_cyan());
+                return Ok(ExitCode::from(1));
+            }
+
+            println!(
+                "{} {}",
+                "已删除战网密码前4位，订单号".bright_green(),
+                order_id.bright_cyan()
+            );
+        }
+        PwdAction::List => {
+            let path = Vault::default_path()?;
+            if !path.exists() {
+                println!("密码库不存在");
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            for order_id in Vault::open(path, &pwd::vault_password(false)?)?.order_ids() {
+                println!("{}", order_id);
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
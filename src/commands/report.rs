@@ -0,0 +1,156 @@
+//! report 子命令：按日期或游戏时段汇总订单的本地历史记录
+
+use crate::commands::history::query_history;
+use crate::config::Config;
+use crate::display::{display_daily_report, display_service_check, display_sessions, print_header};
+use crate::{OutputFormat, now, parse_duration, parse_order_id, serialize_output};
+use anyhow::{Context, Result};
+use clap::{ArgGroup, Args};
+use hs_order::HttpConfig;
+use hs_order::report::{DailyStats, ServiceCheck, Session, check_service, daily_stats, sessions};
+use serde::Serialize;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("view").required(true).multiple(true)))]
+pub struct ReportArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(
+        long,
+        group = "view",
+        help = "按日期汇总",
+        long_help = "按日期（北京时间）汇总金币、卡包、经验和对战胜负，并显示每天的对战场次、游戏时长、上号时段、每小时经验和胜率\n\n游戏时长由对局开始和结束时间计算；本地保存过约定的每日上号时间段时，还会列出约定时间外的对战和没有上号的日期"
+    )]
+    daily: bool,
+
+    #[arg(
+        long,
+        group = "view",
+        help = "按游戏时段汇总",
+        long_help = "把间隔不超过 --gap 的连续对局合并为一个游戏时段，显示每个时段的开始和结束时间、对局数、胜负、对局时长合计和对战经验\n\n可以与 --daily 同时使用"
+    )]
+    sessions: bool,
+
+    #[arg(
+        long,
+        value_name = "GAP",
+        default_value = "15m",
+        requires = "sessions",
+        help = "合并游戏时段的最大间隔",
+        long_help = "合并游戏时段的最大间隔：一局的开始时间与上一局的结束时间间隔不超过该值时属于同一个时段\n\n支持 s/m/h 后缀（如 30s、15m、1h），不带后缀表示秒，默认为 15 分钟",
+        value_parser = |s: &str| parse_duration(s, "合并游戏时段的最大间隔")
+    )]
+    gap: Duration,
+
+    #[arg(
+        short,
+        long,
+        help = "跳过查询订单数据",
+        long_help = "跳过查询订单数据，直接将传入的订单号（或订单别名中配置的订单编号）作为订单编号读取本地历史记录\n\n默认会先查询一次订单数据并保存到本地历史记录"
+    )]
+    skip_query: bool,
+
+    #[arg(
+        long,
+        value_name = "NUM",
+        help = "只显示最近的若干天",
+        long_help = "只显示最近的若干天（有记录的日期）的统计和游戏时段"
+    )]
+    days: Option<usize>,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        help = "输出格式",
+        long_help = "输出格式\n\ntext: 彩色文本和表格（默认）\njson: JSON，字段名为英文且保持稳定，适合脚本处理\nyaml: YAML，字段与 JSON 相同"
+    )]
+    output: OutputFormat,
+}
+
+/// 汇总报表
+#[derive(Serialize)]
+struct Report<'a> {
+    /// 订单编号
+    oid: &'a str,
+    /// 每天的汇总统计（按日期倒序），传入 --daily 时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days: Option<&'a [DailyStats]>,
+    /// 按约定的每日上号时间段核对的结果，传入 --daily 时输出，没有保存过上号时间段时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_check: Option<Option<&'a ServiceCheck>>,
+    /// 游戏时段（按时间倒序），传入 --sessions 时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sessions: Option<&'a [Session]>,
+}
+
+/// 执行 report 子命令
+pub fn run(args: &ReportArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    let text = args.output == OutputFormat::Text;
+
+    if text {
+        print_header();
+    }
+
+    let Some(history) = query_history(&args.order_id, args.skip_query, config, http, text)? else {
+        return Ok(ExitCode::from(1));
+    };
+
+    let mut days = daily_stats(
+        &history.gold_records,
+        &history.exp_records,
+        &history.battle_records,
+    );
+    if let Some(limit) = args.days {
+        days.truncate(limit);
+    }
+
+    // 使用最近保存的上号时间段和截止时间核对全部对战记录
+    let latest = history.snapshots.first();
+    let service_check = history
+        .snapshots
+        .iter()
+        .find_map(|snapshot| snapshot.service_window)
+        .map(|window| {
+            check_service(
+                window,
+                &history.battle_records,
+                &now(),
+                latest.and_then(|snapshot| snapshot.deadline.as_ref()),
+            )
+        });
+
+    // 与按日期汇总显示相同的日期范围
+    let gap = chrono::Duration::from_std(args.gap).context("合并游戏时段的最大间隔过长")?;
+    let since = days.last().map(|day| day.date);
+    let sessions: Vec<Session> = sessions(&history.battle_records, gap)
+        .into_iter()
+        .filter(|session| since.is_none_or(|since| session.start.date_naive() >= since))
+        .collect();
+
+    if text {
+        if args.daily {
+            display_daily_report(&history.oid, &days);
+            if let Some(check) = &service_check {
+                display_service_check(check);
+            }
+        }
+        if args.sessions {
+            display_sessions(&history.oid, &sessions, args.gap);
+        }
+    } else {
+        let report = Report {
+            oid: &history.oid,
+            days: args.daily.then_some(days.as_slice()),
+            service_check: args.daily.then_some(service_check.as_ref()),
+            sessions: args.sessions.then_some(sessions.as_slice()),
+        };
+        print!("{}", serialize_output(&report, args.output)?);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,322 @@
+//! schedule 子命令：按定时规则在前台持续运行，到点调整订单配置
+//!
+//! 定时规则文件为 TOML 格式，每条规则包含 cron 表达式（按北京时间计算）、订单号或 @别名列表，以及要设置的配置；
+//! 规则到期时与 apply 子命令一样，只发送与当前配置不一致的项。
+
+use crate::commands::apply::apply_order;
+use crate::config::{Config, OrderTarget};
+use crate::display::print_header;
+use crate::pwd::PwdResolver;
+use crate::schedule::{CronExpr, Due, Scheduler};
+use crate::settings::{ConfigChange, DesiredState};
+use crate::{now, now_text, parse_duration, parse_order_id};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Args;
+use colored::Colorize;
+use hs_order::utils::format_time;
+use hs_order::{HttpConfig, OrderClient};
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+/// 默认的补执行时限
+const DEFAULT_GRACE: Duration = Duration::from_secs(10 * 60);
+/// 每次等待的最长时间（定期醒来检查，以应对系统时间调整和休眠）
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+#[derive(Args)]
+pub struct ScheduleArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        help = "定时规则文件",
+        long_help = "定时规则文件（TOML）\n\n示例:\ngrace = \"10m\"\n\n[[rules]]\nname = \"早上打标准\"\ncron = \"0 8 * * *\"\norders = [\"@main\"]\nset = { mode = \"标准\" }"
+    )]
+    file: PathBuf,
+
+    #[arg(short, long, help = "列出各规则的下一次执行时间后退出")]
+    list: bool,
+
+    #[arg(long, help = "规则到期时只显示需要修改的项，不发送设置请求")]
+    dry_run: bool,
+
+    #[arg(long, help = "设置后不重新查询确认是否生效")]
+    no_verify: bool,
+}
+
+/// 定时规则文件内容
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleFile {
+    /// 补执行时限：错过计划执行时间不超过该时限时立即补执行
+    #[serde(default = "default_grace", deserialize_with = "deserialize_grace")]
+    grace: Duration,
+    /// 定时规则
+    #[serde(default)]
+    rules: Vec<RuleConfig>,
+}
+
+/// 定时规则文件中的一条规则
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleConfig {
+    /// 规则名称
+    name: Option<String>,
+    /// cron 表达式（分 时 日 月 周）
+    cron: String,
+    /// 订单号或 @别名
+    orders: Vec<String>,
+    /// 要设置的配置
+    set: DesiredState,
+}
+
+/// 解析后的规则
+struct Rule {
+    name: String,
+    cron: CronExpr,
+    targets: Vec<OrderTarget>,
+    change: ConfigChange,
+}
+
+/// 默认的补执行时限
+fn default_grace() -> Duration {
+    DEFAULT_GRACE
+}
+
+/// 反序列化补执行时限（秒数或带 s/m/h 后缀的字符串）
+fn deserialize_grace<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Duration, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Grace {
+        Secs(u64),
+        Text(String),
+    }
+
+    match Grace::deserialize(deserializer)? {
+        Grace::Secs(secs) => Ok(Duration::from_secs(secs)),
+        Grace::Text(text) => parse_duration(&text, "补执行时限").map_err(serde::de::Error::custom),
+    }
+}
+
+/// 解析定时规则文件内容
+fn parse_schedule(content: &str) -> Result<ScheduleFile> {
+    Ok(toml::from_str(content)?)
+}
+
+/// 读取并解析定时规则文件
+fn load_schedule(path: &Path, config: &Config) -> Result<(Vec<Rule>, Duration)> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("读取定时规则文件 {} 失败", path.display()))?;
+    let file = parse_schedule(&content)
+        .with_context(|| format!("解析定时规则文件 {} 失败", path.display()))?;
+
+    let rules = file
+        .rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            let name = rule
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("规则 {}", i + 1));
+            let cron = CronExpr::parse(&rule.cron).map_err(|e| anyhow!("{}: {}", name, e))?;
+            let change = rule
+                .set
+                .to_change()
+                .map_err(|e| anyhow!("{}: {}", name, e))?;
+
+            if rule.orders.is_empty() {
+                bail!("{}: 没有指定订单", name);
+            }
+
+            let targets = rule
+                .orders
+                .iter()
+                .map(|input| {
+                    parse_order_id(input).map_err(|e| anyhow!("{}: {}: {}", name, input, e))?;
+                    config.resolve(input)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(Rule {
+                name,
+                cron,
+                targets,
+                change,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((rules, file.grace))
+}
+
+/// 输出带时间的日志
+fn log(message: impl Display) {
+    println!("{} {}", format!("[{}]", now_text()).bright_black(), message);
+}
+
+/// 执行 schedule 子命令
+pub fn run(args: &ScheduleArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    let (rules, grace) = load_schedule(&args.file, config)?;
+
+    print_header();
+
+    if rules.is_empty() {
+        println!("定时规则文件中没有规则");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let chrono_grace = chrono::Duration::from_std(grace).context("补执行时限过长")?;
+    let mut scheduler = Scheduler::new(
+        rules.iter().map(|rule| rule.cron.clone()).collect(),
+        chrono_grace,
+        &now(),
+    );
+
+    if args.list {
+        for (i, rule) in rules.iter().enumerate() {
+            let orders: Vec<_> = rule.targets.iter().map(|t| t.input.as_str()).collect();
+            println!("{}", rule.name.bright_cyan().bold());
+            println!("  执行时间: {}", rule.cron.as_str());
+            println!("  订单: {}", orders.join(", "));
+            println!("  设置: {}", rule.change.describe());
+            println!(
+                "  下次执行: {}",
+                format_time(scheduler.next_run(i).as_ref()).bright_yellow()
+            );
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let client = OrderClient::with_config(http).context("初始化订单客户端失败")?;
+    let mut resolver = PwdResolver::new(None);
+
+    // 启动时获取所有订单的密码，避免到点执行时需要交互式输入
+    if !args.dry_run {
+        for target in rules.iter().flat_map(|rule| &rule.targets) {
+            resolver.resolve(target)?;
+        }
+    }
+
+    log(format!(
+        "已启动，共 {} 条规则，补执行时限 {} 秒",
+        rules.len(),
+        grace.as_secs()
+    ));
+
+    loop {
+        for due in scheduler.due(&now()) {
+            match due {
+                Due::Run { rule, at } => {
+                    let rule = &rules[rule];
+                    log(format!(
+                        "执行规则 {}（计划时间 {}）: {}",
+                        rule.name.bright_cyan(),
+                        format_time(Some(&at)),
+                        rule.change.describe()
+                    ));
+
+                    let failed = rule
+                        .targets
+                        .iter()
+                        .filter(|target| {
+                            !apply_order(
+                                &client,
+                                &mut resolver,
+                                target,
+                                &rule.change,
+                                args.dry_run,
+                                args.no_verify,
+                            )
+                        })
+                        .count();
+
+                    if failed == 0 {
+                        log(format!("规则 {} 执行完成", rule.name.bright_cyan()));
+                    } else {
+                        log(format!(
+                            "规则 {} 执行完成，{} 个订单失败",
+                            rule.name.bright_cyan(),
+                            failed.to_string().bright_red()
+                        ));
+                    }
+                }
+                Due::Missed { rule, at, count } => log(format!(
+                    "{} 规则 {} {}",
+                    "已跳过".bright_red(),
+                    rules[rule].name.bright_cyan(),
+                    if count > 1 {
+                        format!("自 {} 起错过的 {} 次执行", format_time(Some(&at)), count)
+                    } else {
+                        format!("在 {} 错过的执行", format_time(Some(&at)))
+                    }
+                )),
+            }
+        }
+
+        let Some(next) = scheduler.next_wakeup() else {
+            log("没有需要执行的规则，退出");
+            return Ok(ExitCode::SUCCESS);
+        };
+
+        let wait = (next - now()).to_std().unwrap_or_default();
+        thread::sleep(wait.min(MAX_SLEEP));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hs_order::models::BattleMode;
+
+    #[test]
+    fn test_parse_schedule() {
+        let file = parse_schedule(
+            r#"
+grace = "30m"
+
+[[rules]]
+name = "早上打标准"
+cron = "0 8 * * *"
+orders = ["@main"]
+set = { mode = "标准" }
+
+[[rules]]
+cron = "0 20 * * *"
+orders = ["1234567890123456789"]
+set = { mode = "酒馆战棋", auto = true }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(file.grace, Duration::from_secs(1800));
+        assert_eq!(file.rules.len(), 2);
+        assert_eq!(
+            file.rules[1].set.to_change().unwrap().mode,
+            Some(BattleMode::Battlegrounds)
+        );
+
+        let file = parse_schedule("").unwrap();
+        assert_eq!(file.grace, DEFAULT_GRACE);
+        assert!(file.rules.is_empty());
+    }
+
+    #[test]
+    fn test_parse_schedule_err() {
+        assert!(
+            parse_schedule(
+                "[[rules]]\ncron = \"0 8 * * *\"\norders = []\nset = { modes = \"wild\" }\n"
+            )
+            .is_err()
+        );
+        assert!(parse_schedule("grace = \"abc\"\n").is_err());
+        assert!(parse_schedule("grace = \"9999999999999999h\"\n").is_err());
+    }
+}
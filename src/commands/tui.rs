@@ -0,0 +1,194 @@
+//! tui 子命令：在终端界面中查看订单并修改订单配置
+//!
+//! 界面按固定间隔自动刷新，每次刷新都会保存到本地历史记录。修改配置需要在界面中确认，发送后会立即重新查询，
+//! 与 `--query` 的订单相关操作一样确认设置是否生效。
+
+use crate::commands::history;
+use crate::config::Config;
+use crate::oid_cache::OidCache;
+use crate::pwd::PwdResolver;
+use crate::settings::ConfigChange;
+use crate::tui::{Action, App};
+use crate::{now, parse_interval, parse_order_id, print_error};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Args;
+use hs_order::{HttpConfig, OrderClient};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[derive(Args)]
+pub struct TuiArgs {
+    #[arg(value_name = "ORDER_ID", help = "订单号或 @别名", value_parser = parse_order_id)]
+    order_id: String,
+
+    #[arg(
+        short,
+        long,
+        value_name = "INTERVAL",
+        default_value = "1m",
+        help = "自动刷新间隔",
+        long_help = "自动刷新间隔，支持 s/m/h 后缀（如 30s、10m、1h），不带后缀表示秒，最小为 10 秒，默认为 1 分钟",
+        value_parser = parse_interval
+    )]
+    interval: Duration,
+
+    #[arg(
+        long,
+        help = "只读模式",
+        long_help = "只读模式：不获取战网密码前4位，界面中不能修改订单配置"
+    )]
+    read_only: bool,
+}
+
+/// 执行 tui 子命令
+pub fn run(args: &TuiArgs, config: &Config, http: &HttpConfig) -> Result<ExitCode> {
+    let target = config.resolve(&args.order_id)?;
+    let client = OrderClient::with_config(http).context("初始化订单客户端失败")?;
+
+    // 进入终端界面前获取密码，需要时可以在终端中输入
+    let pwd4 = if args.read_only {
+        None
+    } else {
+        Some(PwdResolver::new(None).resolve(&target)?)
+    };
+
+    // 与其它子命令相同，优先使用配置或缓存中的订单编号查询
+    let mut oid_cache = OidCache::load().unwrap_or_else(|e| {
+        print_error("读取订单编号缓存失败", &e);
+        OidCache::default()
+    });
+    let key = match &target.oid {
+        Some(oid) => oid.as_str(),
+        None => oid_cache.get(&target.order_id).unwrap_or(&target.order_id),
+    };
+    let order = match client.fetch_order(key) {
+        Ok(order) => order,
+        Err(e) => {
+            print_error("查询失败", &anyhow!("{}", e).context("获取订单数据失败"));
+            return Ok(ExitCode::from(1));
+        }
+    };
+    if target.oid.is_none()
+        && let Err(e) = oid_cache.insert(&target.order_id, &order.oid)
+    {
+        print_error("保存订单编号缓存失败", &e);
+    }
+    if let Err(e) = history::save(&order) {
+        print_error("保存历史记录失败", &e);
+    }
+
+    let mut app = App::new(order, args.read_only, args.interval, now());
+    let session = Session {
+        client: &client,
+        pwd4: pwd4.as_deref(),
+    };
+
+    let mut terminal = ratatui::init();
+    let result = session.run(&mut terminal, &mut app, args.interval);
+    ratatui::restore();
+    result?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 终端界面运行期间查询和设置订单所需的信息
+struct Session<'a> {
+    /// 订单客户端
+    client: &'a OrderClient,
+    /// 战网密码前4位，只读模式下为空
+    pwd4: Option<&'a str>,
+}
+
+impl Session<'_> {
+    /// 处理按键和自动刷新，直到退出
+    fn run(&self, terminal: &mut DefaultTerminal, app: &mut App, interval: Duration) -> Result<()> {
+        let mut next_refresh = Instant::now() + interval;
+
+        loop {
+            terminal.draw(|frame| app.render(frame))?;
+
+            let timeout = next_refresh.saturating_duration_since(Instant::now());
+            let action = if event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key),
+                    _ => None,
+                }
+            } else {
+                Some(Action::Refresh)
+            };
+
+            match action {
+                Some(Action::Quit) => return Ok(()),
+                Some(Action::Refresh) => {
+                    app.set_info("正在刷新…");
+                    terminal.draw(|frame| app.render(frame))?;
+                    match self.refresh(app) {
+                        Ok(()) => app.clear_status(),
+                        Err(e) => app.set_error(format!("{:#}", e)),
+                    }
+                }
+                Some(Action::Apply(change)) => {
+                    app.set_info(format!("正在设置{}…", change.describe()));
+                    terminal.draw(|frame| app.render(frame))?;
+                    match self.apply(app, &change) {
+                        Ok(()) => app.set_info(format!("已设置{}", change.describe())),
+                        Err(e) => app.set_error(format!("{:#}", e)),
+                    }
+                }
+                None => continue,
+            }
+
+            next_refresh = Instant::now() + interval;
+        }
+    }
+
+    /// 使用已查询到的订单编号重新查询订单数据并保存到本地历史记录
+    fn refresh(&self, app: &mut App) -> Result<()> {
+        let oid = app.order().oid.clone();
+        let order = self.client.fetch_order(&oid).context("刷新失败")?;
+        app.update(order, now());
+        history::save(app.order()).context("保存历史记录失败")
+    }
+
+    /// 发送设置请求，并重新查询确认设置生效
+    fn apply(&self, app: &mut App, change: &ConfigChange) -> Result<()> {
+        let Some(pwd4) = self.pwd4 else {
+            bail!("只读模式下不能修改订单配置");
+        };
+        let oid = app.order().oid.clone();
+        let client = self.client;
+
+        if let Some(mode) = change.mode {
+            client
+                .set_battle_mode(&oid, pwd4, mode)
+                .context("设置对战模式失败")?;
+        }
+        if let Some(heroes) = change.heroes {
+            client
+                .set_battle_heroes(&oid, pwd4, heroes)
+                .context("设置对战英雄失败")?;
+        }
+        if let Some(auto_claim) = change.auto_claim {
+            client
+                .set_auto_claim(&oid, pwd4, auto_claim)
+                .context("设置自动领取奖励失败")?;
+        }
+        if let Some(pause) = change.pause {
+            client
+                .set_pause(&oid, pwd4, pause)
+                .context("设置暂停上号失败")?;
+        }
+
+        self.refresh(app)?;
+
+        let mismatches = change.mismatches(&app.order().config);
+        if !mismatches.is_empty() {
+            let names: Vec<&str> = mismatches.iter().map(|m| m.name).collect();
+            bail!("设置未生效: {}", names.join("、"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+//! 配置文件模块
+//!
+//! 配置文件为 TOML 格式，可以配置 HTTP 请求选项，为常用订单定义别名（`@别名`），并保存订单号、订单编号、战网密码前4位和默认的显示选项。
+
+use crate::paths;
+use crate::{HttpArgs, OutputFormat, parse_order_id, parse_pwd4, parse_table_size, parse_timeout};
+use anyhow::{Context, Result, anyhow, bail};
+use hs_order::HttpConfig;
+use hs_order::constants::RETRIES_LIMIT;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+/// 配置文件内容
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// HTTP 请求配置
+    pub http: HttpSection,
+    /// 订单别名
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// HTTP 请求配置（未配置的项使用默认值）
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpSection {
+    /// 接口地址
+    pub endpoint: Option<String>,
+    /// 连接超时时间
+    #[serde(deserialize_with = "deserialize_timeout")]
+    pub connect_timeout: Option<Duration>,
+    /// 请求超时时间
+    #[serde(deserialize_with = "deserialize_timeout")]
+    pub timeout: Option<Duration>,
+    /// 请求失败时的最大重试次数
+    #[serde(deserialize_with = "deserialize_retries")]
+    pub retries: Option<u32>,
+    /// 接口数据格式与预期不一致时是否视为错误
+    pub strict: Option<bool>,
+}
+
+/// 订单别名配置
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// 订单号
+    pub order_id: Option<String>,
+    /// 订单编号
+    pub oid: Option<String>,
+    /// 战网密码前4位
+    pub pwd: Option<String>,
+    /// 游戏数据统计表格显示的最大记录条数
+    #[serde(default, deserialize_with = "deserialize_table_size")]
+    pub table_size: Option<usize>,
+    /// 查询结果的输出格式
+    pub output: Option<OutputFormat>,
+    /// 购买的目标奖励等级（与显示的奖励等级一致）
+    pub target_level: Option<u32>,
+}
+
+/// 解析后的订单
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderTarget {
+    /// 命令行中传入的订单号或别名
+    pub input: String,
+    /// 查询时使用的订单号
+    pub order_id: String,
+    /// 已知的订单编号
+    pub oid: Option<String>,
+    /// 配置的战网密码前4位
+    pub pwd: Option<String>,
+}
+
+impl Config {
+    /// 读取配置文件，配置文件不存在时返回空配置
+    pub fn load() -> Result<Self> {
+        let Some(path) = paths::config_path() else {
+            return Ok(Self::default());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content)
+                .with_context(|| format!("解析配置文件 {} 失败", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("读取配置文件 {} 失败", path.display())),
+        }
+    }
+
+    /// 解析配置文件内容
+    fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// 合并命令行选项（包括环境变量）和配置文件中的 HTTP 请求配置，命令行选项优先
+    pub fn http_config(&self, args: &HttpArgs) -> HttpConfig {
+        let default = HttpConfig::default();
+        let http = &self.http;
+
+        HttpConfig {
+            endpoint: args
+                .endpoint
+                .clone()
+                .or_else(|| http.endpoint.clone())
+                .unwrap_or(default.endpoint),
+            connect_timeout: args
+                .connect_timeout
+                .or(http.connect_timeout)
+                .unwrap_or(default.connect_timeout),
+            timeout: args.timeout.or(http.timeout).unwrap_or(default.timeout),
+            max_retries: args.retries.or(http.retries).unwrap_or(default.max_retries),
+            strict: args.strict || http.strict.unwrap_or(default.strict),
+        }
+    }
+
+    /// 获取别名对应的配置，`input` 不是别名时返回 `None`
+    pub fn profile(&self, input: &str) -> Option<&Profile> {
+        input
+            .strip_prefix('@')
+            .and_then(|name| self.profiles.get(name))
+    }
+
+    /// 将命令行中传入的订单号或别名解析为订单
+    pub fn resolve(&self, input: &str) -> Result<OrderTarget> {
+        let Some(name) = input.strip_prefix('@') else {
+            return Ok(OrderTarget {
+                input: input.to_string(),
+                order_id: input.to_string(),
+                oid: None,
+                pwd: None,
+            });
+        };
+
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| anyhow!("配置文件中没有订单别名: {}", input))?;
+
+        let order_id = profile
+            .order_id
+            .as_ref()
+            .or(profile.oid.as_ref())
+            .ok_or_else(|| anyhow!("订单别名 {} 缺少 order_id 或 oid", input))?;
+
+        for id in [&profile.order_id, &profile.oid].into_iter().flatten() {
+            if id.starts_with('@') {
+                bail!("订单别名 {} 的订单号不能是别名", input);
+            }
+            parse_order_id(id).map_err(|e| anyhow!("订单别名 {}: {}", input, e))?;
+        }
+
+        if let Some(pwd) = &profile.pwd {
+            parse_pwd4(pwd).map_err(|e| anyhow!("订单别名 {}: {}", input, e))?;
+        }
+
+        Ok(OrderTarget {
+            input: input.to_string(),
+            order_id: order_id.clone(),
+            oid: profile.oid.clone(),
+            pwd: profile.pwd.clone(),
+        })
+    }
+}
+
+/// 反序列化表格显示的最大记录条数（整数或 "all"）
+fn deserialize_table_size<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<usize>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum TableSize {
+        Num(usize),
+        Text(String),
+    }
+
+    match TableSize::deserialize(deserializer)? {
+        TableSize::Num(num) => Ok(Some(num)),
+        TableSize::Text(text) => parse_table_size(&text)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// 反序列化超时时间（秒数或带 s/ms 后缀的字符串）
+fn deserialize_timeout<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Duration>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timeout {
+        Secs(u64),
+        Text(String),
+    }
+
+    match Timeout::deserialize(deserializer)? {
+        Timeout::Secs(secs) => parse_timeout(&secs.to_string()),
+        Timeout::Text(text) => parse_timeout(&text),
+    }
+    .map(Some)
+    .map_err(serde::de::Error::custom)
+}
+
+/// 反序列化请求最大重试次数（不超过 [`RETRIES_LIMIT`]）
+fn deserialize_retries<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<u32>, D::Error> {
+    match u32::deserialize(deserializer)? {
+        retries if retries <= RETRIES_LIMIT => Ok(Some(retries)),
+        _ => Err(serde::de::Error::custom(format!(
+            "重试次数不能大于 {}",
+            RETRIES_LIMIT
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[profiles.main]
+order_id = "1234567890123456789"
+pwd = "zwmm"
+table_size = "all"
+output = "json"
+
+[profiles.alt]
+oid = "2025010100013333123456"
+table_size = 5
+target_level = 60
+"#;
+
+    #[test]
+    fn test_config_parse() {
+        let config = Config::parse(CONFIG).unwrap();
+        let main = config.profile("@main").unwrap();
+        assert_eq!(main.table_size, Some(usize::MAX));
+        assert_eq!(main.output, Some(OutputFormat::Json));
+        assert_eq!(config.profile("@alt").unwrap().table_size, Some(5));
+        assert_eq!(config.profile("@alt").unwrap().target_level, Some(60));
+        assert!(config.profile("main").is_none());
+    }
+
+    #[test]
+    fn test_config_http() {
+        let config = Config::parse(
+            "[http]\nendpoint = \"http://127.0.0.1:8080/hs.php\"\ntimeout = \"1500ms\"\nretries = 0\nstrict = true\n",
+        )
+        .unwrap();
+
+        let http = config.http_config(&HttpArgs::default());
+        assert_eq!(http.endpoint, "http://127.0.0.1:8080/hs.php");
+        assert_eq!(http.timeout, Duration::from_millis(1500));
+        assert_eq!(http.max_retries, 0);
+        assert_eq!(http.connect_timeout, HttpConfig::default().connect_timeout);
+        assert!(http.strict);
+
+        let args = HttpArgs {
+            endpoint: None,
+            connect_timeout: Some(Duration::from_millis(500)),
+            timeout: Some(Duration::from_secs(9)),
+            retries: Some(4),
+            strict: false,
+        };
+        let http = config.http_config(&args);
+        assert_eq!(http.connect_timeout, Duration::from_millis(500));
+        assert_eq!(http.timeout, Duration::from_secs(9));
+        assert_eq!(http.max_retries, 4);
+
+        assert!(Config::parse("[http]\nretries = 40\n").is_err());
+    }
+
+    #[test]
+    fn test_config_parse_unknown_field() {
+        assert!(Config::parse("[profiles.main]\npassword = \"zwmm\"\n").is_err());
+    }
+
+    #[test]
+    fn test_config_resolve() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let main = config.resolve("@main").unwrap();
+        assert_eq!(main.order_id, "1234567890123456789");
+        assert_eq!(main.oid, None);
+        assert_eq!(main.pwd.as_deref(), Some("zwmm"));
+
+        let alt = config.resolve("@alt").unwrap();
+        assert_eq!(alt.order_id, "2025010100013333123456");
+        assert_eq!(alt.oid.as_deref(), Some("2025010100013333123456"));
+
+        let plain = config.resolve("123").unwrap();
+        assert_eq!(plain.order_id, "123");
+        assert_eq!(plain.pwd, None);
+    }
+
+    #[test]
+    fn test_config_resolve_err() {
+        let config = Config::parse(
+            "[profiles.empty]\npwd = \"zwmm\"\n\n[profiles.bad]\norder_id = \"123\"\npwd = \"zw\"\n",
+        )
+        .unwrap();
+        let err = |input| config.resolve(input).unwrap_err().to_string();
+        assert_eq!(err("@none"), "配置文件中没有订单别名: @none");
+        assert_eq!(err("@empty"), "订单别名 @empty 缺少 order_id 或 oid");
+        assert_eq!(err("@bad"), "订单别名 @bad: 战网密码前4位必须为4个字符");
+    }
+}
@@ -2,15 +2,11 @@
 
 use std::time::Duration;
 
-/// API请求地址
+/// 默认的API请求地址（Host、Origin 和 Referer 请求头由该地址得出）
 pub const API_URL: &str = "http://139.155.71.163:1000/training/hs.php";
-/// 主机地址（Host）
-pub const HEADER_HOST: &str = "139.155.71.163:1000";
-/// 来源地址（Origin）
-pub const HEADER_ORIGIN: &str = "http://139.155.71.163:1000";
-/// 连接超时时间
+/// 默认的连接超时时间
 pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
-/// 请求超时时间
+/// 默认的请求超时时间
 pub const TIMEOUT: Duration = Duration::from_secs(5);
 /// 常见浏览器 UA 列表
 pub const UA_LIST: &[&str] = &[
@@ -22,16 +18,18 @@ pub const UA_LIST: &[&str] = &[
     "Mozilla/5.0 (X11; Linux x86_64; rv:132.0) Gecko/20100101 Firefox/132.0",
 ];
 
-/// 请求最大重试次数
+/// 默认的请求最大重试次数
 pub const MAX_RETRIES: u32 = 2;
+/// 请求最大重试次数的上限
+pub const RETRIES_LIMIT: u32 = 10;
 /// 指数退避的基准毫秒数
 pub const RETRY_BASE_MS: u64 = 200;
+/// 指数退避的最长等待毫秒数
+pub const MAX_BACKOFF_MS: u64 = 5_000;
 /// 成功响应代码
 pub const SUCCESS_CODE: i32 = 1;
 
 // 数据索引常量
-/// 基本信息数组大小
-pub const BASIC_INFO_SIZE: usize = 10;
 /// 金币记录索引
 pub const GOLD_RECORDS_INDEX: usize = 10;
 /// 经验记录索引
@@ -44,6 +42,14 @@ pub const DATE_INDEX: usize = 3;
 pub const WINS_INDEX: usize = 4;
 /// 失败次数索引
 pub const LOSSES_INDEX: usize = 5;
+/// 服务器区域索引
+pub const REGION_INDEX: usize = 7;
+/// 最近一次上号时间索引
+pub const LAST_LOGIN_INDEX: usize = 8;
+/// 字符串 ID 列表索引
+pub const ID_LIST_INDEX: usize = 13;
+/// dldata 数组中含义已知或已观察到的项数
+pub const DLDATA_LEN: usize = 16;
 
 /// 英雄职业名称
 pub const HERO_NAMES: &[&str] = &[
@@ -59,9 +65,26 @@ pub const HERO_NAMES: &[&str] = &[
     "恶魔猎手",
     "死亡骑士",
 ];
+/// 英雄职业英文标识（与 [`HERO_NAMES`] 一一对应）
+pub const HERO_IDS: &[&str] = &[
+    "warrior",
+    "shaman",
+    "rogue",
+    "paladin",
+    "hunter",
+    "druid",
+    "warlock",
+    "mage",
+    "priest",
+    "demon_hunter",
+    "death_knight",
+];
 /// 最大英雄掩码值
 pub const MAX_HERO_MASK: u32 = (1 << HERO_NAMES.len()) - 1;
 
+/// 通行证每级所需经验
+pub const EXP_PER_LEVEL: i64 = 1500;
+
 // 订单状态
 /// 已完成订单状态
 pub const STATUS_FINISHED: &str = "1";
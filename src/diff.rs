@@ -0,0 +1,198 @@
+//! 订单变化比较模块
+//!
+//! 比较同一订单的前后两次查询结果，找出新出现的记录和发生变化的订单状态。
+
+use crate::models::{BattleRecord, ExpRecord, GoldRecord, Order, OrderStatus};
+use serde::Serialize;
+
+/// 订单变化事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum OrderEvent {
+    /// 新的金币记录
+    Gold(GoldRecord),
+    /// 新的经验记录
+    Exp(ExpRecord),
+    /// 新的对战记录
+    Battle(BattleRecord),
+    /// 奖励等级变化（从 0 开始，显示时需要 +1）
+    LevelChanged {
+        /// 变化前的等级
+        from: i64,
+        /// 变化后的等级
+        to: i64,
+    },
+    /// 订单状态变化
+    StatusChanged {
+        /// 变化前的状态
+        from: OrderStatus,
+        /// 变化后的状态
+        to: OrderStatus,
+    },
+    /// 备注变化
+    RemarkChanged {
+        /// 变化前的备注
+        from: String,
+        /// 变化后的备注
+        to: String,
+    },
+}
+
+/// 比较前后两次查询结果，返回按时间先后排列的变化事件
+///
+/// 新记录是指出现在 `next` 中但不在 `prev` 中的记录；因接口只返回最近的部分记录而从 `prev` 中消失的记录不会产生事件。
+pub fn diff_orders(prev: &Order, next: &Order) -> Vec<OrderEvent> {
+    let mut events = Vec::new();
+
+    if prev.status != next.status {
+        events.push(OrderEvent::StatusChanged {
+            from: prev.status,
+            to: next.status,
+        });
+    }
+
+    if prev.reward_level != next.reward_level {
+        events.push(OrderEvent::LevelChanged {
+            from: prev.reward_level,
+            to: next.reward_level,
+        });
+    }
+
+    if prev.remark != next.remark {
+        events.push(OrderEvent::RemarkChanged {
+            from: prev.remark.clone(),
+            to: next.remark.clone(),
+        });
+    }
+
+    let prev_data = &prev.dldata;
+    let next_data = &next.dldata;
+
+    events.extend(
+        new_records(&prev_data.gold_records, &next_data.gold_records).map(OrderEvent::Gold),
+    );
+    events.extend(new_records(&prev_data.exp_records, &next_data.exp_records).map(OrderEvent::Exp));
+    events.extend(
+        new_records(&prev_data.battle_records, &next_data.battle_records).map(OrderEvent::Battle),
+    );
+
+    events
+}
+
+/// 找出新出现的记录（接口返回的记录按时间倒序排列，这里按时间先后返回）
+fn new_records<'a, T: PartialEq + Clone>(
+    prev: &'a [T],
+    next: &'a [T],
+) -> impl Iterator<Item = T> + 'a {
+    next.iter()
+        .rev()
+        .filter(|record| !prev.contains(record))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BattleResult, DlData, OrderConfig, ServiceDetails};
+    use crate::utils::to_shanghai_time;
+
+    fn order(battles: Vec<BattleRecord>) -> Order {
+        Order {
+            oid: "2025010100013333123456".into(),
+            deadline: None,
+            status: OrderStatus::Running,
+            gold: 0,
+            packs: 0,
+            reward_level: 43,
+            num7: 0,
+            num8: 0,
+            details: String::new(),
+            service: ServiceDetails::default(),
+            remark: String::new(),
+            config: OrderConfig::default(),
+            dldata: DlData {
+                gold_records: vec![],
+                exp_records: vec![],
+                battle_records: battles,
+                ..Default::default()
+            },
+            warnings: vec![],
+        }
+    }
+
+    fn battle(start: i64, result: BattleResult) -> BattleRecord {
+        BattleRecord {
+            time: to_shanghai_time(start),
+            end_time: to_shanghai_time(start + 600),
+            result,
+            exp: 10,
+        }
+    }
+
+    #[test]
+    fn test_diff_orders_unchanged() {
+        let prev = order(vec![battle(1762927893, BattleResult::Loss)]);
+        assert!(diff_orders(&prev, &prev.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_orders_new_battles_in_time_order() {
+        let prev = order(vec![battle(1762920000, BattleResult::Loss)]);
+        let next = order(vec![
+            battle(1762940000, BattleResult::Win),
+            battle(1762930000, BattleResult::Unknown),
+            battle(1762920000, BattleResult::Loss),
+        ]);
+        let events = diff_orders(&prev, &next);
+        assert_eq!(
+            events,
+            vec![
+                OrderEvent::Battle(battle(1762930000, BattleResult::Unknown)),
+                OrderEvent::Battle(battle(1762940000, BattleResult::Win)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_orders_dropped_records_ignored() {
+        let prev = order(vec![
+            battle(1762930000, BattleResult::Win),
+            battle(1762920000, BattleResult::Loss),
+        ]);
+        let next = order(vec![battle(1762930000, BattleResult::Win)]);
+        assert!(diff_orders(&prev, &next).is_empty());
+    }
+
+    #[test]
+    fn test_diff_orders_state_changes() {
+        let prev = order(vec![]);
+        let mut next = order(vec![]);
+        next.status = OrderStatus::Finished;
+        next.reward_level = 44;
+        next.remark = "开始酒馆对战".into();
+        let events = diff_orders(&prev, &next);
+        assert_eq!(
+            events,
+            vec![
+                OrderEvent::StatusChanged {
+                    from: OrderStatus::Running,
+                    to: OrderStatus::Finished,
+                },
+                OrderEvent::LevelChanged { from: 43, to: 44 },
+                OrderEvent::RemarkChanged {
+                    from: String::new(),
+                    to: "开始酒馆对战".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_order_event_serialize() {
+        let v = serde_json::to_value(OrderEvent::LevelChanged { from: 43, to: 44 }).unwrap();
+        assert_eq!(
+            v,
+            serde_json::json!({"event": "level_changed", "from": 43, "to": 44})
+        );
+    }
+}
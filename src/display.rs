@@ -1,13 +1,277 @@
 //! 显示模块
 
-use crate::constants::*;
-use crate::models::{BattleRecord, DlData, ExpRecord, GoldRecord, OrderConfig, OrderData};
-use crate::parser::parse_battle_heroes;
-use crate::utils::{format_signed, parse_unsigned_int};
-use anyhow::Result;
+use crate::now;
+use crate::settings::FieldDiff;
+use chrono::NaiveDate;
 use colored::*;
+use hs_order::OrderEvent;
+use hs_order::chart::{daily_battles, exp_curve, gold_curve, line_chart, sparkline};
+use hs_order::history::{History, Snapshot};
+use hs_order::models::{
+    BattleMode, BattleRecord, BattleResult, DlData, ExpRecord, GoldRecord, HeroSet, Order,
+    OrderStatus, Timestamp,
+};
+use hs_order::progress::Progress;
+use hs_order::raw::{Change, RawField};
+use hs_order::report::{DailyStats, ServiceCheck, Session, check_service};
+use hs_order::schema::SchemaWarning;
+use hs_order::stats::{
+    calculate_battle_stats, calculate_exp_total, calculate_gold_totals, calculate_win_rate,
+};
+use hs_order::utils::{format_signed, format_time, to_shanghai_time};
 use tabled::{Table, Tabled};
 
+/// 订单摘要表格行
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "订单号")]
+    order_id: String,
+    #[tabled(rename = "订单编号")]
+    oid: String,
+    #[tabled(rename = "状态")]
+    status: &'static str,
+    #[tabled(rename = "对战模式")]
+    mode: &'static str,
+    #[tabled(rename = "奖励等级")]
+    level: String,
+    #[tabled(rename = "金币")]
+    gold: String,
+    #[tabled(rename = "今日对战")]
+    today_battles: String,
+    #[tabled(rename = "截止时间")]
+    deadline: String,
+}
+
+impl SummaryRow {
+    fn new(order_id: &str, order: Option<&Order>) -> Self {
+        match order {
+            Some(order) => Self {
+                order_id: order_id.to_string(),
+                oid: order.oid.clone(),
+                status: order_status_name(order.status),
+                mode: battle_mode_name(order.config.mode),
+                level: (order.reward_level + 1).to_string(),
+                gold: order.gold.to_string(),
+                today_battles: order.dldata.today_battles.to_string(),
+                deadline: format_time(order.deadline.as_ref()),
+            },
+            None => Self {
+                order_id: order_id.to_string(),
+                oid: "-".to_string(),
+                status: "查询失败",
+                mode: "-",
+                level: "-".to_string(),
+                gold: "-".to_string(),
+                today_battles: "-".to_string(),
+                deadline: "-".to_string(),
+            },
+        }
+    }
+}
+
+/// 金币记录表格行
+#[derive(Tabled)]
+struct GoldRow {
+    #[tabled(rename = "时间")]
+    time: String,
+    #[tabled(rename = "金币变化")]
+    gold_change: String,
+    #[tabled(rename = "卡包变化")]
+    pack_change: String,
+}
+
+impl From<&GoldRecord> for GoldRow {
+    fn from(record: &GoldRecord) -> Self {
+        Self {
+            time: format_time(record.time.as_ref()),
+            gold_change: format_signed(record.gold_change),
+            pack_change: format_signed(record.pack_change),
+        }
+    }
+}
+
+/// 经验记录表格行
+#[derive(Tabled)]
+struct ExpRow {
+    #[tabled(rename = "时间")]
+    time: String,
+    #[tabled(rename = "经验变化")]
+    exp_change: String,
+    #[tabled(rename = "等级")]
+    level: i64,
+    #[tabled(rename = "总经验")]
+    total_exp: i64,
+    #[tabled(rename = "当前等级经验")]
+    current_level_exp: i64,
+}
+
+impl From<&ExpRecord> for ExpRow {
+    fn from(record: &ExpRecord) -> Self {
+        Self {
+            time: format_time(record.time.as_ref()),
+            exp_change: format_signed(record.exp_change),
+            level: record.level,
+            total_exp: record.total_exp,
+            current_level_exp: record.current_level_exp,
+        }
+    }
+}
+
+/// 对战记录表格行
+#[derive(Tabled)]
+struct BattleRow {
+    #[tabled(rename = "时间")]
+    time: String,
+    #[tabled(rename = "结束时间")]
+    end_time: String,
+    #[tabled(rename = "时长")]
+    duration: String,
+    #[tabled(rename = "结果")]
+    result: &'static str,
+    #[tabled(rename = "经验")]
+    exp: String,
+}
+
+impl From<&BattleRecord> for BattleRow {
+    fn from(record: &BattleRecord) -> Self {
+        Self {
+            time: format_time(record.time.as_ref()),
+            end_time: record
+                .end_time
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |time| format_time(Some(time))),
+            duration: record.duration().map_or_else(
+                || "-".to_string(),
+                |d| format!("{} 分 {:02} 秒", d.num_minutes(), d.num_seconds() % 60),
+            ),
+            result: battle_result_name(record.result),
+            exp: format_signed(record.exp),
+        }
+    }
+}
+
+/// 按日期汇总的表格行
+#[derive(Tabled)]
+struct DailyRow {
+    #[tabled(rename = "日期")]
+    date: String,
+    #[tabled(rename = "对战")]
+    battles: usize,
+    #[tabled(rename = "胜/负/未知")]
+    results: String,
+    #[tabled(rename = "胜率")]
+    win_rate: String,
+    #[tabled(rename = "游戏时长")]
+    play_time: String,
+    #[tabled(rename = "上号时段")]
+    period: String,
+    #[tabled(rename = "经验")]
+    exp: String,
+    #[tabled(rename = "每小时经验")]
+    exp_per_hour: String,
+    #[tabled(rename = "金币")]
+    gold: String,
+    #[tabled(rename = "卡包")]
+    packs: String,
+}
+
+impl From<&DailyStats> for DailyRow {
+    fn from(stats: &DailyStats) -> Self {
+        let clock = |time: &Timestamp| {
+            if time.date_naive() == stats.date {
+                time.format("%H:%M").to_string()
+            } else {
+                time.format("次日 %H:%M").to_string()
+            }
+        };
+
+        Self {
+            date: stats.date.format("%Y-%m-%d").to_string(),
+            battles: stats.battles,
+            results: format!("{}/{}/{}", stats.wins, stats.losses, stats.unknowns),
+            win_rate: format!("{}%", stats.win_rate),
+            play_time: format_duration(stats.play_secs),
+            period: match (&stats.first_start, &stats.last_end) {
+                (Some(start), Some(end)) => format!("{} ~ {}", clock(start), clock(end)),
+                (Some(start), None) => format!("{} ~", clock(start)),
+                _ => "-".to_string(),
+            },
+            exp: format_signed(stats.exp),
+            exp_per_hour: stats
+                .exp_per_hour
+                .map_or_else(|| "-".to_string(), |exp| format!("{:.0}", exp)),
+            gold: format_signed(stats.gold),
+            packs: format_signed(stats.packs),
+        }
+    }
+}
+
+/// 游戏时段表格行
+#[derive(Tabled)]
+struct SessionRow {
+    #[tabled(rename = "开始时间")]
+    start: String,
+    #[tabled(rename = "结束时间")]
+    end: String,
+    #[tabled(rename = "对局")]
+    battles: usize,
+    #[tabled(rename = "胜/负")]
+    results: String,
+    #[tabled(rename = "对局时长")]
+    play_time: String,
+    #[tabled(rename = "时段时长")]
+    span: String,
+    #[tabled(rename = "经验")]
+    exp: String,
+}
+
+impl From<&Session> for SessionRow {
+    fn from(session: &Session) -> Self {
+        Self {
+            start: format_time(Some(&session.start)),
+            end: format_time(Some(&session.end)),
+            battles: session.battles,
+            results: format!("{}/{}", session.wins, session.losses),
+            play_time: format_duration(session.play_secs),
+            span: format_duration(session.span_secs()),
+            exp: format_signed(session.exp),
+        }
+    }
+}
+
+/// 状态快照表格行
+#[derive(Tabled)]
+struct SnapshotRow {
+    #[tabled(rename = "保存时间")]
+    recorded_at: String,
+    #[tabled(rename = "状态")]
+    status: &'static str,
+    #[tabled(rename = "对战模式")]
+    mode: &'static str,
+    #[tabled(rename = "奖励等级")]
+    level: i64,
+    #[tabled(rename = "金币")]
+    gold: i64,
+    #[tabled(rename = "卡包")]
+    packs: i64,
+    #[tabled(rename = "备注")]
+    remark: String,
+}
+
+impl From<&Snapshot> for SnapshotRow {
+    fn from(snapshot: &Snapshot) -> Self {
+        Self {
+            recorded_at: format_time(Some(&snapshot.recorded_at)),
+            status: order_status_name(snapshot.status),
+            mode: battle_mode_name(snapshot.config.mode),
+            level: snapshot.reward_level + 1,
+            gold: snapshot.gold,
+            packs: snapshot.packs,
+            remark: snapshot.remark.clone(),
+        }
+    }
+}
+
 /// 打印程序标题
 pub fn print_header() {
     println!("{}", "=== 炉石传说代练订单助手 ===".bright_cyan().bold());
@@ -20,29 +284,39 @@ pub fn print_line() {
 }
 
 /// 显示订单基本信息
-pub fn display_order_info(order: &OrderData, config: &OrderConfig, dldata: &DlData) -> Result<()> {
+pub fn display_order_info(order: &Order) {
+    let config = &order.config;
+
     println!("{}", "订单基本信息".bright_blue().bold());
     print_line();
 
     println!("订单编号: {}", order.oid.bright_cyan());
-    println!("截止时间: {}", order.edate.bright_white());
     println!(
-        "订单状态: {}",
-        get_order_status(&order.finish, &order.banned)
+        "截止时间: {}",
+        format_time(order.deadline.as_ref()).bright_white()
     );
+    println!("订单状态: {}", get_order_status(order.status));
+    match (&order.service.window, order.details.trim()) {
+        (Some(window), _) => println!("上号时间: {}", window.to_string().bright_white()),
+        (None, "") => {}
+        (None, details) => println!("上号时间: {}", details.bright_white()),
+    }
+    if let Some(last_login) = &order.service.last_login {
+        println!("最近上号: {}", format_time(Some(last_login)).bright_white());
+    }
+    println!("对战模式: {}", get_battle_mode_text(config.mode));
+    println!("对战英雄: {}", get_battle_heroes_text(config.heroes));
+    println!("自动领取: {}", get_auto_claim_text(config.auto_claim));
+    println!("暂停上号: {}", get_pause_text(config.pause));
+    println!("金币数量: {} 枚", order.gold.to_string().bright_yellow());
+    println!("卡包数量: {} 包", order.packs.to_string().bright_blue());
     println!(
-        "对战模式: {}",
-        get_battle_mode_text(config.battlemode.as_deref().unwrap_or(MODE_CASUAL))
+        "奖励等级: {} 级",
+        (order.reward_level + 1).to_string().bright_purple()
     );
-    println!("对战英雄: {}", get_battle_heroes_text(&config.battleheroes));
-    println!("自动领取: {}", get_auto_claim_text(&config.auto));
-    println!("金币数量: {} 枚", order.num1.bright_yellow());
-    println!("卡包数量: {} 包", order.num2.bright_blue());
-    let reward_level = order.num3.parse::<i64>().unwrap_or(0) + 1;
-    println!("奖励等级: {} 级", reward_level.to_string().bright_purple());
     println!(
         "今日对战: {} 场",
-        dldata.today_battles.to_string().bright_cyan()
+        order.dldata.today_battles.to_string().bright_cyan()
     );
 
     if !order.remark.is_empty() {
@@ -50,69 +324,657 @@ pub fn display_order_info(order: &OrderData, config: &OrderConfig, dldata: &DlDa
     }
 
     println!();
-    Ok(())
+
+    display_schema_warnings(&order.warnings);
+}
+
+/// 显示接口数据格式警告，没有警告时不显示
+pub fn display_schema_warnings(warnings: &[SchemaWarning]) {
+    if warnings.is_empty() {
+        return;
+    }
+
+    println!("{}", "接口数据格式警告".bright_yellow().bold());
+    print_line();
+    for warning in warnings {
+        println!("{}", format!("- {}", warning).yellow());
+    }
+    println!(
+        "接口可能已经更新，部分数据可能缺失或不准确；可以通过 inspect --raw 查看原始数据，传入 --strict 时视为错误"
+    );
+    println!();
+}
+
+/// 显示进度预估：按日均经验预估截止时的奖励等级，`target_level` 为购买的目标奖励等级（与显示一致）
+pub fn display_progress(order: &Order, target_level: Option<u32>) {
+    println!("{}", "进度预估".bright_green().bold());
+    print_line();
+
+    let now = now();
+    let Some(progress) = Progress::estimate(order, &now) else {
+        println!("经验记录或截止时间不足，无法预估进度");
+        println!();
+        return;
+    };
+
+    println!(
+        "日均经验: {} 点（根据最近 {:.1} 天的经验记录）",
+        format!("{:.0}", progress.daily_exp).bright_purple(),
+        progress.sample_days
+    );
+    println!("剩余时间: {:.1} 天", progress.remaining_days);
+    println!(
+        "预计等级: {} 级（截止时）",
+        (progress.projected_level + 1).to_string().bright_purple()
+    );
+
+    if let Some(target) = target_level {
+        // 目标等级与显示一致，从 1 开始
+        let level = i64::from(target) - 1;
+        println!("目标等级: {} 级", target.to_string().bright_white());
+
+        if progress.reaches(level) {
+            println!(
+                "{}",
+                "按当前进度可以在截止时间前达到目标等级".bright_green()
+            );
+        } else {
+            let required = progress
+                .required_daily_exp(level)
+                .map(|exp| format!("，需要日均 {:.0} 点", exp))
+                .unwrap_or_default();
+            println!(
+                "{} 按当前进度预计无法在截止时间前达到目标等级，还需 {} 点经验{}",
+                "警告".bright_red().bold(),
+                progress.exp_needed(level).to_string().bright_red(),
+                required
+            );
+        }
+    }
+
+    println!();
+}
+
+/// 按约定的每日上号时间段核对订单的对战记录，没有上号时间段时不显示
+pub fn display_order_service(order: &Order) {
+    let Some(window) = order.service.window else {
+        return;
+    };
+
+    let now = now();
+    let check = check_service(
+        window,
+        &order.dldata.battle_records,
+        &now,
+        order.deadline.as_ref(),
+    );
+    display_service_check(&check);
+}
+
+/// 显示上号时间段的核对结果
+pub fn display_service_check(check: &ServiceCheck) {
+    println!("{}", "上号核对".bright_green().bold());
+    print_line();
+
+    println!("约定时间: {}", check.window.to_string().bright_white());
+
+    if check.is_ok() {
+        println!("{}", "对战都在约定时间内，没有未上号的日期".bright_green());
+        println!();
+        return;
+    }
+
+    if !check.outside.is_empty() {
+        println!(
+            "{} 约定时间外的对战 {} 场",
+            "警告".bright_red().bold(),
+            check.outside.len().to_string().bright_red()
+        );
+    }
+
+    if !check.idle_days.is_empty() {
+        println!(
+            "{} 没有上号的日期（共 {} 天）: {}",
+            "警告".bright_red().bold(),
+            check.idle_days.len().to_string().bright_red(),
+            format_date_ranges(&check.idle_days).bright_red()
+        );
+    }
+
+    println!();
+    display_records_table::<_, BattleRow>(&check.outside, "约定时间外的对战", usize::MAX);
+}
+
+/// 显示监控时检测到的订单变化事件
+pub fn display_order_event(event: &OrderEvent) {
+    let now = now();
+    let prefix = format!("[{}]", format_time(Some(&now))).bright_black();
+
+    match event {
+        OrderEvent::Gold(record) => println!(
+            "{} {} {} 金币 {} 卡包 {}",
+            prefix,
+            "新金币记录".bright_yellow(),
+            format_time(record.time.as_ref()),
+            format_signed(record.gold_change).bright_green(),
+            format_signed(record.pack_change).bright_blue()
+        ),
+        OrderEvent::Exp(record) => println!(
+            "{} {} {} 经验 {} 等级 {} 当前等级经验 {}",
+            prefix,
+            "新经验记录".bright_purple(),
+            format_time(record.time.as_ref()),
+            format_signed(record.exp_change).bright_purple(),
+            record.level,
+            record.current_level_exp
+        ),
+        OrderEvent::Battle(record) => println!(
+            "{} {} {} {} 经验 {}",
+            prefix,
+            "新对战记录".bright_red(),
+            format_time(record.time.as_ref()),
+            get_battle_result_text(record.result),
+            format_signed(record.exp).bright_purple()
+        ),
+        OrderEvent::LevelChanged { from, to } => println!(
+            "{} {} {} 级 → {} 级",
+            prefix,
+            "奖励等级变化".bright_cyan(),
+            from + 1,
+            (to + 1).to_string().bright_purple()
+        ),
+        OrderEvent::StatusChanged { from, to } => println!(
+            "{} {} {} → {}",
+            prefix,
+            "订单状态变化".bright_cyan(),
+            order_status_name(*from),
+            get_order_status(*to)
+        ),
+        OrderEvent::RemarkChanged { to, .. } => println!(
+            "{} {} {}",
+            prefix,
+            "备注更新".bright_cyan(),
+            to.bright_white()
+        ),
+    }
+}
+
+/// 显示设置前后的配置差异
+pub fn display_config_diff(diffs: &[FieldDiff]) {
+    if diffs.is_empty() {
+        println!("  {}", "配置没有变化".bright_black());
+        return;
+    }
+
+    for diff in diffs {
+        println!(
+            "  {}: {} → {}",
+            diff.name,
+            diff.before.bright_red(),
+            diff.after.bright_green()
+        );
+    }
+}
+
+/// 获取订单状态名称
+pub fn order_status_name(status: OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::Finished => "已完成",
+        OrderStatus::Running => "进行中",
+        OrderStatus::Banned => "已终止",
+        OrderStatus::Unknown => "未知状态",
+    }
+}
+
+/// 显示多个订单的摘要表格
+pub fn display_batch_summary(results: &[(String, hs_order::Result<Order>)]) {
+    println!("{}", "订单摘要".bright_blue().bold());
+    print_line();
+
+    let rows: Vec<SummaryRow> = results
+        .iter()
+        .map(|(order_id, result)| SummaryRow::new(order_id, result.as_ref().ok()))
+        .collect();
+    println!("{}", Table::new(rows));
+
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    println!(
+        "共 {} 个订单，成功 {} 个，失败 {} 个",
+        results.len().to_string().bright_white(),
+        (results.len() - failed).to_string().bright_green(),
+        failed.to_string().bright_red()
+    );
+    println!();
+}
+
+/// 获取对战模式名称
+pub fn battle_mode_name(mode: BattleMode) -> &'static str {
+    match mode {
+        BattleMode::Casual => "休闲模式",
+        BattleMode::Standard => "标准模式",
+        BattleMode::Wild => "狂野模式",
+        BattleMode::Twist => "幻变模式",
+        BattleMode::Battlegrounds => "酒馆战棋",
+        BattleMode::Unknown => "未知模式",
+    }
+}
+
+/// 获取对战英雄名称列表文本
+pub fn battle_heroes_name(heroes: HeroSet) -> String {
+    if heroes.is_empty() {
+        "无".to_string()
+    } else if heroes.is_all() {
+        "全部".to_string()
+    } else {
+        heroes.names().join(", ")
+    }
+}
+
+/// 获取对战结果名称
+pub fn battle_result_name(result: BattleResult) -> &'static str {
+    match result {
+        BattleResult::Win => "胜利",
+        BattleResult::Loss => "失败",
+        BattleResult::Unknown => "未知",
+    }
+}
+
+/// 获取对战结果文本
+fn get_battle_result_text(result: BattleResult) -> ColoredString {
+    let name = battle_result_name(result);
+
+    match result {
+        BattleResult::Win => name.bright_green(),
+        BattleResult::Loss => name.bright_red(),
+        BattleResult::Unknown => name.bright_magenta(),
+    }
 }
 
 /// 获取订单状态文本
-fn get_order_status(finish: &str, banned: &str) -> ColoredString {
-    match (finish, banned) {
-        (STATUS_FINISHED, STATUS_RUNNING) => "已完成".bright_green(),
-        (STATUS_RUNNING, STATUS_RUNNING) => "进行中".bright_blue(),
-        (_, STATUS_BANNED) => "已终止".bright_red(),
-        _ => "未知状态".bright_magenta(),
+fn get_order_status(status: OrderStatus) -> ColoredString {
+    let name = order_status_name(status);
+
+    match status {
+        OrderStatus::Finished => name.bright_green(),
+        OrderStatus::Running => name.bright_blue(),
+        OrderStatus::Banned => name.bright_red(),
+        OrderStatus::Unknown => name.bright_magenta(),
     }
 }
 
 /// 获取对战模式文本
-fn get_battle_mode_text(battlemode: &str) -> ColoredString {
-    match battlemode {
-        MODE_CASUAL => "休闲模式".bright_yellow(),
-        MODE_STANDARD => "标准模式".bright_yellow(),
-        MODE_WILD => "狂野模式".bright_yellow(),
-        MODE_TWIST => "幻变模式".bright_yellow(),
-        MODE_BATTLEGROUNDS => "酒馆战棋".bright_yellow(),
-        _ => format!("未知模式({})", battlemode).bright_magenta(),
+fn get_battle_mode_text(mode: BattleMode) -> ColoredString {
+    match mode {
+        BattleMode::Unknown => battle_mode_name(mode).bright_magenta(),
+        _ => battle_mode_name(mode).bright_yellow(),
     }
 }
 
 /// 获取对战英雄文本
-fn get_battle_heroes_text(battleheroes: &Option<String>) -> ColoredString {
-    match battleheroes {
-        Some(heroes_str) => {
-            if heroes_str.is_empty() {
-                return "无".bright_yellow();
+fn get_battle_heroes_text(heroes: HeroSet) -> ColoredString {
+    let text = battle_heroes_name(heroes);
+
+    if heroes.is_empty() {
+        text.bright_yellow()
+    } else if heroes.is_all() {
+        text.bright_blue()
+    } else {
+        text.bright_white()
+    }
+}
+
+/// 获取自动领取文本
+fn get_auto_claim_text(auto_claim: bool) -> ColoredString {
+    if auto_claim {
+        "开启".bright_green()
+    } else {
+        "关闭".bright_yellow()
+    }
+}
+
+/// 获取暂停上号文本
+fn get_pause_text(pause: bool) -> ColoredString {
+    if pause {
+        "是".bright_red()
+    } else {
+        "否".bright_green()
+    }
+}
+
+/// 显示游戏数据统计
+pub fn display_game_data(dl_data: &DlData, table_size: usize) {
+    display_records(
+        &dl_data.gold_records,
+        &dl_data.exp_records,
+        &dl_data.battle_records,
+        table_size,
+    );
+}
+
+/// 显示订单的本地历史记录
+pub fn display_history(history: &History, table_size: usize) {
+    println!("{}", "订单历史记录".bright_blue().bold());
+    print_line();
+
+    println!("订单编号: {}", history.oid.bright_cyan());
+
+    let times = history
+        .gold_records
+        .iter()
+        .map(|r| r.time)
+        .chain(history.exp_records.iter().map(|r| r.time))
+        .chain(history.battle_records.iter().map(|r| r.time))
+        .flatten();
+    if let (Some(first), Some(last)) = (times.clone().min(), times.max()) {
+        println!(
+            "记录范围: {} ~ {}",
+            format_time(Some(&first)).bright_white(),
+            format_time(Some(&last)).bright_white()
+        );
+    }
+
+    println!(
+        "状态快照: {} 个",
+        history.snapshots.len().to_string().bright_white()
+    );
+    println!();
+
+    display_records_table::<_, SnapshotRow>(&history.snapshots, "状态快照", table_size);
+    display_records(
+        &history.gold_records,
+        &history.exp_records,
+        &history.battle_records,
+        table_size,
+    );
+}
+
+/// 显示按日期汇总的报表
+pub fn display_daily_report(oid: &str, days: &[DailyStats]) {
+    println!("{}", "每日统计".bright_blue().bold());
+    print_line();
+
+    println!("订单编号: {}", oid.bright_cyan());
+
+    if days.is_empty() {
+        println!("暂无记录");
+        println!();
+        return;
+    }
+
+    let play_secs: i64 = days.iter().map(|day| day.play_secs).sum();
+    println!(
+        "统计天数: {} 天，共 {} 场对战，游戏时长 {}",
+        days.len().to_string().bright_white(),
+        days.iter()
+            .map(|day| day.battles)
+            .sum::<usize>()
+            .to_string()
+            .bright_white(),
+        format_duration(play_secs).bright_white()
+    );
+    println!();
+
+    println!("{}", Table::new(days.iter().map(DailyRow::from)));
+    println!(
+        "游戏时长由对局开始和结束时间计算，没有结束时间的对局不计入；跨越零点的对局计入开始的那一天"
+    );
+    println!();
+}
+
+/// 显示游戏时段
+pub fn display_sessions(oid: &str, sessions: &[Session], gap: std::time::Duration) {
+    println!("{}", "游戏时段".bright_blue().bold());
+    print_line();
+
+    println!("订单编号: {}", oid.bright_cyan());
+
+    if sessions.is_empty() {
+        println!("暂无对战记录");
+        println!();
+        return;
+    }
+
+    let play_secs: i64 = sessions.iter().map(|session| session.play_secs).sum();
+    println!(
+        "共 {} 个时段，{} 场对战，对局时长 {}（间隔不超过 {} 分钟的对局合并为一个时段）",
+        sessions.len().to_string().bright_white(),
+        sessions
+            .iter()
+            .map(|session| session.battles)
+            .sum::<usize>()
+            .to_string()
+            .bright_white(),
+        format_duration(play_secs).bright_white(),
+        gap.as_secs() / 60
+    );
+    println!();
+
+    println!("{}", Table::new(sessions.iter().map(SessionRow::from)));
+    println!("对局时长不包括对局之间的间隔，时段时长为第一局开始到最后一局结束");
+    println!();
+}
+
+/// 显示经验曲线、金币曲线和每日对局数，`width` 为图表的总列数，`height` 为折线图的行数
+pub fn display_charts(
+    oid: &str,
+    gold_records: &[GoldRecord],
+    exp_records: &[ExpRecord],
+    battle_records: &[BattleRecord],
+    width: usize,
+    height: usize,
+) {
+    println!("{}", "经验曲线".bright_purple().bold());
+    print_line();
+
+    println!("订单编号: {}", oid.bright_cyan());
+
+    let exp = exp_curve(exp_records);
+    match (exp.first(), exp.last()) {
+        (Some((start, first)), Some((end, last))) => {
+            println!(
+                "总经验: {} → {}（{}），时间跨度 {}",
+                first,
+                last.to_string().bright_purple(),
+                format_signed(last - first).bright_purple(),
+                format_duration((*end - *start).num_seconds())
+            );
+            println!();
+            for line in line_chart(&exp, width, height) {
+                println!("{}", line.bright_purple());
             }
+        }
+        _ => println!("暂无经验记录"),
+    }
+    println!();
 
-            let heroes = parse_battle_heroes(heroes_str);
+    println!("{}", "金币曲线".bright_yellow().bold());
+    print_line();
 
-            if heroes.is_empty() {
-                "无".bright_yellow()
-            } else if heroes.len() == HERO_NAMES.len() {
-                "全部".bright_blue()
-            } else {
-                heroes.join(", ").bright_white()
+    let gold = gold_curve(gold_records);
+    match gold.last() {
+        Some((_, total)) => {
+            println!("累计金币变化: {} 枚", format_signed(total).bright_yellow());
+            println!();
+            for line in line_chart(&gold, width, height) {
+                println!("{}", line.bright_yellow());
             }
         }
-        None => "全部".bright_blue(),
+        None => println!("暂无金币记录"),
     }
+    println!();
+
+    println!("{}", "每日对局".bright_red().bold());
+    print_line();
+
+    let days = daily_battles(battle_records);
+    // 每天一个字符，超出宽度时只显示最近的日期
+    let shown = &days[days.len().saturating_sub(width)..];
+    match (shown.first(), shown.last()) {
+        (Some((first, _)), Some((last, _))) => {
+            let counts: Vec<usize> = shown.iter().map(|(_, count)| *count).collect();
+            println!("{}", sparkline(&counts).bright_cyan());
+            println!(
+                "{} ~ {}，共 {} 天，最多 {} 场/天，{} 天没有对局{}",
+                first.format("%m-%d"),
+                last.format("%m-%d"),
+                shown.len(),
+                counts.iter().max().unwrap_or(&0).to_string().bright_white(),
+                counts
+                    .iter()
+                    .filter(|count| **count == 0)
+                    .count()
+                    .to_string()
+                    .bright_white(),
+                if shown.len() < days.len() {
+                    format!("（只显示最近 {} 天）", shown.len())
+                } else {
+                    String::new()
+                }
+            );
+        }
+        _ => println!("暂无对战记录"),
+    }
+    println!();
 }
 
-/// 获取自动领取文本
-fn get_auto_claim_text(auto: &Option<String>) -> ColoredString {
-    match auto.as_deref() {
-        Some("1") => "开启".bright_green(),
-        Some("0") => "关闭".bright_yellow(),
-        None => "关闭".bright_yellow(),
-        Some(_) => "未知".bright_magenta(),
+/// 原始数据中单个值显示的最大字符数
+const RAW_VALUE_WIDTH: usize = 60;
+
+/// 逐项显示原始订单数据，有变化的值高亮并显示上一次的值
+pub fn display_raw_fields(oid: &str, baseline: Option<&Timestamp>, fields: &[RawField]) {
+    println!("{}", "原始数据".bright_blue().bold());
+    print_line();
+
+    println!("订单编号: {}", oid.bright_cyan());
+    match baseline {
+        Some(time) => println!(
+            "对比基准: {} 保存的原始数据，共 {} 项变化",
+            format_time(Some(time)).bright_white(),
+            fields
+                .iter()
+                .filter(|field| field.is_changed())
+                .count()
+                .to_string()
+                .bright_yellow()
+        ),
+        None => println!("对比基准: 无（首次查看，下次查看时将显示与本次相比的变化）"),
+    }
+    println!();
+
+    let width = fields
+        .iter()
+        .map(|field| field.path.len())
+        .max()
+        .unwrap_or(0);
+    for field in fields {
+        let path = format!("{:width$}", field.path);
+        let value = format_raw_value(&field.value);
+        let meaning = field.meaning.dimmed();
+
+        match &field.change {
+            Change::NoBaseline | Change::Unchanged => {
+                println!("{}  {}  {}", path.bright_cyan(), value, meaning)
+            }
+            Change::Changed(old) => println!(
+                "{}  {}  {}\n{:width$}  {}",
+                path.bright_yellow().bold(),
+                value.bright_yellow(),
+                meaning,
+                "",
+                format!("上次: {}", format_raw_value(old)).yellow()
+            ),
+            Change::Added => println!(
+                "{}  {}  {}",
+                path.bright_green().bold(),
+                value.bright_green(),
+                format!("（新增）{}", field.meaning).dimmed()
+            ),
+            Change::Removed(old) => println!(
+                "{}  {}  {}",
+                path.bright_red().bold(),
+                format!("（已移除）上次: {}", format_raw_value(old)).bright_red(),
+                meaning
+            ),
+        }
     }
+    println!();
 }
 
-/// 显示游戏数据统计
-pub fn display_game_data(dl_data: &DlData, table_size: usize) -> Result<()> {
-    display_gold_statistics(&dl_data.gold_records, table_size);
-    display_exp_statistics(&dl_data.exp_records, table_size);
-    display_battle_statistics(&dl_data.battle_records, table_size);
-    Ok(())
+/// 格式化原始数据中的值
+///
+/// 记录列表只显示条数和最新的一条，疑似秒级时间戳的整数附带对应的北京时间，过长的值会被截断。
+fn format_raw_value(value: &serde_json::Value) -> String {
+    let text = match value.as_array() {
+        Some(items) if items.iter().any(|item| item.is_array()) => format!(
+            "共 {} 条，最新: {}",
+            items.len(),
+            items
+                .first()
+                .map(|item| item.to_string())
+                .unwrap_or_default()
+        ),
+        _ => value.to_string(),
+    };
+
+    let mut text = if text.chars().count() > RAW_VALUE_WIDTH {
+        text.chars().take(RAW_VALUE_WIDTH).collect::<String>() + "…"
+    } else {
+        text
+    };
+
+    let timestamp = value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()));
+    if let Some(time) = timestamp
+        .filter(|t| (1_000_000_000..4_000_000_000).contains(t))
+        .and_then(to_shanghai_time)
+    {
+        text += &format!("（{}）", format_time(Some(&time)));
+    }
+
+    text
+}
+
+/// 将升序排列的日期格式化为文本，连续的日期合并为范围（如`2025-11-12 ~ 2025-11-14, 2025-11-16`）
+fn format_date_ranges(dates: &[NaiveDate]) -> String {
+    let mut ranges: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+
+    for &date in dates {
+        match ranges.last_mut() {
+            Some((_, end)) if end.succ_opt() == Some(date) => *end = date,
+            _ => ranges.push((date, date)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.format("%Y-%m-%d").to_string()
+            } else {
+                format!("{} ~ {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 将秒数格式化为“x 小时 y 分”
+fn format_duration(secs: i64) -> String {
+    let minutes = secs / 60;
+    format!("{} 小时 {:02} 分", minutes / 60, minutes % 60)
+}
+
+/// 显示金币、经验和对战记录的统计
+fn display_records(
+    gold_records: &[GoldRecord],
+    exp_records: &[ExpRecord],
+    battle_records: &[BattleRecord],
+    table_size: usize,
+) {
+    display_gold_statistics(gold_records, table_size);
+    display_exp_statistics(exp_records, table_size);
+    display_battle_statistics(battle_records, table_size);
 }
 
 /// 显示金币统计
@@ -138,7 +1000,7 @@ fn display_gold_statistics(gold_records: &[GoldRecord], table_size: usize) {
     );
     println!();
 
-    display_records_table(gold_records, "金币记录", table_size);
+    display_records_table::<_, GoldRow>(gold_records, "金币记录", table_size);
 }
 
 /// 显示经验统计
@@ -159,7 +1021,7 @@ fn display_exp_statistics(exp_records: &[ExpRecord], table_size: usize) {
     );
     println!();
 
-    display_records_table(exp_records, "经验记录", table_size);
+    display_records_table::<_, ExpRow>(exp_records, "经验记录", table_size);
 }
 
 /// 显示对战统计
@@ -174,12 +1036,7 @@ fn display_battle_statistics(battle_records: &[BattleRecord], table_size: usize)
     }
 
     let (wins, losses, total_exp) = calculate_battle_stats(battle_records);
-    let total_battles = wins + losses;
-    let win_rate = if total_battles > 0 {
-        (wins as f64 / total_battles as f64 * 100.0) as u8
-    } else {
-        0
-    };
+    let win_rate = calculate_win_rate(wins, losses);
 
     println!("胜利场次: {} 场", wins.to_string().bright_green());
     println!("失败场次: {} 场", losses.to_string().bright_red());
@@ -187,50 +1044,14 @@ fn display_battle_statistics(battle_records: &[BattleRecord], table_size: usize)
     println!("对战经验: {} 点", format_signed(total_exp).bright_purple());
     println!();
 
-    display_records_table(battle_records, "对战记录", table_size);
-}
-
-/// 计算金币总计
-fn calculate_gold_totals(gold_records: &[GoldRecord]) -> (usize, usize) {
-    let mut total_gold = 0;
-    let mut total_packs = 0;
-
-    for record in gold_records {
-        total_gold += parse_unsigned_int(&record.gold_change);
-        total_packs += parse_unsigned_int(&record.pack_change);
-    }
-
-    (total_gold, total_packs)
-}
-
-/// 计算经验总计
-fn calculate_exp_total(exp_records: &[ExpRecord]) -> usize {
-    exp_records
-        .iter()
-        .map(|record| parse_unsigned_int(&record.exp_change))
-        .sum()
-}
-
-/// 计算对战统计
-fn calculate_battle_stats(battle_records: &[BattleRecord]) -> (usize, usize, usize) {
-    let mut wins = 0;
-    let mut losses = 0;
-    let mut total_exp = 0;
-
-    for record in battle_records {
-        if record.result.contains("胜利") {
-            wins += 1;
-        } else if record.result.contains("失败") {
-            losses += 1;
-        }
-        total_exp += parse_unsigned_int(&record.exp);
-    }
-
-    (wins, losses, total_exp)
+    display_records_table::<_, BattleRow>(battle_records, "对战记录", table_size);
 }
 
 /// 显示记录表格
-fn display_records_table<T: Tabled>(records: &[T], record_type: &str, table_size: usize) {
+fn display_records_table<'a, T, R>(records: &'a [T], record_type: &str, table_size: usize)
+where
+    R: Tabled + From<&'a T>,
+{
     if records.is_empty() || table_size == 0 {
         return;
     }
@@ -246,72 +1067,7 @@ fn display_records_table<T: Tabled>(records: &[T], record_type: &str, table_size
         display_records.len().to_string().bright_white(),
         record_type
     );
-    let table = Table::new(display_records);
+    let table = Table::new(display_records.iter().map(R::from));
     println!("{}", table);
     println!();
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_calculate_gold_totals() {
-        let items = vec![
-            GoldRecord {
-                time: String::new(),
-                gold_change: "+50".into(),
-                pack_change: "0".into(),
-            },
-            GoldRecord {
-                time: String::new(),
-                gold_change: "+100".into(),
-                pack_change: "+1".into(),
-            },
-        ];
-        let (g, p) = calculate_gold_totals(&items);
-        assert_eq!(g, 150);
-        assert_eq!(p, 1);
-    }
-
-    #[test]
-    fn test_calculate_exp_total() {
-        let items = vec![
-            ExpRecord {
-                time: String::new(),
-                exp_change: "+161".into(),
-                level: String::new(),
-                total_exp: String::new(),
-                current_level_exp: String::new(),
-            },
-            ExpRecord {
-                time: String::new(),
-                exp_change: "+78".into(),
-                level: String::new(),
-                total_exp: String::new(),
-                current_level_exp: String::new(),
-            },
-        ];
-        assert_eq!(calculate_exp_total(&items), 239);
-    }
-
-    #[test]
-    fn test_calculate_battle_stats() {
-        let items = vec![
-            BattleRecord {
-                time: String::new(),
-                result: "胜利".into(),
-                exp: "+156".into(),
-            },
-            BattleRecord {
-                time: String::new(),
-                result: "失败".into(),
-                exp: "+43".into(),
-            },
-        ];
-        let (w, l, e) = calculate_battle_stats(&items);
-        assert_eq!(w, 1);
-        assert_eq!(l, 1);
-        assert_eq!(e, 199);
-    }
-}
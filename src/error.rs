@@ -0,0 +1,67 @@
+//! 错误类型模块
+
+/// 订单库的错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// 接口地址不正确
+    #[error("接口地址不正确: {0}")]
+    InvalidEndpoint(String),
+    /// 构建 HTTP 客户端失败
+    #[error("构建 HTTP 客户端失败: {0}")]
+    Client(reqwest::Error),
+    /// 网络请求失败（已超过最大重试次数）
+    #[error("网络请求失败: {0}")]
+    Network(reqwest::Error),
+    /// 服务器返回错误状态码（已超过最大重试次数）
+    #[error("请求超过最大重试次数: {0}")]
+    Status(reqwest::Error),
+    /// 读取响应失败
+    #[error("读取响应失败: {0}")]
+    Read(reqwest::Error),
+    /// 响应不是合法的 JSON
+    #[error("解析响应 JSON 失败: {0}")]
+    Json(serde_json::Error),
+    /// 接口返回了错误代码
+    #[error("API错误({code}): {message}")]
+    Api {
+        /// 错误代码
+        code: i32,
+        /// 错误信息
+        message: String,
+    },
+    /// 无法设置为未知的对战模式
+    #[error("未知的对战模式")]
+    UnknownBattleMode,
+    /// 接口返回了空数据
+    #[error("API返回空数据")]
+    EmptyData,
+    /// config 字段格式不正确
+    #[error("config格式不正确: {0}")]
+    ConfigFormat(serde_json::Error),
+    /// dldata 字段格式不正确
+    #[error("dldata格式不正确: {0}")]
+    DlDataFormat(serde_json::Error),
+    /// dldata 字段数据不完整
+    #[error("dldata数据不完整")]
+    DlDataIncomplete,
+    /// 读写本地历史记录失败
+    #[error("读写历史记录失败: {0}")]
+    HistoryIo(std::io::Error),
+    /// 本地历史记录格式不正确
+    #[error("历史记录第{line}行格式不正确: {source}")]
+    HistoryFormat {
+        /// 出错的行号（从 1 开始）
+        line: usize,
+        /// 解析错误
+        source: serde_json::Error,
+    },
+    /// 接口数据格式与预期不一致（严格模式）
+    #[error("接口数据格式与预期不一致: {}", .0.iter().map(|w| w.to_string()).collect::<Vec<_>>().join("；"))]
+    Schema(Vec<crate::schema::SchemaWarning>),
+    /// 本地保存的原始数据格式不正确
+    #[error("保存的原始数据格式不正确: {0}")]
+    RawFormat(serde_json::Error),
+}
+
+/// 订单库的结果类型
+pub type Result<T> = std::result::Result<T, Error>;
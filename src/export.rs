@@ -0,0 +1,330 @@
+//! 记录导出模块
+//!
+//! 将金币、经验和对战记录整理为表格，并输出为 CSV、TSV 或 Markdown 文本，便于在电子表格中核对。
+
+use crate::history::file_stem;
+use crate::models::{BattleRecord, BattleResult, ExpRecord, GoldRecord, Timestamp};
+use chrono::SecondsFormat;
+use std::fmt::Write;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// 逗号分隔（RFC 4180）
+    Csv,
+    /// 制表符分隔，带 UTF-8 BOM，可以直接用 Excel 打开
+    Tsv,
+    /// Markdown 表格
+    Markdown,
+}
+
+impl ExportFormat {
+    /// 文件扩展名
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Markdown => "md",
+        }
+    }
+
+    /// 导出文件名（`<oid>-<name>.<ext>`），订单编号与历史记录文件一样经过 [`file_stem`] 处理
+    pub fn file_name(self, oid: &str, name: &str) -> String {
+        format!("{}-{}.{}", file_stem(oid), name, self.extension())
+    }
+}
+
+/// 导出的表格
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    /// 列名（与 JSON 输出中的字段名一致）
+    pub headers: &'static [&'static str],
+    /// 各行的值，缺失的值为空字符串
+    pub rows: Vec<Vec<String>>,
+}
+
+/// 金币记录的列名
+const GOLD_HEADERS: &[&str] = &["time", "gold_change", "pack_change"];
+/// 经验记录的列名
+const EXP_HEADERS: &[&str] = &[
+    "time",
+    "exp_change",
+    "level",
+    "total_exp",
+    "current_level_exp",
+];
+/// 对战记录的列名
+const BATTLE_HEADERS: &[&str] = &["time", "end_time", "duration_secs", "result", "exp"];
+/// 合并表格的列名
+const COMBINED_HEADERS: &[&str] = &[
+    "type",
+    "time",
+    "end_time",
+    "duration_secs",
+    "result",
+    "gold_change",
+    "pack_change",
+    "exp_change",
+    "level",
+    "total_exp",
+    "current_level_exp",
+];
+
+impl Sheet {
+    /// 金币记录表格
+    pub fn gold(records: &[GoldRecord]) -> Self {
+        Self {
+            headers: GOLD_HEADERS,
+            rows: records
+                .iter()
+                .map(|r| {
+                    vec![
+                        format_time(r.time.as_ref()),
+                        r.gold_change.to_string(),
+                        r.pack_change.to_string(),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// 经验记录表格（等级与 JSON 输出一致，从 0 开始，显示时需要 +1）
+    pub fn exp(records: &[ExpRecord]) -> Self {
+        Self {
+            headers: EXP_HEADERS,
+            rows: records
+                .iter()
+                .map(|r| {
+                    vec![
+                        format_time(r.time.as_ref()),
+                        r.exp_change.to_string(),
+                        r.level.to_string(),
+                        r.total_exp.to_string(),
+                        r.current_level_exp.to_string(),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// 对战记录表格
+    pub fn battle(records: &[BattleRecord]) -> Self {
+        Self {
+            headers: BATTLE_HEADERS,
+            rows: records
+                .iter()
+                .map(|r| {
+                    vec![
+                        format_time(r.time.as_ref()),
+                        format_time(r.end_time.as_ref()),
+                        format_duration(r),
+                        result_name(r.result).to_string(),
+                        r.exp.to_string(),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// 三类记录合并为一个表格，按时间倒序排列，`type` 列为 `gold`、`exp` 或 `battle`
+    pub fn combined(gold: &[GoldRecord], exp: &[ExpRecord], battle: &[BattleRecord]) -> Self {
+        let empty = || vec![String::new(); COMBINED_HEADERS.len()];
+        let mut rows: Vec<(Option<Timestamp>, Vec<String>)> = Vec::new();
+
+        for r in gold {
+            let mut row = empty();
+            row[0] = "gold".into();
+            row[1] = format_time(r.time.as_ref());
+            row[5] = r.gold_change.to_string();
+            row[6] = r.pack_change.to_string();
+            rows.push((r.time, row));
+        }
+        for r in exp {
+            let mut row = empty();
+            row[0] = "exp".into();
+            row[1] = format_time(r.time.as_ref());
+            row[7] = r.exp_change.to_string();
+            row[8] = r.level.to_string();
+            row[9] = r.total_exp.to_string();
+            row[10] = r.current_level_exp.to_string();
+            rows.push((r.time, row));
+        }
+        for r in battle {
+            let mut row = empty();
+            row[0] = "battle".into();
+            row[1] = format_time(r.time.as_ref());
+            row[2] = format_time(r.end_time.as_ref());
+            row[3] = format_duration(r);
+            row[4] = result_name(r.result).into();
+            row[7] = r.exp.to_string();
+            rows.push((r.time, row));
+        }
+
+        // 稳定排序，同一时间的记录保持金币、经验、对战的顺序
+        rows.sort_by_key(|(time, _)| std::cmp::Reverse(*time));
+
+        Self {
+            headers: COMBINED_HEADERS,
+            rows: rows.into_iter().map(|(_, row)| row).collect(),
+        }
+    }
+
+    /// 按指定格式输出表格
+    pub fn render(&self, format: ExportFormat) -> String {
+        let headers: Vec<String> = self.headers.iter().map(|h| h.to_string()).collect();
+        let lines = std::iter::once(&headers).chain(&self.rows);
+        let mut out = String::new();
+
+        match format {
+            ExportFormat::Csv => {
+                for row in lines {
+                    let cells: Vec<String> = row.iter().map(|cell| csv_escape(cell)).collect();
+                    out += &cells.join(",");
+                    out += "\r\n";
+                }
+            }
+            ExportFormat::Tsv => {
+                out.push('\u{feff}');
+                for row in lines {
+                    let cells: Vec<String> = row.iter().map(|cell| tsv_escape(cell)).collect();
+                    out += &cells.join("\t");
+                    out += "\r\n";
+                }
+            }
+            ExportFormat::Markdown => {
+                for (i, row) in lines.enumerate() {
+                    let cells: Vec<String> = row.iter().map(|cell| markdown_escape(cell)).collect();
+                    let _ = writeln!(out, "| {} |", cells.join(" | "));
+                    if i == 0 {
+                        let _ = writeln!(out, "|{}", " --- |".repeat(row.len()));
+                    }
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// 格式化为 ISO 8601 时间（如`2025-11-12T14:11:33+08:00`），时间为空时为空字符串
+fn format_time(time: Option<&Timestamp>) -> String {
+    time.map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, false))
+        .unwrap_or_default()
+}
+
+/// 对局时长（秒），无法计算时为空字符串
+fn format_duration(record: &BattleRecord) -> String {
+    record
+        .duration()
+        .map(|d| d.num_seconds().to_string())
+        .unwrap_or_default()
+}
+
+/// 对战结果的英文标识（与 JSON 输出一致）
+fn result_name(result: BattleResult) -> &'static str {
+    match result {
+        BattleResult::Win => "win",
+        BattleResult::Loss => "loss",
+        BattleResult::Unknown => "unknown",
+    }
+}
+
+/// 转义 CSV 单元格：包含逗号、双引号或换行时加上双引号
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// 转义 TSV 单元格：制表符和换行替换为空格
+fn tsv_escape(cell: &str) -> String {
+    cell.replace(['\t', '\n', '\r'], " ")
+}
+
+/// 转义 Markdown 表格单元格
+fn markdown_escape(cell: &str) -> String {
+    cell.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_shanghai_time;
+
+    fn battle() -> BattleRecord {
+        BattleRecord {
+            time: to_shanghai_time(1762927893),
+            end_time: to_shanghai_time(1762928742),
+            result: BattleResult::Loss,
+            exp: 14,
+        }
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let sheet = Sheet::battle(&[battle()]);
+        assert_eq!(
+            sheet.render(ExportFormat::Csv),
+            "time,end_time,duration_secs,result,exp\r\n\
+             2025-11-12T14:11:33+08:00,2025-11-12T14:25:42+08:00,849,loss,14\r\n"
+        );
+        assert!(
+            sheet
+                .render(ExportFormat::Tsv)
+                .starts_with("\u{feff}time\tend_time\t")
+        );
+        assert_eq!(
+            sheet.render(ExportFormat::Markdown).lines().nth(1),
+            Some("| --- | --- | --- | --- | --- |")
+        );
+    }
+
+    #[test]
+    fn test_file_name_sanitized() {
+        assert_eq!(
+            ExportFormat::Csv.file_name("2025010100013333123456", "gold"),
+            "2025010100013333123456-gold.csv"
+        );
+        assert_eq!(
+            ExportFormat::Markdown.file_name("../x", "records"),
+            "___x-records.md"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(csv_escape("a,\"b\""), "\"a,\"\"b\"\"\"");
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(tsv_escape("a\tb\nc"), "a b c");
+        assert_eq!(markdown_escape("a|b"), "a\\|b");
+    }
+
+    #[test]
+    fn test_combined_sorted_by_time() {
+        let gold = GoldRecord {
+            time: to_shanghai_time(1762928000),
+            gold_change: 50,
+            pack_change: 0,
+        };
+        let exp = ExpRecord {
+            time: to_shanghai_time(1762928742),
+            exp_change: 14,
+            level: 43,
+            total_exp: 31043,
+            current_level_exp: 443,
+        };
+        let sheet = Sheet::combined(&[gold], &[exp], &[battle()]);
+
+        let types: Vec<&str> = sheet.rows.iter().map(|row| row[0].as_str()).collect();
+        assert_eq!(types, ["exp", "gold", "battle"]);
+        assert_eq!(sheet.rows[0][8], "43");
+        assert!(
+            sheet
+                .rows
+                .iter()
+                .all(|row| row.len() == COMBINED_HEADERS.len())
+        );
+    }
+}
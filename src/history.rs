@@ -0,0 +1,516 @@
+//! 本地历史记录模块
+//!
+//! 接口只返回每类最近的部分记录，本模块将每次查询得到的记录去重后追加保存到本地 JSON Lines 文件中，
+//! 并在订单状态发生变化时保存一份快照，以便查看整个服务期间的完整记录。
+
+use crate::error::{Error, Result};
+use crate::models::{
+    BattleRecord, ExpRecord, GoldRecord, Order, OrderConfig, OrderStatus, ServiceWindow, Timestamp,
+    deserialize_optional_time, deserialize_time,
+};
+use crate::raw::RawSnapshot;
+use chrono::Local;
+use chrono_tz::Asia::Shanghai;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// 本地历史记录存储
+///
+/// 每个订单对应目录下的一个 `<oid>.jsonl` 文件，每行一条记录或快照，只追加不修改。
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    dir: PathBuf,
+}
+
+/// 订单状态快照
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// 保存时间
+    #[serde(deserialize_with = "deserialize_time")]
+    pub recorded_at: Timestamp,
+    /// 到期时间
+    #[serde(deserialize_with = "deserialize_optional_time")]
+    pub deadline: Option<Timestamp>,
+    /// 订单状态
+    pub status: OrderStatus,
+    /// 金币数量
+    pub gold: i64,
+    /// 卡包数量
+    pub packs: i64,
+    /// 奖励等级（从 0 开始，显示时需要 +1）
+    pub reward_level: i64,
+    /// 备注
+    pub remark: String,
+    /// 订单配置
+    pub config: OrderConfig,
+    /// 约定的每日上号时间段
+    #[serde(default)]
+    pub service_window: Option<ServiceWindow>,
+}
+
+impl Snapshot {
+    /// 根据已解析的订单生成快照
+    fn new(order: &Order, recorded_at: Timestamp) -> Self {
+        Self {
+            recorded_at,
+            deadline: order.deadline,
+            status: order.status,
+            gold: order.gold,
+            packs: order.packs,
+            reward_level: order.reward_level,
+            remark: order.remark.clone(),
+            config: order.config.clone(),
+            service_window: order.service.window,
+        }
+    }
+
+    /// 除保存时间外的内容是否相同
+    fn same_state(&self, other: &Self) -> bool {
+        Self {
+            recorded_at: other.recorded_at,
+            ..self.clone()
+        } == *other
+    }
+}
+
+/// 历史记录文件中的一行
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Entry {
+    Snapshot(Snapshot),
+    Gold(GoldRecord),
+    Exp(ExpRecord),
+    Battle(BattleRecord),
+}
+
+/// 订单的完整历史记录（各列表均按时间倒序排列）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct History {
+    /// 订单编号
+    pub oid: String,
+    /// 状态快照
+    pub snapshots: Vec<Snapshot>,
+    /// 金币记录
+    pub gold_records: Vec<GoldRecord>,
+    /// 经验记录
+    pub exp_records: Vec<ExpRecord>,
+    /// 对战记录
+    pub battle_records: Vec<BattleRecord>,
+}
+
+impl History {
+    /// 是否没有任何记录
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+            && self.gold_records.is_empty()
+            && self.exp_records.is_empty()
+            && self.battle_records.is_empty()
+    }
+}
+
+impl HistoryStore {
+    /// 使用指定目录创建历史记录存储
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 默认的历史记录目录（如 Linux 下为 `~/.local/share/hs-order-cli/history`）
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("hs-order-cli").join("history"))
+    }
+
+    /// 历史记录目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 订单对应的历史记录文件路径
+    pub fn path(&self, oid: &str) -> PathBuf {
+        self.dir.join(format!("{}.jsonl", file_stem(oid)))
+    }
+
+    /// 订单对应的原始数据文件路径
+    pub fn raw_path(&self, oid: &str) -> PathBuf {
+        self.path(oid).with_extension("raw.json")
+    }
+
+    /// 保存订单的原始数据，覆盖上一次保存的原始数据
+    pub fn save_raw(&self, oid: &str, snapshot: &RawSnapshot) -> Result<()> {
+        let content = serde_json::to_string_pretty(snapshot).map_err(Error::Json)?;
+        fs::create_dir_all(&self.dir).map_err(Error::HistoryIo)?;
+        fs::write(self.raw_path(oid), content).map_err(Error::HistoryIo)
+    }
+
+    /// 读取上一次保存的原始数据，没有保存过时返回 `None`
+    pub fn load_raw(&self, oid: &str) -> Result<Option<RawSnapshot>> {
+        match fs::read_to_string(self.raw_path(oid)) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(Error::RawFormat),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::HistoryIo(e)),
+        }
+    }
+
+    /// 保存订单中新出现的记录，订单状态变化时同时保存快照，返回新增的行数
+    pub fn save(&self, order: &Order) -> Result<usize> {
+        self.save_at(order, Local::now().with_timezone(&Shanghai))
+    }
+
+    /// 以指定的保存时间保存订单
+    fn save_at(&self, order: &Order, recorded_at: Timestamp) -> Result<usize> {
+        let history = self.load(&order.oid)?;
+        let dldata = &order.dldata;
+        let mut entries = Vec::new();
+
+        let snapshot = Snapshot::new(order, recorded_at);
+        if history
+            .snapshots
+            .first()
+            .is_none_or(|last| !last.same_state(&snapshot))
+        {
+            entries.push(Entry::Snapshot(snapshot));
+        }
+
+        entries.extend(
+            new_records(&history.gold_records, &dldata.gold_records, |r| r.time)
+                .into_iter()
+                .map(Entry::Gold),
+        );
+        entries.extend(
+            new_records(&history.exp_records, &dldata.exp_records, |r| r.time)
+                .into_iter()
+                .map(Entry::Exp),
+        );
+        entries.extend(
+            changed_battles(&history.battle_records, &dldata.battle_records).map(Entry::Battle),
+        );
+
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut lines = String::new();
+        for entry in &entries {
+            lines += &serde_json::to_string(entry).map_err(Error::Json)?;
+            lines.push('\n');
+        }
+
+        fs::create_dir_all(&self.dir).map_err(Error::HistoryIo)?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(&order.oid))
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .map_err(Error::HistoryIo)?;
+
+        Ok(entries.len())
+    }
+
+    /// 读取订单的完整历史记录，没有历史记录时返回空记录
+    ///
+    /// 同一时间的多条金币、经验记录都会保留；对战记录以开始时间区分，同一局只保留最后保存的一条。
+    /// 没有时间的记录被忽略。
+    pub fn load(&self, oid: &str) -> Result<History> {
+        let content = match fs::read_to_string(self.path(oid)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(Error::HistoryIo(e)),
+        };
+
+        let mut snapshots = Vec::new();
+        let mut gold = Vec::new();
+        let mut exp = Vec::new();
+        let mut battle = BTreeMap::new();
+
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry = serde_json::from_str(line).map_err(|source| Error::HistoryFormat {
+                line: i + 1,
+                source,
+            })?;
+
+            match entry {
+                Entry::Snapshot(s) => snapshots.push(s),
+                Entry::Gold(r) if r.time.is_some() => gold.push(r),
+                Entry::Exp(r) if r.time.is_some() => exp.push(r),
+                Entry::Battle(r) => {
+                    if let Some(time) = r.time {
+                        battle.insert(time, r);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        snapshots.reverse();
+
+        Ok(History {
+            oid: oid.to_string(),
+            snapshots,
+            gold_records: sort_desc(gold, |r| r.time),
+            exp_records: sort_desc(exp, |r| r.time),
+            battle_records: battle.into_values().rev().collect(),
+        })
+    }
+}
+
+/// 订单编号对应的文件名（不含扩展名），字母和数字以外的字符替换为 `_`，避免订单编号中的路径分隔符等字符
+pub fn file_stem(oid: &str) -> String {
+    oid.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// 按时间倒序排列记录，同一时间的记录按保存顺序倒序排列
+fn sort_desc<T>(mut records: Vec<T>, time: impl Fn(&T) -> Option<Timestamp>) -> Vec<T> {
+    records.sort_by_key(|r| time(r));
+    records.reverse();
+    records
+}
+
+/// 找出尚未保存或内容有变化的对战记录（按时间先后返回），没有开始时间的记录直接忽略
+///
+/// 一局对战以开始时间区分：进行中保存的记录在对局结束后内容会变化（结束时间、结果和经验），
+/// 此时保存新的内容，读取时替换之前保存的记录，而不是算作另一局。
+fn changed_battles<'a>(
+    saved: &'a [BattleRecord],
+    records: &'a [BattleRecord],
+) -> impl Iterator<Item = BattleRecord> + 'a {
+    records
+        .iter()
+        .rev()
+        .filter(|record| {
+            record
+                .time
+                .is_some_and(|t| saved.iter().find(|r| r.time == Some(t)) != Some(*record))
+        })
+        .cloned()
+}
+
+/// 找出尚未保存的记录（按时间先后返回），没有时间的记录无法去重，直接忽略
+///
+/// 同一时间可能有多条记录（包括内容完全相同的记录），因此按整条记录比较，并按每个时间已保存的条数计数：
+/// 已保存一条的记录再次出现一次时不会重复保存。
+fn new_records<T: PartialEq + Clone>(
+    saved: &[T],
+    records: &[T],
+    time: impl Fn(&T) -> Option<Timestamp>,
+) -> Vec<T> {
+    let mut unmatched: BTreeMap<Timestamp, Vec<&T>> = BTreeMap::new();
+    for record in saved {
+        if let Some(t) = time(record) {
+            unmatched.entry(t).or_default().push(record);
+        }
+    }
+
+    records
+        .iter()
+        .rev()
+        .filter(|record| {
+            let Some(t) = time(record) else {
+                return false;
+            };
+            let same_time = unmatched.entry(t).or_default();
+            match same_time.iter().position(|saved| saved == record) {
+                Some(i) => {
+                    same_time.swap_remove(i);
+                    false
+                }
+                None => true,
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BattleResult, DlData, ServiceDetails};
+    use crate::utils::to_shanghai_time;
+
+    fn temp_store(name: &str) -> HistoryStore {
+        let dir =
+            std::env::temp_dir().join(format!("hs-order-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        HistoryStore::new(dir)
+    }
+
+    fn battle(start: i64, result: BattleResult) -> BattleRecord {
+        BattleRecord {
+            time: to_shanghai_time(start),
+            end_time: to_shanghai_time(start + 600),
+            result,
+            exp: 10,
+        }
+    }
+
+    fn order(battles: Vec<BattleRecord>) -> Order {
+        Order {
+            oid: "2025010100013333123456".into(),
+            deadline: None,
+            status: OrderStatus::Running,
+            gold: 0,
+            packs: 0,
+            reward_level: 43,
+            num7: 0,
+            num8: 0,
+            details: String::new(),
+            service: ServiceDetails::default(),
+            remark: String::new(),
+            config: OrderConfig::default(),
+            dldata: DlData {
+                gold_records: vec![],
+                exp_records: vec![],
+                battle_records: battles,
+                ..Default::default()
+            },
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_history_raw_roundtrip() {
+        let store = temp_store("raw");
+        let oid = "2025010100013333123456";
+        assert_eq!(store.load_raw(oid).unwrap(), None);
+
+        let snapshot = RawSnapshot {
+            recorded_at: to_shanghai_time(1762940000).unwrap(),
+            data: serde_json::json!({ "oid": oid, "num8": "428301" })
+                .as_object()
+                .unwrap()
+                .clone(),
+        };
+        store.save_raw(oid, &snapshot).unwrap();
+        let loaded = store.load_raw(oid).unwrap();
+        fs::remove_dir_all(store.dir()).unwrap();
+
+        assert_eq!(loaded, Some(snapshot));
+    }
+
+    #[test]
+    fn test_history_load_missing() {
+        let store = temp_store("missing");
+        assert!(store.load("2025010100013333123456").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_history_save_dedup() {
+        let store = temp_store("dedup");
+        let now = to_shanghai_time(1762940000).unwrap();
+
+        let first = order(vec![
+            battle(1762930000, BattleResult::Unknown),
+            battle(1762920000, BattleResult::Loss),
+        ]);
+        assert_eq!(store.save_at(&first, now).unwrap(), 3);
+        assert_eq!(store.save_at(&first, now).unwrap(), 0);
+
+        // 接口只返回最近的记录，旧记录消失后仍保留在本地；同一局对战以最新内容为准
+        let mut second = order(vec![
+            battle(1762940000, BattleResult::Win),
+            battle(1762930000, BattleResult::Win),
+        ]);
+        second.reward_level = 44;
+        assert_eq!(store.save_at(&second, now).unwrap(), 3);
+
+        let history = store.load(&first.oid).unwrap();
+        fs::remove_dir_all(store.dir()).unwrap();
+
+        assert_eq!(
+            history.battle_records,
+            vec![
+                battle(1762940000, BattleResult::Win),
+                battle(1762930000, BattleResult::Win),
+                battle(1762920000, BattleResult::Loss),
+            ]
+        );
+        assert_eq!(history.snapshots.len(), 2);
+        assert_eq!(history.snapshots[0].reward_level, 44);
+        assert_eq!(history.snapshots[0].recorded_at, now);
+    }
+
+    #[test]
+    fn test_history_battle_completed() {
+        let store = temp_store("battle-completed");
+        let now = to_shanghai_time(1762940000).unwrap();
+
+        // 对局进行中：没有结束时间、结果未知、经验为 0
+        let in_progress = BattleRecord {
+            time: to_shanghai_time(1762930000),
+            end_time: None,
+            result: BattleResult::Unknown,
+            exp: 0,
+        };
+        let completed = battle(1762930000, BattleResult::Win);
+
+        assert_eq!(store.save_at(&order(vec![in_progress]), now).unwrap(), 2);
+        assert_eq!(
+            store.save_at(&order(vec![completed.clone()]), now).unwrap(),
+            1
+        );
+        assert_eq!(
+            store.save_at(&order(vec![completed.clone()]), now).unwrap(),
+            0
+        );
+
+        let history = store.load("2025010100013333123456").unwrap();
+        fs::remove_dir_all(store.dir()).unwrap();
+
+        assert_eq!(history.battle_records, vec![completed]);
+    }
+
+    #[test]
+    fn test_history_same_second_records() {
+        let store = temp_store("same-second");
+        let now = to_shanghai_time(1762940000).unwrap();
+        let gold = |change| GoldRecord {
+            time: to_shanghai_time(1762930000),
+            gold_change: change,
+            pack_change: 0,
+        };
+
+        // 同一秒内的两条不同记录和两条内容完全相同的记录
+        let mut first = order(vec![]);
+        first.dldata.gold_records = vec![gold(10), gold(5), gold(5)];
+        assert_eq!(store.save_at(&first, now).unwrap(), 4);
+        assert_eq!(store.save_at(&first, now).unwrap(), 0);
+
+        // 又出现一条相同的记录时只保存新增的一条
+        let mut second = order(vec![]);
+        second.dldata.gold_records = vec![gold(5), gold(10), gold(5), gold(5)];
+        assert_eq!(store.save_at(&second, now).unwrap(), 1);
+        assert_eq!(store.save_at(&second, now).unwrap(), 0);
+
+        let history = store.load(&first.oid).unwrap();
+        fs::remove_dir_all(store.dir()).unwrap();
+
+        let mut changes: Vec<i64> = history.gold_records.iter().map(|r| r.gold_change).collect();
+        changes.sort();
+        assert_eq!(changes, [5, 5, 5, 10]);
+    }
+
+    #[test]
+    fn test_history_load_bad_line() {
+        let store = temp_store("bad");
+        fs::create_dir_all(store.dir()).unwrap();
+        fs::write(store.path("123"), "\n{\"kind\":\"gold\"}\n").unwrap();
+        let r = store.load("123");
+        fs::remove_dir_all(store.dir()).unwrap();
+        assert!(matches!(r, Err(Error::HistoryFormat { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_history_path_sanitized() {
+        let store = HistoryStore::new("history");
+        assert_eq!(store.path("../x"), Path::new("history").join("___x.jsonl"));
+    }
+}
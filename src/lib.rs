@@ -0,0 +1,49 @@
+//! # 亿唐网游专营店 - 炉石传说代练订单库
+//!
+//! 提供订单数据查询、数据解析以及订单相关操作（设置对战模式、设置对战英雄、设置是否自动领取奖励、暂停/恢复上号）的可复用接口。
+//! 命令行工具 `hs-order-cli` 即基于本库实现。
+//!
+//! ```no_run
+//! use hs_order::OrderClient;
+//!
+//! let client = OrderClient::new()?;
+//! let order = client.fetch_order("1234567890123456789")?;
+//!
+//! println!("订单编号: {}", order.oid);
+//! println!("金币记录: {} 条", order.dldata.gold_records.len());
+//! # Ok::<(), hs_order::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod api;
+pub mod chart;
+pub mod constants;
+pub mod diff;
+pub mod error;
+pub mod export;
+pub mod history;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod models;
+pub mod parser;
+pub mod progress;
+pub mod raw;
+pub mod report;
+pub mod schema;
+pub mod stats;
+pub mod summary;
+pub mod utils;
+
+pub use api::{HttpConfig, OrderClient};
+pub use diff::{OrderEvent, diff_orders};
+pub use error::{Error, Result};
+pub use history::{History, HistoryStore, Snapshot};
+pub use models::{
+    BattleMode, BattleRecord, BattleResult, DlData, ExpRecord, GoldRecord, HeroSet, Order,
+    OrderConfig, OrderData, OrderStatus, ServiceDetails, ServiceWindow, Timestamp,
+};
+pub use progress::Progress;
+pub use schema::SchemaWarning;
+pub use stats::Statistics;
+pub use summary::OrderSummary;
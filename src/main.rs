@@ -1,37 +1,94 @@
 //! # 亿唐网游专营店 - 炉石传说代练订单助手
 
-mod api;
-mod constants;
+mod commands;
+mod config;
 mod display;
-mod models;
-mod parser;
-mod utils;
-
-use crate::api::fetch_order_data;
-use crate::constants::{
-    HERO_NAMES, MAX_HERO_MASK, MODE_BATTLEGROUNDS, MODE_CASUAL, MODE_STANDARD, MODE_TWIST,
-    MODE_WILD,
+mod oid_cache;
+mod paths;
+mod pwd;
+mod schedule;
+mod settings;
+#[cfg(feature = "tui")]
+mod tui;
+mod vault;
+
+use crate::commands::apply::ApplyArgs;
+use crate::commands::chart::ChartArgs;
+use crate::commands::configure::ConfigureArgs;
+use crate::commands::export::ExportArgs;
+use crate::commands::history::HistoryArgs;
+use crate::commands::inspect::InspectArgs;
+use crate::commands::pwd::PwdArgs;
+use crate::commands::report::ReportArgs;
+use crate::commands::schedule::ScheduleArgs;
+#[cfg(feature = "tui")]
+use crate::commands::tui::TuiArgs;
+use crate::config::{Config, OrderTarget, Profile};
+use crate::display::{
+    battle_heroes_name, battle_mode_name, display_batch_summary, display_config_diff,
+    display_game_data, display_order_event, display_order_info, display_order_service,
+    display_progress, display_schema_warnings, print_header,
 };
-use crate::display::{display_game_data, display_order_info, print_header};
-use crate::parser::{parse_dldata, parse_order_config};
-use anyhow::{Context, Result};
-use clap::{Args, Parser, ValueEnum};
+use crate::oid_cache::OidCache;
+use crate::pwd::PwdResolver;
+use crate::settings::{ConfigChange, diff_configs};
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Local;
+use chrono_tz::Asia::Shanghai;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
+use hs_order::constants::{HERO_NAMES, MAX_HERO_MASK, RETRIES_LIMIT};
+use hs_order::models::{BattleMode, HeroSet, Order, OrderConfig, Timestamp};
+use hs_order::utils::format_time;
+use hs_order::{OrderClient, OrderEvent, OrderSummary, diff_orders};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+/// 最小监控间隔（秒）
+const MIN_WATCH_INTERVAL_SECS: u64 = 10;
 
 #[derive(Parser)]
 #[command(
     name = "hs-order-cli",
     version,
     about = "炉石传说代练订单助手",
-    long_about = "亿唐网游专营店 - 炉石传说代练订单助手\n支持订单数据查询和订单相关操作：设置对战模式，设置对战英雄，设置是否自动领取奖励"
+    long_about = "亿唐网游专营店 - 炉石传说代练订单助手\n支持订单数据查询和订单相关操作：设置对战模式，设置对战英雄，设置是否自动领取奖励，暂停/恢复上号",
+    subcommand_negates_reqs = true
 )]
 struct Cli {
-    #[arg(value_name = "ORDER_ID", required = true, help = "订单号", value_parser = parse_order_id)]
-    order_id: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(
+        value_name = "ORDER_ID",
+        required_unless_present = "from_file",
+        help = "订单号或 @别名，可以传入多个",
+        long_help = "订单号或 @别名，可以传入多个\n\n别名在配置文件中定义（默认为 ~/.config/hs-order-cli/config.toml，可通过环境变量 HS_ORDER_CONFIG 指定），如 @main",
+        value_parser = parse_order_id
+    )]
+    order_ids: Vec<String>,
+
+    #[arg(
+        short = 'f',
+        long,
+        value_name = "PATH",
+        help = "从文件中读取订单号",
+        long_help = "从文件中读取订单号，每行一个，忽略空行和以 # 开头的行\n\n传入 - 表示从标准输入读取；可以与命令行中传入的订单号同时使用"
+    )]
+    from_file: Option<PathBuf>,
 
     #[command(flatten)]
     actions: Actions,
 
+    #[command(flatten)]
+    http: HttpArgs,
+
     #[arg(
         short = 't',
         long = "table-size",
@@ -43,7 +100,13 @@ struct Cli {
     )]
     table_size: usize,
 
-    #[arg(short, long, help = "战网密码前4位", value_parser = parse_pwd4)]
+    #[arg(
+        short,
+        long,
+        help = "战网密码前4位",
+        long_help = "战网密码前4位\n\n通过命令行传入的密码会留在 shell 历史和进程列表中，建议改用环境变量 HS_ORDER_PWD、配置文件、系统密钥环或密码库（见 pwd 子命令）；都没有提供时会在终端中提示输入",
+        value_parser = parse_pwd4
+    )]
     pwd: Option<String>,
 
     #[arg(
@@ -53,6 +116,161 @@ struct Cli {
         long_help = "跳过查询订单数据\n\n仅在传入订单相关操作选项时有效，直接将传入的订单号作为订单编号"
     )]
     skip_query: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "query",
+        help = "设置后不重新查询确认是否生效",
+        long_help = "设置后不重新查询确认是否生效\n\n默认在订单相关操作完成后重新查询订单数据，逐项比较配置，有未生效的项时报告差异并以非 0 退出码退出"
+    )]
+    no_verify: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "query",
+        help = "只预览订单相关操作，不发送设置请求",
+        long_help = "只预览订单相关操作，不发送设置请求\n\n查询订单的当前配置，显示对战模式（battlemode）、对战英雄（battleheroes 掩码）、自动领取（auto）和暂停上号（pause）设置前后的差异；不需要提供战网密码前4位"
+    )]
+    dry_run: bool,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        conflicts_with_all = ["mode", "hero", "auto", "pause"],
+        help = "查询结果的输出格式",
+        long_help = "查询结果的输出格式\n\ntext: 彩色文本和表格（默认）\njson: JSON，字段名为英文且保持稳定，适合脚本处理\nyaml: YAML，字段与 JSON 相同"
+    )]
+    output: OutputFormat,
+
+    #[arg(
+        short,
+        long,
+        requires = "query",
+        help = "查询多个订单时显示每个订单的详细数据",
+        long_help = "查询多个订单时，在摘要表格之后显示每个订单的详细数据\n\n只查询一个订单时总是显示详细数据"
+    )]
+    detail: bool,
+
+    #[arg(
+        short,
+        long,
+        value_name = "INTERVAL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "5m",
+        requires = "query",
+        help = "持续监控订单，只输出新出现的记录和变化",
+        long_help = "持续监控订单：按固定间隔重复查询订单数据，只输出新出现的金币、经验、对战记录以及奖励等级、订单状态和备注的变化\n\n间隔默认为 5 分钟，可以通过 --watch=<INTERVAL> 指定，支持 s/m/h 后缀（如 30s、10m、1h），不带后缀表示秒，最小为 10 秒\n只能监控一个订单；输出格式为 json 时每行输出一个事件（JSON Lines）",
+        value_parser = parse_interval
+    )]
+    watch: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        requires = "query",
+        help = "购买的目标奖励等级",
+        long_help = "购买的目标奖励等级（与显示的奖励等级一致）\n\n查询时根据经验记录的日均经验预估截止时的奖励等级，预计达不到目标等级时给出警告，并显示还需要的经验和所需的日均经验",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    target_level: Option<u32>,
+
+    #[arg(
+        long,
+        help = "不保存本次查询的历史记录",
+        long_help = "不保存本次查询的历史记录\n\n默认每次查询（包括监控时的每次刷新）都会将订单记录去重后保存到本地，可通过 history 子命令查看"
+    )]
+    no_history: bool,
+}
+
+/// HTTP 请求和接口数据选项，未传入时使用配置文件中的设置
+#[derive(Args, Default)]
+struct HttpArgs {
+    #[arg(
+        long,
+        global = true,
+        value_name = "URL",
+        env = "HS_ORDER_ENDPOINT",
+        help = "订单系统接口地址",
+        long_help = "订单系统接口地址，Host、Origin 和 Referer 请求头由该地址得出\n\n默认为 http://139.155.71.163:1000/training/hs.php"
+    )]
+    endpoint: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "TIMEOUT",
+        env = "HS_ORDER_CONNECT_TIMEOUT",
+        help = "连接超时时间",
+        long_help = "连接超时时间，支持 s/ms 后缀（如 3s、500ms），不带后缀表示秒，默认为 3 秒",
+        value_parser = parse_timeout
+    )]
+    connect_timeout: Option<Duration>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "TIMEOUT",
+        env = "HS_ORDER_TIMEOUT",
+        help = "请求超时时间",
+        long_help = "请求超时时间，支持 s/ms 后缀（如 10s、1500ms），不带后缀表示秒，默认为 5 秒",
+        value_parser = parse_timeout
+    )]
+    timeout: Option<Duration>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NUM",
+        env = "HS_ORDER_RETRIES",
+        help = "请求失败时的最大重试次数",
+        long_help = "请求失败时的最大重试次数，默认为 2，最大为 10",
+        value_parser = clap::value_parser!(u32).range(..=i64::from(RETRIES_LIMIT))
+    )]
+    retries: Option<u32>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "接口数据格式与预期不一致时视为错误",
+        long_help = "接口数据格式与预期不一致时视为错误（查询失败并以非 0 退出码退出）\n\n默认只显示警告：未知的字段、dldata 长度不是 16、值的类型不正确、格式不正确而被忽略的记录"
+    )]
+    strict: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 查看订单在本地保存的完整历史记录
+    History(HistoryArgs),
+    /// 管理保存的战网密码前4位（本地加密密码库或系统密钥环）
+    Pwd(PwdArgs),
+    /// 按期望状态文件调整订单配置（只修改与期望状态不一致的项）
+    Apply(ApplyArgs),
+    /// 通过交互式向导选择对战模式、勾选对战英雄并设置开关（只修改有变化的项）
+    Configure(ConfigureArgs),
+    /// 按定时规则在前台持续运行，到点调整订单配置（按北京时间计算）
+    Schedule(ScheduleArgs),
+    /// 按日期汇总订单的本地历史记录
+    Report(ReportArgs),
+    /// 查看接口返回的原始订单数据（逐项注明含义并与上一次相比）
+    Inspect(InspectArgs),
+    /// 将订单的本地历史记录导出为 CSV、TSV 或 Markdown 文件
+    Export(ExportArgs),
+    /// 以字符图表显示经验曲线、金币曲线和每日对局数
+    Chart(ChartArgs),
+    /// 在终端界面中实时查看订单，并通过快捷键修改订单配置
+    #[cfg(feature = "tui")]
+    Tui(TuiArgs),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OutputFormat {
+    Text,
+    Json,
+    Yaml,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -75,25 +293,43 @@ enum ModeArg {
     Battlegrounds,
 }
 
+impl From<ModeArg> for BattleMode {
+    fn from(mode: ModeArg) -> Self {
+        match mode {
+            ModeArg::Casual => BattleMode::Casual,
+            ModeArg::Standard => BattleMode::Standard,
+            ModeArg::Wild => BattleMode::Wild,
+            ModeArg::Twist => BattleMode::Twist,
+            ModeArg::Battlegrounds => BattleMode::Battlegrounds,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
-enum AutoArg {
+enum SwitchArg {
     #[value(alias = "1", alias = "true")]
     On,
     #[value(alias = "0", alias = "false")]
     Off,
 }
 
+impl SwitchArg {
+    /// 是否为开启
+    fn is_on(self) -> bool {
+        matches!(self, SwitchArg::On)
+    }
+}
+
 #[derive(Args)]
 #[group(required = true, multiple = true)]
 struct Actions {
-    #[arg(short, long, help = "查询订单数据", long_help = "查询订单数据\n\n不能与订单相关操作选项被同时传入", conflicts_with_all = ["mode", "hero", "auto"])]
+    #[arg(short, long, help = "查询订单数据", long_help = "查询订单数据\n\n不能与订单相关操作选项被同时传入", conflicts_with_all = ["mode", "hero", "auto", "pause"])]
     query: bool,
 
     #[arg(
         short,
         long,
         value_name = "MODE",
-        requires = "pwd",
         help = "设置对战模式",
         long_help = "设置对战模式，支持中英文和简称\n\n别名说明:\ncasual = 1|c|休闲\nstandard = 2|s|标准\nwild = 3|w|狂野\ntwist = 4|t|幻变\nbattlegrounds = 5|b|酒馆|战棋|酒馆战棋"
     )]
@@ -103,7 +339,6 @@ struct Actions {
         long,
         value_name = "HERO",
         value_delimiter = ',',
-        requires = "pwd",
         help = "设置对战英雄",
         long_help = "设置对战英雄: 支持英雄名称列表(英文逗号分隔)、掩码数值和全部/ALL\n\n可选英雄: 战士/萨满祭司/潜行者/圣骑士/猎人/德鲁伊/术士/法师/牧师/恶魔猎手/死亡骑士\n掩码计算: 每个英雄按上述顺序由低位到高位依次对应一个二进制位，将允许使用的英雄的对应位设为1，其余位设为0，转为十进制数",
         value_parser = parse_hero_item
@@ -113,186 +348,735 @@ struct Actions {
         short,
         long,
         value_name = "ON/OFF",
-        requires = "pwd",
         help = "设置是否自动领取奖励",
         long_help = "设置是否自动领取奖励\n\n别名说明:\non = 1|true\noff = 0|false"
     )]
-    auto: Option<AutoArg>,
+    auto: Option<SwitchArg>,
+    #[arg(
+        long,
+        value_name = "ON/OFF",
+        help = "设置是否暂停上号",
+        long_help = "设置是否暂停上号，on 为暂停上号，off 为恢复上号\n\n别名说明:\non = 1|true\noff = 0|false"
+    )]
+    pause: Option<SwitchArg>,
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let order_id = cli.order_id.as_str();
-    print_header();
+fn main() -> Result<ExitCode> {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    let config = Config::load()?;
+    let http = config.http_config(&cli.http);
+
+    match &cli.command {
+        Some(Command::History(args)) => return commands::history::run(args, &config, &http),
+        Some(Command::Pwd(args)) => return commands::pwd::run(args, &config),
+        Some(Command::Apply(args)) => return commands::apply::run(args, &config, &http),
+        Some(Command::Configure(args)) => return commands::configure::run(args, &config, &http),
+        Some(Command::Schedule(args)) => return commands::schedule::run(args, &config, &http),
+        Some(Command::Report(args)) => return commands::report::run(args, &config, &http),
+        Some(Command::Inspect(args)) => return commands::inspect::run(args, &config, &http),
+        Some(Command::Export(args)) => return commands::export::run(args, &config, &http),
+        Some(Command::Chart(args)) => return commands::chart::run(args, &config, &http),
+        #[cfg(feature = "tui")]
+        Some(Command::Tui(args)) => return commands::tui::run(args, &config, &http),
+        None => {}
+    }
+
+    let targets = resolve_targets(
+        &config,
+        &collect_order_ids(&cli.order_ids, cli.from_file.as_deref())?,
+    )?;
+
+    if let [target] = targets.as_slice()
+        && let Some(profile) = config.profile(&target.input)
+    {
+        apply_profile_defaults(&mut cli, &matches, profile);
+    }
+
+    let pwds = if cli.actions.query {
+        Vec::new()
+    } else if cli.dry_run {
+        // 预览时不发送设置请求，不需要战网密码前4位
+        vec![String::new(); targets.len()]
+    } else {
+        let mut resolver = PwdResolver::new(cli.pwd.clone());
+        targets
+            .iter()
+            .map(|target| resolver.resolve(target))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let client = OrderClient::with_config(&http).context("初始化订单客户端失败")?;
 
     if cli.actions.query {
-        match process_order(order_id, cli.table_size) {
+        return Ok(run_query(&client, &cli, &targets));
+    }
+
+    print_header();
+
+    let mut oid_cache = OidCache::load().unwrap_or_else(|e| {
+        print_error("读取订单编号缓存失败", &e);
+        OidCache::default()
+    });
+    let mut failed = 0;
+
+    for (target, pwd4) in targets.iter().zip(&pwds) {
+        if !run_settings(&client, &cli, target, pwd4, &mut oid_cache) {
+            failed += 1;
+        }
+    }
+
+    Ok(exit_code(failed, targets.len()))
+}
+
+/// 将订单号和别名解析为订单（去除订单号重复的项）
+fn resolve_targets(config: &Config, inputs: &[String]) -> Result<Vec<OrderTarget>> {
+    let mut targets: Vec<OrderTarget> = Vec::new();
+
+    for input in inputs {
+        let target = config.resolve(input)?;
+
+        if !targets.iter().any(|t| t.order_id == target.order_id) {
+            targets.push(target);
+        }
+    }
+
+    Ok(targets)
+}
+
+/// 命令行中未传入的显示选项使用订单别名中的配置
+fn apply_profile_defaults(cli: &mut Cli, matches: &ArgMatches, profile: &Profile) {
+    let is_default = |id| matches.value_source(id) == Some(ValueSource::DefaultValue);
+
+    if let Some(table_size) = profile.table_size
+        && is_default("table_size")
+    {
+        cli.table_size = table_size;
+    }
+
+    if let Some(output) = profile.output
+        && is_default("output")
+    {
+        cli.output = output;
+    }
+
+    if cli.target_level.is_none() {
+        cli.target_level = profile.target_level;
+    }
+}
+
+/// 汇总命令行和文件中的订单号（去除重复项）
+fn collect_order_ids(args: &[String], from_file: Option<&Path>) -> Result<Vec<String>> {
+    let mut order_ids: Vec<String> = Vec::new();
+    let mut push = |order_id: String| {
+        if !order_ids.contains(&order_id) {
+            order_ids.push(order_id);
+        }
+    };
+
+    args.iter().cloned().for_each(&mut push);
+
+    if let Some(path) = from_file {
+        let content = if path == Path::new("-") {
+            io::read_to_string(io::stdin()).context("从标准输入读取订单号失败")?
+        } else {
+            fs::read_to_string(path)
+                .with_context(|| format!("读取订单号文件 {} 失败", path.display()))?
+        };
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            push(parse_order_id(line).map_err(|e| anyhow!("{}: {}", e, line))?);
+        }
+    }
+
+    if order_ids.is_empty() {
+        bail!("没有传入任何订单号");
+    }
+
+    Ok(order_ids)
+}
+
+/// 根据失败数量确定退出码：全部成功为 0，全部失败为 1，部分失败为 2
+fn exit_code(failed: usize, total: usize) -> ExitCode {
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else if failed == total {
+        ExitCode::from(1)
+    } else {
+        ExitCode::from(2)
+    }
+}
+
+/// 查询订单数据
+fn run_query(client: &OrderClient, cli: &Cli, targets: &[OrderTarget]) -> ExitCode {
+    let order_ids: Vec<String> = targets.iter().map(|t| t.order_id.clone()).collect();
+    let order_ids = order_ids.as_slice();
+
+    if let Some(interval) = cli.watch {
+        let [order_id] = order_ids else {
+            eprintln!("{} 只能监控一个订单", "监控失败".bright_red());
+            return ExitCode::from(1);
+        };
+
+        if cli.output != OutputFormat::Text {
+            return run_watch_serialized(client, cli, order_id, interval);
+        }
+
+        print_header();
+        return run_watch(client, cli, order_id, interval);
+    }
+
+    if cli.output != OutputFormat::Text {
+        return print_orders_serialized(client, cli, order_ids);
+    }
+
+    print_header();
+
+    if let [order_id] = order_ids {
+        return match process_order(client, cli, order_id) {
             Ok(_) => {
                 println!("{}", "查询完成！".bright_green());
+                ExitCode::SUCCESS
             }
             Err(e) => {
                 print_error("查询失败", &e);
+                ExitCode::from(1)
             }
-        }
-        return Ok(());
+        };
     }
 
-    let has_settings =
-        cli.actions.mode.is_some() || cli.actions.hero.is_some() || cli.actions.auto.is_some();
+    println!(
+        "正在查询 {} 个订单\n",
+        order_ids.len().to_string().bright_cyan()
+    );
+
+    let results: Vec<(String, hs_order::Result<Order>)> = targets
+        .iter()
+        .map(|t| t.input.clone())
+        .zip(client.fetch_orders(order_ids))
+        .collect();
+
+    display_batch_summary(&results);
 
-    if has_settings {
-        let oid = match resolve_oid(order_id, cli.skip_query) {
-            Ok(o) => o,
+    for order in results.iter().filter_map(|(_, r)| r.as_ref().ok()) {
+        save_history(cli, order);
+    }
+
+    for (order_id, result) in &results {
+        match result {
+            Ok(order) if cli.detail => {
+                println!(
+                    "{} {}\n",
+                    "订单详情".bright_blue().bold(),
+                    order_id.bright_cyan()
+                );
+                display_order_info(order);
+                display_progress(order, cli.target_level);
+                display_order_service(order);
+                display_game_data(&order.dldata, cli.table_size);
+            }
+            Ok(order) if !order.warnings.is_empty() => {
+                println!("订单 {}:", order_id.bright_cyan());
+                display_schema_warnings(&order.warnings);
+            }
+            Ok(_) => {}
             Err(e) => {
-                print_error("查询订单编号失败", &e);
-                return Ok(());
+                print_error(&format!("订单 {} 查询失败", order_id), &anyhow!("{}", e));
             }
-        };
+        }
+    }
+
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    exit_code(failed, results.len())
+}
+
+/// 处理单个订单查询
+fn process_order(client: &OrderClient, cli: &Cli, order_id: &str) -> Result<()> {
+    println!("正在查询订单: {}\n", order_id.bright_cyan());
+
+    let order = client.fetch_order(order_id).context("获取订单数据失败")?;
+    display_order_info(&order);
+    display_progress(&order, cli.target_level);
+    display_order_service(&order);
+    display_game_data(&order.dldata, cli.table_size);
+    save_history(cli, &order);
+
+    Ok(())
+}
 
-        println!("订单编号 {}", oid.bright_cyan());
+/// 将订单记录保存到本地历史记录，失败时只输出警告
+fn save_history(cli: &Cli, order: &Order) {
+    if cli.no_history {
+        return;
+    }
 
-        if let Some(mode_input) = cli.actions.mode
-            && let Err(e) = set_battle_mode(&oid, mode_input, cli.pwd.as_deref().unwrap())
-        {
-            print_error("设置对战模式失败", &e);
+    if let Err(e) = commands::history::save(order) {
+        if cli.output == OutputFormat::Text {
+            print_error("保存历史记录失败", &e);
+        } else {
+            eprintln!("保存历史记录失败: {:#}", e);
         }
+    }
+}
 
-        if let Some(hero_inputs) = cli.actions.hero.as_deref()
-            && let Err(e) = set_current_hero(&oid, hero_inputs, cli.pwd.as_deref().unwrap())
-        {
-            print_error("设置对战英雄失败", &e);
+/// 持续监控订单，输出新出现的记录和变化
+fn run_watch(client: &OrderClient, cli: &Cli, order_id: &str, interval: Duration) -> ExitCode {
+    println!("正在查询订单: {}\n", order_id.bright_cyan());
+
+    let mut prev = match client.fetch_order(order_id) {
+        Ok(order) => order,
+        Err(e) => {
+            print_error("查询失败", &anyhow!("{}", e));
+            return ExitCode::from(1);
         }
+    };
 
-        if let Some(auto_input) = cli.actions.auto
-            && let Err(e) = set_auto_claim(&oid, auto_input, cli.pwd.as_deref().unwrap())
-        {
-            print_error("设置自动领取奖励失败", &e);
+    display_order_info(&prev);
+    display_progress(&prev, cli.target_level);
+    display_order_service(&prev);
+    display_game_data(&prev.dldata, cli.table_size);
+    save_history(cli, &prev);
+    println!(
+        "{} 每 {} 秒刷新一次，按 Ctrl+C 退出\n",
+        "开始监控订单".bright_green(),
+        interval.as_secs().to_string().bright_white()
+    );
+
+    loop {
+        thread::sleep(interval);
+
+        match client.fetch_order(order_id) {
+            Ok(next) => {
+                for event in diff_orders(&prev, &next) {
+                    display_order_event(&event);
+                }
+                save_history(cli, &next);
+                prev = next;
+            }
+            Err(e) => print_error(&format!("[{}] 刷新失败", now_text()), &anyhow!("{}", e)),
         }
+    }
+}
 
-        return Ok(());
+/// 持续监控订单，以机器可读格式逐条输出变化事件
+fn run_watch_serialized(
+    client: &OrderClient,
+    cli: &Cli,
+    order_id: &str,
+    interval: Duration,
+) -> ExitCode {
+    let mut prev = match client.fetch_order(order_id) {
+        Ok(order) => order,
+        Err(e) => {
+            eprintln!("获取订单数据失败: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    save_history(cli, &prev);
+
+    loop {
+        thread::sleep(interval);
+
+        match client.fetch_order(order_id) {
+            Ok(next) => {
+                for event in diff_orders(&prev, &next) {
+                    let item = WatchItem {
+                        detected_at: now(),
+                        event: &event,
+                    };
+                    let text = match cli.output {
+                        OutputFormat::Json => {
+                            serde_json::to_string(&item).map_err(|e| e.to_string())
+                        }
+                        _ => serde_yaml::to_string(&item)
+                            .map(|yaml| format!("---\n{}", yaml.trim_end()))
+                            .map_err(|e| e.to_string()),
+                    };
+
+                    match text {
+                        Ok(text) => println!("{}", text),
+                        Err(e) => eprintln!("序列化事件失败: {}", e),
+                    }
+                }
+                save_history(cli, &next);
+                prev = next;
+            }
+            Err(e) => eprintln!("刷新失败: {}", e),
+        }
     }
+}
 
-    Ok(())
+/// 监控时单个变化事件的机器可读输出
+#[derive(Serialize)]
+struct WatchItem<'a> {
+    detected_at: Timestamp,
+    #[serde(flatten)]
+    event: &'a OrderEvent,
 }
 
-/// 处理单个订单查询
-fn process_order(order_id: &str, table_size: usize) -> Result<()> {
-    println!("正在查询订单: {}\n", order_id.bright_cyan());
+/// 以机器可读格式输出订单查询结果
+///
+/// 只查询一个订单时输出该订单的摘要对象，查询多个订单时输出数组，数组元素包含订单号和摘要或错误信息
+fn print_orders_serialized(client: &OrderClient, cli: &Cli, order_ids: &[String]) -> ExitCode {
+    let format = cli.output;
+    let results = client.fetch_orders(order_ids);
+    let failed = results.iter().filter(|r| r.is_err()).count();
 
-    let order = fetch_order_data(order_id).context("获取订单数据接口失败")?;
-    let config = parse_order_config(&order.config).context("解析订单配置信息失败")?;
-    let dldata = parse_dldata(&order.dldata).context("解析游戏统计数据失败")?;
-    display_order_info(&order, &config, &dldata).context("显示订单基本信息失败")?;
-    display_game_data(&dldata, table_size).context("显示游戏数据失败")?;
+    for order in results.iter().flatten() {
+        save_history(cli, order);
+    }
 
-    Ok(())
-}
+    let serialized = if let [result] = results.as_slice() {
+        match result {
+            Ok(order) => serialize_output(&OrderSummary::new(order), format),
+            Err(e) => Err(anyhow!("获取订单数据失败: {}", e)),
+        }
+    } else {
+        let items: Vec<BatchItem> = order_ids
+            .iter()
+            .zip(&results)
+            .map(|(order_id, result)| BatchItem {
+                order_id,
+                order: result.as_ref().ok().map(OrderSummary::new),
+                error: result.as_ref().err().map(|e| e.to_string()),
+            })
+            .collect();
+        serialize_output(&items, format)
+    };
 
-/// 通过查询订单数据获取订单编号（oid）
-fn resolve_oid(order_id: &str, skip_query: bool) -> Result<String> {
-    if skip_query {
-        return Ok(order_id.to_string());
+    match serialized {
+        Ok(text) => print!("{}", text),
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return ExitCode::from(1);
+        }
     }
 
-    let order = fetch_order_data(order_id).context("查询订单数据失败")?;
-    Ok(order.oid)
+    exit_code(failed, results.len())
 }
 
-/// 设置对战模式
-fn set_battle_mode(oid: &str, mode_input: ModeArg, pwd4: &str) -> Result<()> {
-    let (normalized, display_name) = match mode_input {
-        ModeArg::Casual => (MODE_CASUAL, "休闲模式"),
-        ModeArg::Standard => (MODE_STANDARD, "标准模式"),
-        ModeArg::Wild => (MODE_WILD, "狂野模式"),
-        ModeArg::Twist => (MODE_TWIST, "幻变模式"),
-        ModeArg::Battlegrounds => (MODE_BATTLEGROUNDS, "酒馆战棋"),
+/// 批量查询时单个订单的机器可读输出
+#[derive(Serialize)]
+struct BatchItem<'a> {
+    order_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order: Option<OrderSummary<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// 将查询结果序列化为指定格式
+fn serialize_output<T: Serialize>(value: &T, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(value).context("序列化 JSON 失败")?;
+            Ok(json + "\n")
+        }
+        OutputFormat::Yaml => serde_yaml::to_string(value).context("序列化 YAML 失败"),
+        OutputFormat::Text => unreachable!(),
+    }
+}
+
+/// 对单个订单执行订单相关操作，全部成功时返回 `true`
+fn run_settings(
+    client: &OrderClient,
+    cli: &Cli,
+    target: &OrderTarget,
+    pwd4: &str,
+    oid_cache: &mut OidCache,
+) -> bool {
+    let oid = match resolve_oid(client, target, cli.skip_query, oid_cache) {
+        Ok(o) => o,
+        Err(e) => {
+            print_error("查询订单编号失败", &e);
+            return false;
+        }
     };
 
-    api::set_battle_mode(oid, pwd4, normalized).context("设置对战模式接口失败")?;
+    println!("订单编号 {}", oid.bright_cyan());
 
-    println!(
-        "{} {}",
-        "已设置对战模式为".bright_green(),
-        display_name.bright_yellow()
-    );
+    let change = requested_change(cli);
 
-    Ok(())
+    if cli.dry_run {
+        return preview_settings(client, cli, &oid, &change);
+    }
+
+    let before = if cli.no_verify {
+        None
+    } else {
+        match client.fetch_order(&oid).context("查询订单数据失败") {
+            Ok(order) => {
+                save_history(cli, &order);
+                Some(order.config)
+            }
+            Err(e) => {
+                print_error("查询当前配置失败", &e);
+                None
+            }
+        }
+    };
+
+    let (applied, mut ok) = apply_change(client, &oid, &change, pwd4);
+
+    if !cli.no_verify && !applied.is_empty() {
+        let result = client
+            .fetch_order(&oid)
+            .context("重新查询订单数据失败")
+            .and_then(|order| {
+                save_history(cli, &order);
+                verify_settings(&applied, before.as_ref(), &order.config)
+            });
+
+        if let Err(e) = result {
+            print_error("确认设置结果失败", &e);
+            ok = false;
+        }
+    }
+
+    ok
 }
 
-/// 设置对战英雄
-fn set_current_hero(oid: &str, hero_inputs: &[String], pwd4: &str) -> Result<()> {
-    let mut mask: u32 = 0;
-
-    if hero_inputs.len() == 1 {
-        // 输入是全部/ALL或掩码或单个英雄名称
-        let lower = hero_inputs[0].to_lowercase();
-
-        if lower == "all" || lower == "全部" {
-            mask = MAX_HERO_MASK;
-        } else if let Ok(m) = lower.parse::<u32>() {
-            // 输入是英雄掩码
-            mask = m;
+/// 依次发送设置请求，返回设置成功的项和是否全部成功
+fn apply_change(
+    client: &OrderClient,
+    oid: &str,
+    change: &ConfigChange,
+    pwd4: &str,
+) -> (ConfigChange, bool) {
+    let mut ok = true;
+    let mut applied = ConfigChange::default();
+
+    if let Some(mode) = change.mode {
+        match set_battle_mode(client, oid, mode, pwd4) {
+            Ok(()) => applied.mode = Some(mode),
+            Err(e) => {
+                print_error("设置对战模式失败", &e);
+                ok = false;
+            }
         }
     }
 
-    if mask == 0 {
-        // 输入是单个或多个英雄名称（其中可能包含全部/ALL）
-        for name in hero_inputs {
-            if let Some(idx) = HERO_NAMES.iter().position(|&h| h == name) {
-                mask |= 1 << idx;
-            } else if name.to_lowercase() == "all" || name == "全部" {
-                mask = MAX_HERO_MASK;
+    if let Some(heroes) = change.heroes {
+        match set_current_hero(client, oid, heroes, pwd4) {
+            Ok(()) => applied.heroes = Some(heroes),
+            Err(e) => {
+                print_error("设置对战英雄失败", &e);
+                ok = false;
             }
         }
     }
 
-    let mut selected_names: Vec<&str> = Vec::new();
+    if let Some(auto_claim) = change.auto_claim {
+        match set_auto_claim(client, oid, auto_claim, pwd4) {
+            Ok(()) => applied.auto_claim = Some(auto_claim),
+            Err(e) => {
+                print_error("设置自动领取奖励失败", &e);
+                ok = false;
+            }
+        }
+    }
 
-    for (i, &name) in HERO_NAMES.iter().enumerate() {
-        if mask & (1 << i) != 0 {
-            selected_names.push(name);
+    if let Some(pause) = change.pause {
+        match set_pause(client, oid, pause, pwd4) {
+            Ok(()) => applied.pause = Some(pause),
+            Err(e) => {
+                print_error("设置暂停上号失败", &e);
+                ok = false;
+            }
         }
     }
 
-    api::set_battle_heroes(oid, pwd4, &mask.to_string()).context("设置对战英雄接口失败")?;
+    (applied, ok)
+}
 
-    if mask == MAX_HERO_MASK {
-        println!(
-            "{} {}",
-            "已设置对战英雄为".bright_green(),
-            "全部".bright_yellow()
-        );
-    } else {
+/// 由命令行选项得出要设置的配置
+fn requested_change(cli: &Cli) -> ConfigChange {
+    ConfigChange {
+        mode: cli.actions.mode.map(BattleMode::from),
+        heroes: cli.actions.hero.as_deref().map(parse_hero_inputs),
+        auto_claim: cli.actions.auto.map(SwitchArg::is_on),
+        pause: cli.actions.pause.map(SwitchArg::is_on),
+    }
+}
+
+/// 预览订单相关操作：查询当前配置并显示设置前后的差异，不发送设置请求
+fn preview_settings(client: &OrderClient, cli: &Cli, oid: &str, change: &ConfigChange) -> bool {
+    let order = match client.fetch_order(oid).context("查询订单数据失败") {
+        Ok(order) => order,
+        Err(e) => {
+            print_error("查询当前配置失败", &e);
+            return false;
+        }
+    };
+    save_history(cli, &order);
+
+    println!(
+        "{}",
+        "预览（不会发送设置请求），设置前后对比:".bright_yellow()
+    );
+    display_config_diff(&diff_configs(&order.config, &change.apply(&order.config)));
+
+    true
+}
+
+/// 显示设置前后的差异，并确认重新查询得到的配置中已设置的项都已生效
+fn verify_settings(
+    applied: &ConfigChange,
+    before: Option<&OrderConfig>,
+    after: &OrderConfig,
+) -> Result<()> {
+    if let Some(before) = before {
+        println!("{}", "设置前后对比:".bright_blue());
+        display_config_diff(&diff_configs(before, after));
+    }
+
+    let mismatches = applied.mismatches(after);
+
+    if mismatches.is_empty() {
+        println!("{}", "已重新查询确认设置生效".bright_green());
+        return Ok(());
+    }
+
+    for mismatch in &mismatches {
         println!(
-            "{} {}",
-            "已设置对战英雄为".bright_green(),
-            selected_names.join(", ").bright_yellow()
+            "{} {}: 期望 {}，实际 {}",
+            "设置未生效".bright_red(),
+            mismatch.name,
+            mismatch.after.bright_yellow(),
+            mismatch.before.bright_red()
         );
     }
 
+    bail!("{} 项设置未生效", mismatches.len())
+}
+
+/// 获取订单编号（oid）
+///
+/// 依次使用订单别名中配置的订单编号、本地缓存的订单编号，都没有时查询订单数据并缓存结果。
+fn resolve_oid(
+    client: &OrderClient,
+    target: &OrderTarget,
+    skip_query: bool,
+    oid_cache: &mut OidCache,
+) -> Result<String> {
+    if let Some(oid) = &target.oid {
+        return Ok(oid.clone());
+    }
+
+    if skip_query {
+        return Ok(target.order_id.clone());
+    }
+
+    if let Some(oid) = oid_cache.get(&target.order_id) {
+        return Ok(oid.to_string());
+    }
+
+    let order = client
+        .fetch_order_data(&target.order_id)
+        .context("查询订单数据失败")?;
+
+    if let Err(e) = oid_cache.insert(&target.order_id, &order.oid) {
+        print_error("保存订单编号缓存失败", &e);
+    }
+
+    Ok(order.oid)
+}
+
+/// 设置对战模式
+fn set_battle_mode(client: &OrderClient, oid: &str, mode: BattleMode, pwd4: &str) -> Result<()> {
+    client
+        .set_battle_mode(oid, pwd4, mode)
+        .context("设置对战模式接口失败")?;
+
+    println!(
+        "{} {}",
+        "已设置对战模式为".bright_green(),
+        battle_mode_name(mode).bright_yellow()
+    );
+
     Ok(())
 }
 
-/// 设置自动领取奖励
-fn set_auto_claim(oid: &str, auto_input: AutoArg, pwd4: &str) -> Result<()> {
-    let val = match auto_input {
-        AutoArg::On => "1",
-        AutoArg::Off => "0",
-    };
+/// 设置对战英雄
+fn set_current_hero(client: &OrderClient, oid: &str, heroes: HeroSet, pwd4: &str) -> Result<()> {
+    client
+        .set_battle_heroes(oid, pwd4, heroes)
+        .context("设置对战英雄接口失败")?;
 
-    api::set_auto_claim(oid, pwd4, val).context("设置自动领取接口失败")?;
+    println!(
+        "{} {}",
+        "已设置对战英雄为".bright_green(),
+        battle_heroes_name(heroes).bright_yellow()
+    );
+
+    Ok(())
+}
+
+/// 设置自动领取奖励
+fn set_auto_claim(client: &OrderClient, oid: &str, auto_claim: bool, pwd4: &str) -> Result<()> {
+    client
+        .set_auto_claim(oid, pwd4, auto_claim)
+        .context("设置自动领取接口失败")?;
 
     println!(
         "{} {}",
         "已设置自动领取奖励为".bright_green(),
-        if val == "1" { "开启" } else { "关闭" }.bright_yellow()
+        if auto_claim { "开启" } else { "关闭" }.bright_yellow()
     );
 
     Ok(())
 }
 
-/// 解析订单号
+/// 设置是否暂停上号
+fn set_pause(client: &OrderClient, oid: &str, pause: bool, pwd4: &str) -> Result<()> {
+    client
+        .set_pause(oid, pwd4, pause)
+        .context("设置暂停上号接口失败")?;
+
+    println!(
+        "{} {}",
+        "已设置上号状态为".bright_green(),
+        if pause {
+            "暂停上号"
+        } else {
+            "恢复上号"
+        }
+        .bright_yellow()
+    );
+
+    Ok(())
+}
+
+/// 将对战英雄输入项（英雄名称、掩码数值或全部/ALL）合并为英雄集合
+fn parse_hero_inputs(hero_inputs: &[String]) -> HeroSet {
+    hero_inputs.iter().fold(HeroSet::empty(), |heroes, input| {
+        let lower = input.to_lowercase();
+
+        if lower == "all" || input == "全部" {
+            HeroSet::all()
+        } else if let Ok(m) = input.parse::<u32>() {
+            heroes | HeroSet::from_bits_truncate(m)
+        } else {
+            heroes | HeroSet::from_hero_name(input).unwrap_or(HeroSet::empty())
+        }
+    })
+}
+
+/// 解析订单号或订单别名（@别名）
 fn parse_order_id(s: &str) -> std::result::Result<String, String> {
+    if let Some(name) = s.strip_prefix('@') {
+        return if name.is_empty() || name.chars().any(char::is_whitespace) {
+            Err("订单别名不能为空或包含空白字符".to_string())
+        } else {
+            Ok(s.to_string())
+        };
+    }
+
     if s.chars().all(|c| c.is_ascii_digit()) {
         Ok(s.to_string())
     } else {
@@ -300,6 +1084,52 @@ fn parse_order_id(s: &str) -> std::result::Result<String, String> {
     }
 }
 
+/// 解析监控间隔
+fn parse_interval(s: &str) -> std::result::Result<Duration, String> {
+    match parse_duration(s, "监控间隔")? {
+        d if d.as_secs() >= MIN_WATCH_INTERVAL_SECS => Ok(d),
+        _ => Err(format!("监控间隔不能小于 {} 秒", MIN_WATCH_INTERVAL_SECS)),
+    }
+}
+
+/// 解析时长：支持 s/m/h 后缀，不带后缀表示秒，`name` 为错误信息中的名称
+fn parse_duration(s: &str, name: &str) -> std::result::Result<Duration, String> {
+    let lower = s.trim().to_lowercase();
+    let (num, unit) = match lower.char_indices().last() {
+        Some((i, 's')) => (&lower[..i], 1),
+        Some((i, 'm')) => (&lower[..i], 60),
+        Some((i, 'h')) => (&lower[..i], 3600),
+        _ => (lower.as_str(), 1),
+    };
+
+    let n = num
+        .parse::<u64>()
+        .map_err(|_| format!("{}必须为整数，可带 s/m/h 后缀", name))?;
+    n.checked_mul(unit)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("{}超出范围", name))
+}
+
+/// 解析请求超时时间
+fn parse_timeout(s: &str) -> std::result::Result<Duration, String> {
+    let lower = s.trim().to_lowercase();
+    let duration = if let Some(ms) = lower.strip_suffix("ms") {
+        ms.parse().map(Duration::from_millis)
+    } else {
+        lower
+            .strip_suffix('s')
+            .unwrap_or(&lower)
+            .parse()
+            .map(Duration::from_secs)
+    };
+
+    match duration {
+        Ok(d) if !d.is_zero() => Ok(d),
+        Ok(_) => Err("请求超时时间必须大于 0".to_string()),
+        Err(_) => Err("请求超时时间必须为整数，可带 s/ms 后缀".to_string()),
+    }
+}
+
 /// 解析游戏数据统计表格显示的最大记录条数
 fn parse_table_size(s: &str) -> std::result::Result<usize, String> {
     if s.to_lowercase() == "all" {
@@ -343,6 +1173,16 @@ fn parse_hero_item(s: &str) -> std::result::Result<String, String> {
     }
 }
 
+/// 获取当前北京时间
+fn now() -> Timestamp {
+    Local::now().with_timezone(&Shanghai)
+}
+
+/// 获取当前北京时间文本
+fn now_text() -> String {
+    format_time(Some(&now()))
+}
+
 /// 打印错误信息
 fn print_error(context: &str, e: &anyhow::Error) {
     print!("{}", context.bright_red());
@@ -377,6 +1217,88 @@ mod tests {
         assert!(matches!(r, Err(e) if e == "订单号必须为纯数字"));
     }
 
+    #[test]
+    fn test_parse_order_id_alias() {
+        assert_eq!(parse_order_id("@main").unwrap(), "@main");
+        assert!(matches!(parse_order_id("@"), Err(e) if e == "订单别名不能为空或包含空白字符"));
+    }
+
+    #[test]
+    fn test_resolve_targets_dedup() {
+        let config: Config =
+            toml::from_str("[profiles.main]\norder_id = \"1234567890123456789\"\n").unwrap();
+        let inputs = vec!["@main".to_string(), "1234567890123456789".to_string()];
+        let targets = resolve_targets(&config, &inputs).unwrap();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].input, "@main");
+    }
+
+    #[test]
+    fn test_collect_order_ids_dedup() {
+        let args = vec!["123".to_string(), "456".to_string(), "123".to_string()];
+        let r = collect_order_ids(&args, None).unwrap();
+        assert_eq!(r, vec!["123", "456"]);
+    }
+
+    #[test]
+    fn test_collect_order_ids_from_file() {
+        let path = std::env::temp_dir().join(format!("hs-order-ids-{}.txt", std::process::id()));
+        fs::write(&path, "# 主号\n456\n\n  789  \n123\n").unwrap();
+        let r = collect_order_ids(&["123".to_string()], Some(&path));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(r.unwrap(), vec!["123", "456", "789"]);
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(0, 3), ExitCode::SUCCESS);
+        assert_eq!(exit_code(3, 3), ExitCode::from(1));
+        assert_eq!(exit_code(1, 3), ExitCode::from(2));
+    }
+
+    #[test]
+    fn test_parse_interval_ok() {
+        assert_eq!(parse_interval("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_interval("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_interval("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_interval("1H").unwrap(), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_parse_interval_err() {
+        assert!(matches!(parse_interval("5"), Err(e) if e == "监控间隔不能小于 10 秒"));
+        assert!(
+            matches!(parse_interval("abc"), Err(e) if e == "监控间隔必须为整数，可带 s/m/h 后缀")
+        );
+        assert!(matches!(parse_interval("9999999999999999h"), Err(e) if e == "监控间隔超出范围"));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let parse = |s| parse_duration(s, "间隔");
+        assert_eq!(parse("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse("1H"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(
+            parse("abc"),
+            Err("间隔必须为整数，可带 s/m/h 后缀".to_string())
+        );
+        assert!(parse("-5m").is_err());
+        assert_eq!(parse("9999999999999999h"), Err("间隔超出范围".to_string()));
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("10").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_timeout("3s").unwrap(), Duration::from_secs(3));
+        assert_eq!(
+            parse_timeout("1500ms").unwrap(),
+            Duration::from_millis(1500)
+        );
+        assert!(matches!(parse_timeout("0"), Err(e) if e == "请求超时时间必须大于 0"));
+        assert!(parse_timeout("1m").is_err());
+    }
+
     #[test]
     fn test_parse_table_size_ok() {
         let s = "30";
@@ -447,6 +1369,20 @@ mod tests {
         assert_eq!(r.unwrap(), name);
     }
 
+    #[test]
+    fn test_parse_hero_inputs() {
+        let names = vec!["法师".to_string(), "术士".to_string()];
+        assert_eq!(parse_hero_inputs(&names), HeroSet::MAGE | HeroSet::WARLOCK);
+        assert_eq!(
+            parse_hero_inputs(&["512".to_string()]),
+            HeroSet::DEMON_HUNTER
+        );
+        assert_eq!(
+            parse_hero_inputs(&["战士".to_string(), "ALL".to_string()]),
+            HeroSet::all()
+        );
+    }
+
     #[test]
     fn test_parse_hero_item_name_err() {
         let r = parse_hero_item("神谕者");
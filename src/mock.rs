@@ -0,0 +1,387 @@
+//! 模拟服务器模块
+//!
+//! 在本地实现 `doc/API.md` 中描述的表单接口（按订单号或订单编号查询订单数据，设置对战模式、对战英雄、
+//! 是否自动领取奖励和是否暂停上号），数据来自内存中的样例订单，用于在不访问真实接口的情况下测试客户端。
+//!
+//! ```no_run
+//! use hs_order::mock::{MockOrder, MockServer};
+//! use hs_order::{HttpConfig, OrderClient};
+//!
+//! let server = MockServer::start(vec![MockOrder::sample()])?;
+//! let config = HttpConfig {
+//!     endpoint: server.endpoint(),
+//!     ..HttpConfig::default()
+//! };
+//! let order = OrderClient::with_config(&config)?.fetch_order("1234567890123456789")?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::constants::{MAX_HERO_MASK, SUCCESS_CODE};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+/// 查询不到订单或战网密码错误时返回的错误信息（与原系统一致）
+pub const NOT_FOUND_MESSAGE: &str = "没有查询到订单信息，请联系客服人员！";
+/// 参数不正确时返回的错误信息
+pub const INVALID_PARAM_MESSAGE: &str = "参数错误";
+
+/// 模拟服务器中的订单
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockOrder {
+    /// 订单号
+    pub order_id: String,
+    /// 战网密码前4位
+    pub pwd: String,
+    /// 查询接口返回的订单原始数据（字段与 [`OrderData`](crate::OrderData) 相同）
+    pub data: Value,
+    /// 设置接口返回成功但不修改配置（模拟设置没有生效的情况）
+    #[serde(default)]
+    pub ignore_settings: bool,
+}
+
+impl MockOrder {
+    /// 样例订单
+    ///
+    /// 订单号为 `1234567890123456789`，订单编号为 `2025010100013333123456`，战网密码前4位为 `zwmm`。
+    pub fn sample() -> Self {
+        let dldata = json!([
+            8560,
+            9,
+            50,
+            "20251112",
+            1,
+            1,
+            0,
+            "CHN",
+            1762906878,
+            0,
+            [[1762920279, 50, 0], [1762912610, 100, 1]],
+            [
+                [1762928742, 14, 43, 31043, 443],
+                [1762925584, 154, 43, 31029, 429]
+            ],
+            [
+                [1762928742, -1, 14, 1762927893],
+                [1762925584, 1, 154, 1762924010]
+            ],
+            [],
+            0,
+            0
+        ]);
+
+        Self {
+            order_id: "1234567890123456789".to_string(),
+            pwd: "zwmm".to_string(),
+            data: json!({
+                "am": "",
+                "oid": "2025010100013333123456",
+                "edate": "2025-12-18 20:05:30",
+                "config": r#"{"auto":"0","battleheroes":"2047","battlemode":"2","region":"CN"}"#,
+                "details": "08:00-16:00  (2025/11/12 08:01:18)",
+                "finish": "0",
+                "banned": "0",
+                "dltype": "5",
+                "num1": "13040",
+                "num2": "1",
+                "num3": "43",
+                "num7": "0",
+                "num8": "428301",
+                "dldata": dldata.to_string(),
+                "remark": "2025/11/12_08:01:18_开始标准模式"
+            }),
+            ignore_settings: false,
+        }
+    }
+
+    /// 订单编号
+    pub fn oid(&self) -> &str {
+        self.data["oid"].as_str().unwrap_or_default()
+    }
+
+    /// 解析后的配置信息（`config` 字段）
+    pub fn config(&self) -> Map<String, Value> {
+        self.data["config"]
+            .as_str()
+            .and_then(|config| serde_json::from_str(config).ok())
+            .unwrap_or_default()
+    }
+
+    /// 修改配置信息中的一项
+    fn set_config(&mut self, key: &str, value: &str) {
+        let mut config = self.config();
+        config.insert(key.to_string(), Value::String(value.to_string()));
+        self.data["config"] = Value::String(Value::Object(config).to_string());
+    }
+}
+
+/// 模拟服务器的共享状态
+#[derive(Debug, Default)]
+struct State {
+    orders: Vec<MockOrder>,
+    requests: Vec<Vec<(String, String)>>,
+}
+
+/// 模拟服务器
+///
+/// 每个连接在单独的线程中处理，服务器线程在进程退出前一直运行。
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// 在 `127.0.0.1` 的随机端口上启动模拟服务器
+    pub fn start(orders: Vec<MockOrder>) -> io::Result<Self> {
+        Self::bind("127.0.0.1:0", orders)
+    }
+
+    /// 在指定地址上启动模拟服务器
+    pub fn bind<A: ToSocketAddrs>(addr: A, orders: Vec<MockOrder>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            orders,
+            requests: Vec::new(),
+        }));
+
+        let shared = Arc::clone(&state);
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let _ = handle_connection(stream, &state);
+                });
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            handle,
+        })
+    }
+
+    /// 监听地址
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// 接口地址，可用作 [`HttpConfig::endpoint`](crate::HttpConfig::endpoint)
+    pub fn endpoint(&self) -> String {
+        format!("http://{}/training/hs.php", self.addr)
+    }
+
+    /// 按订单号或订单编号获取订单的当前数据
+    pub fn order(&self, key: &str) -> Option<MockOrder> {
+        lock(&self.state)
+            .orders
+            .iter()
+            .find(|order| order.order_id == key || order.oid() == key)
+            .cloned()
+    }
+
+    /// 已收到的请求表单（按接收顺序）
+    pub fn requests(&self) -> Vec<Vec<(String, String)>> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// 阻塞当前线程，直到服务器线程退出
+    pub fn wait(self) {
+        let _ = self.handle.join();
+    }
+}
+
+/// 获取共享状态（忽略其它线程 panic 造成的锁中毒）
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 处理一个连接上的一个请求，响应后关闭连接
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let method = request_line.split_whitespace().next().unwrap_or_default();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let (status, response) = if method == "POST" {
+        let form = url::form_urlencoded::parse(&body).into_owned().collect();
+        ("200 OK", handle_form(&mut lock(state), form))
+    } else {
+        (
+            "405 Method Not Allowed",
+            error_response(INVALID_PARAM_MESSAGE),
+        )
+    };
+
+    let body = response.to_string();
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// 处理表单请求并返回响应内容
+fn handle_form(state: &mut State, form: Vec<(String, String)>) -> Value {
+    state.requests.push(form.clone());
+    let get = |name: &str| {
+        form.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    if let Some(key) = get("key") {
+        return match state
+            .orders
+            .iter()
+            .find(|order| order.order_id == key || order.oid() == key)
+        {
+            Some(order) => json!({
+                "code": SUCCESS_CODE,
+                "error": "成功",
+                "count": 1,
+                "data": [order.data],
+            }),
+            None => error_response(NOT_FOUND_MESSAGE),
+        };
+    }
+
+    let (Some(oid), Some(bnetpwd)) = (get("oid"), get("bnetpwd")) else {
+        return error_response(INVALID_PARAM_MESSAGE);
+    };
+
+    let setting = ["battlemode", "battleheroes", "auto", "pause"]
+        .into_iter()
+        .find_map(|name| get(name).map(|value| (name, value)));
+    let Some((name, value)) = setting else {
+        return error_response(INVALID_PARAM_MESSAGE);
+    };
+
+    if !valid_setting(name, value) {
+        return error_response(INVALID_PARAM_MESSAGE);
+    }
+
+    // 原系统在战网密码错误时也返回“没有查询到订单信息”
+    match state
+        .orders
+        .iter_mut()
+        .find(|order| order.oid() == oid && order.pwd == bnetpwd)
+    {
+        Some(order) => {
+            if !order.ignore_settings {
+                order.set_config(name, value);
+            }
+            json!({ "code": SUCCESS_CODE, "error": "成功" })
+        }
+        None => error_response(NOT_FOUND_MESSAGE),
+    }
+}
+
+/// 检查设置项的取值是否合法
+fn valid_setting(name: &str, value: &str) -> bool {
+    match name {
+        "battlemode" => matches!(value, "1" | "2" | "3" | "4" | "5"),
+        "battleheroes" => value.parse::<u32>().is_ok_and(|mask| mask <= MAX_HERO_MASK),
+        _ => matches!(value, "0" | "1"),
+    }
+}
+
+/// 错误响应
+fn error_response(message: &str) -> Value {
+    json!({ "code": 0, "error": message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BattleMode, Error, HeroSet, HttpConfig, OrderClient};
+
+    fn client(server: &MockServer) -> OrderClient {
+        OrderClient::with_config(&HttpConfig {
+            endpoint: server.endpoint(),
+            max_retries: 0,
+            ..HttpConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mock_query() {
+        let server = MockServer::start(vec![MockOrder::sample()]).unwrap();
+        let client = client(&server);
+
+        let order = client.fetch_order("1234567890123456789").unwrap();
+        assert_eq!(order.oid, "2025010100013333123456");
+        assert_eq!(order.config.mode, BattleMode::Standard);
+        assert_eq!(order.dldata.battle_records.len(), 2);
+
+        let by_oid = client.fetch_order("2025010100013333123456").unwrap();
+        assert_eq!(by_oid.gold, 13040);
+
+        assert!(matches!(
+            client.fetch_order("1"),
+            Err(Error::Api { code: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_mock_settings() {
+        let server = MockServer::start(vec![MockOrder::sample()]).unwrap();
+        let client = client(&server);
+        let oid = "2025010100013333123456";
+
+        client
+            .set_battle_mode(oid, "zwmm", BattleMode::Battlegrounds)
+            .unwrap();
+        client
+            .set_battle_heroes(oid, "zwmm", HeroSet::MAGE | HeroSet::WARLOCK)
+            .unwrap();
+        client.set_auto_claim(oid, "zwmm", true).unwrap();
+        client.set_pause(oid, "zwmm", true).unwrap();
+        assert!(client.set_pause(oid, "xxxx", false).is_err());
+
+        let order = client.fetch_order(oid).unwrap();
+        assert_eq!(order.config.mode, BattleMode::Battlegrounds);
+        assert_eq!(order.config.heroes, HeroSet::MAGE | HeroSet::WARLOCK);
+        assert!(order.config.auto_claim);
+        assert!(order.config.pause);
+        assert_eq!(server.requests().len(), 6);
+    }
+
+    #[test]
+    fn test_valid_setting() {
+        assert!(valid_setting("battlemode", "5"));
+        assert!(!valid_setting("battlemode", "6"));
+        assert!(valid_setting("battleheroes", "2047"));
+        assert!(!valid_setting("battleheroes", "2048"));
+        assert!(!valid_setting("auto", "2"));
+    }
+}
@@ -1,100 +1,573 @@
 //! 数据模型模块
+//!
+//! 包括接口返回的原始数据结构（[`ApiResponse`]、[`OrderData`]）和解析后的强类型订单模型（[`Order`] 等）。
 
-use serde::Deserialize;
-use tabled::Tabled;
+use crate::constants::*;
+use crate::schema::SchemaWarning;
+use bitflags::bitflags;
+use chrono::{DateTime, Days, FixedOffset, NaiveDate, NaiveTime};
+use chrono_tz::Asia::Shanghai;
+use chrono_tz::Tz;
+use serde::de::{self, Deserializer};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// 时间（北京时间 UTC+8）
+pub type Timestamp = DateTime<Tz>;
 
 /// API响应顶层结构
+///
+/// `T` 为订单数据的类型，需要保留接口新增的字段时可以使用 [`serde_json::Map`]。
 #[derive(Debug, Deserialize)]
-pub struct ApiResponse {
+pub struct ApiResponse<T = OrderData> {
+    /// 响应代码，1 表示成功
     pub code: i32,
+    /// 错误信息（成功时为“成功”）
     pub error: String,
+    /// 含义未知，目前固定为 1
     #[serde(default)]
-    #[allow(dead_code)]
     pub count: Option<i32>,
-    pub data: Option<Vec<OrderData>>,
+    /// 订单数据，目前长度固定为 1
+    pub data: Option<Vec<T>>,
 }
 
-/// 订单基本信息
+/// 接口返回的订单原始数据
 #[derive(Debug, Deserialize)]
 pub struct OrderData {
-    #[allow(dead_code)]
+    /// 含义未知，目前固定为空字符串
     pub am: String,
+    /// 订单编号
     pub oid: String,
+    /// 到期时间
     pub edate: String,
+    /// 配置信息（JSON 字符串）
     pub config: String,
-    #[allow(dead_code)]
+    /// 每日上号时间段和最近一次上号时间
     pub details: String,
+    /// 订单完成状态
     pub finish: String,
+    /// 订单封禁状态
     pub banned: String,
-    #[allow(dead_code)]
+    /// 含义未知，目前固定为"5"
     pub dltype: String,
+    /// 金币数量
     pub num1: String,
+    /// 卡包数量
     pub num2: String,
+    /// 奖励等级（显示时需要 +1）
     pub num3: String,
-    #[allow(dead_code)]
+    /// 含义未知，目前固定为"0"
     pub num7: String,
-    #[allow(dead_code)]
+    /// 含义未知，随着时间推移会变大
     pub num8: String,
+    /// 游戏统计数据（JSON 字符串）
     pub dldata: String,
+    /// 备注
     pub remark: String,
 }
 
+/// 已解析的订单
+#[derive(Debug, Clone)]
+pub struct Order {
+    /// 订单编号
+    pub oid: String,
+    /// 到期时间
+    pub deadline: Option<Timestamp>,
+    /// 订单状态
+    pub status: OrderStatus,
+    /// 金币数量
+    pub gold: i64,
+    /// 卡包数量
+    pub packs: i64,
+    /// 奖励等级（从 0 开始，显示时需要 +1）
+    pub reward_level: i64,
+    /// 接口的 num7 字段，含义未知，目前固定为 0
+    pub num7: i64,
+    /// 接口的 num8 字段，含义未知，随着时间推移会变大
+    pub num8: i64,
+    /// 每日上号时间段和最近一次上号时间
+    pub details: String,
+    /// 由 details 解析得到的上号信息
+    pub service: ServiceDetails,
+    /// 备注
+    pub remark: String,
+    /// 订单配置信息
+    pub config: OrderConfig,
+    /// 游戏数据统计信息
+    pub dldata: DlData,
+    /// 接口数据格式警告（通过 [`parse_raw_order`](crate::parser::parse_raw_order) 解析时填写）
+    pub warnings: Vec<SchemaWarning>,
+}
+
+/// 订单状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    /// 进行中
+    Running,
+    /// 已完成
+    Finished,
+    /// 已终止
+    Banned,
+    /// 未知状态
+    Unknown,
+}
+
+impl OrderStatus {
+    /// 根据 finish 和 banned 字段确定订单状态
+    pub fn from_flags(finish: &str, banned: &str) -> Self {
+        match (finish, banned) {
+            (STATUS_FINISHED, STATUS_RUNNING) => Self::Finished,
+            (STATUS_RUNNING, STATUS_RUNNING) => Self::Running,
+            (_, STATUS_BANNED) => Self::Banned,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// 上号信息（由 details 字段解析得到）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ServiceDetails {
+    /// 约定的每日上号时间段
+    pub window: Option<ServiceWindow>,
+    /// 最近一次上号时间
+    pub last_login: Option<Timestamp>,
+}
+
+/// 每日上号时间段（北京时间）
+///
+/// 结束时间不晚于开始时间时表示跨越零点（如 `22:00-06:00`），`24:00` 按 `00:00` 处理。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceWindow {
+    /// 开始时间
+    pub start: NaiveTime,
+    /// 结束时间
+    pub end: NaiveTime,
+}
+
+impl ServiceWindow {
+    /// 是否跨越零点
+    pub fn crosses_midnight(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// 时间是否在时间段内
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.crosses_midnight() {
+            time >= self.start || time < self.end
+        } else {
+            time >= self.start && time < self.end
+        }
+    }
+
+    /// 时间所属的上号日：跨越零点的时间段中，零点之后的部分属于前一天
+    pub fn service_date(&self, time: &Timestamp) -> NaiveDate {
+        let date = time.date_naive();
+
+        if self.crosses_midnight() && time.time() < self.end {
+            date.checked_sub_days(Days::new(1)).unwrap_or(date)
+        } else {
+            date
+        }
+    }
+}
+
+impl fmt::Display for ServiceWindow {
+    /// 格式化为`HH:MM-HH:MM`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
 /// 订单配置信息
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OrderConfig {
-    pub battlemode: Option<String>,
-    #[allow(dead_code)]
+    /// 对战模式
+    pub mode: BattleMode,
+    /// 对战英雄
+    pub heroes: HeroSet,
+    /// 是否自动领取奖励
+    pub auto_claim: bool,
+    /// 是否暂停上号
+    pub pause: bool,
+    /// 账号所在的服务器区域（猜测）
     pub region: Option<String>,
-    #[allow(dead_code)]
-    pub pause: Option<String>,
-    pub battleheroes: Option<String>,
-    pub auto: Option<String>,
+}
+
+impl Default for OrderConfig {
+    /// 各字段缺省时的默认配置
+    fn default() -> Self {
+        Self {
+            mode: BattleMode::Casual,
+            heroes: HeroSet::all(),
+            auto_claim: false,
+            pause: false,
+            region: None,
+        }
+    }
+}
+
+/// 对战模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BattleMode {
+    /// 休闲模式
+    Casual,
+    /// 标准模式
+    Standard,
+    /// 狂野模式
+    Wild,
+    /// 幻变模式
+    Twist,
+    /// 酒馆战棋
+    Battlegrounds,
+    /// 未知模式
+    Unknown,
+}
+
+impl BattleMode {
+    /// 全部已知的对战模式
+    pub const ALL: [BattleMode; 5] = [
+        Self::Casual,
+        Self::Standard,
+        Self::Wild,
+        Self::Twist,
+        Self::Battlegrounds,
+    ];
+
+    /// 根据接口中的模式代码获取对战模式
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            MODE_CASUAL => Self::Casual,
+            MODE_STANDARD => Self::Standard,
+            MODE_WILD => Self::Wild,
+            MODE_TWIST => Self::Twist,
+            MODE_BATTLEGROUNDS => Self::Battlegrounds,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// 获取接口中的模式代码，未知模式返回 `None`
+    pub fn code(self) -> Option<&'static str> {
+        match self {
+            Self::Casual => Some(MODE_CASUAL),
+            Self::Standard => Some(MODE_STANDARD),
+            Self::Wild => Some(MODE_WILD),
+            Self::Twist => Some(MODE_TWIST),
+            Self::Battlegrounds => Some(MODE_BATTLEGROUNDS),
+            Self::Unknown => None,
+        }
+    }
+}
+
+bitflags! {
+    /// 对战英雄集合
+    ///
+    /// 每个英雄按 [`HERO_NAMES`] 的顺序由低位到高位依次对应一个二进制位。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct HeroSet: u32 {
+        /// 战士
+        const WARRIOR = 1 << 0;
+        /// 萨满祭司
+        const SHAMAN = 1 << 1;
+        /// 潜行者
+        const ROGUE = 1 << 2;
+        /// 圣骑士
+        const PALADIN = 1 << 3;
+        /// 猎人
+        const HUNTER = 1 << 4;
+        /// 德鲁伊
+        const DRUID = 1 << 5;
+        /// 术士
+        const WARLOCK = 1 << 6;
+        /// 法师
+        const MAGE = 1 << 7;
+        /// 牧师
+        const PRIEST = 1 << 8;
+        /// 恶魔猎手
+        const DEMON_HUNTER = 1 << 9;
+        /// 死亡骑士
+        const DEATH_KNIGHT = 1 << 10;
+    }
+}
+
+impl HeroSet {
+    /// 根据英雄名称（中文名称或英文标识）获取单个英雄
+    pub fn from_hero_name(name: &str) -> Option<Self> {
+        HERO_NAMES
+            .iter()
+            .position(|&h| h == name)
+            .or_else(|| HERO_IDS.iter().position(|&h| h == name))
+            .map(|idx| Self::from_bits_retain(1 << idx))
+    }
+
+    /// 获取集合中各英雄的中文名称
+    pub fn names(self) -> Vec<&'static str> {
+        self.select(HERO_NAMES)
+    }
+
+    /// 获取集合中各英雄的英文标识
+    pub fn ids(self) -> Vec<&'static str> {
+        self.select(HERO_IDS)
+    }
+
+    /// 按位选出对应的名称
+    fn select(self, names: &[&'static str]) -> Vec<&'static str> {
+        names
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.bits() & (1 << i) != 0)
+            .map(|(_, &name)| name)
+            .collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for HeroSet {
+    /// 从英雄英文标识（或中文名称）列表反序列化
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .try_fold(Self::empty(), |heroes, name| {
+                Self::from_hero_name(name)
+                    .map(|hero| heroes | hero)
+                    .ok_or_else(|| de::Error::custom(format!("未知英雄: {}", name)))
+            })
+    }
+}
+
+impl Serialize for HeroSet {
+    /// 序列化为英雄英文标识列表
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let ids = self.ids();
+        let mut seq = serializer.serialize_seq(Some(ids.len()))?;
+
+        for id in ids {
+            seq.serialize_element(id)?;
+        }
+
+        seq.end()
+    }
 }
 
 /// 游戏数据统计信息
-#[derive(Debug)]
+///
+/// 各项的含义见 `doc/API.md`，含义未知的项原样保存在 [`unknown`](Self::unknown) 中。
+#[derive(Debug, Clone, Default)]
 pub struct DlData {
-    #[allow(dead_code)]
-    pub basic_info: Vec<serde_json::Value>,
+    /// 最近一次的上号日期（下标 3）
+    pub last_date: Option<NaiveDate>,
+    /// 最近一次上号当天获胜的次数（下标 4）
+    pub today_wins: i64,
+    /// 最近一次上号当天失败的次数（下标 5）
+    pub today_losses: i64,
+    /// 账号所在的服务器区域（下标 7）
+    pub region: Option<String>,
+    /// 最近一次上号时间（下标 8）
+    pub last_login: Option<Timestamp>,
+    /// 金币记录
     pub gold_records: Vec<GoldRecord>,
+    /// 经验记录
     pub exp_records: Vec<ExpRecord>,
+    /// 对战记录
     pub battle_records: Vec<BattleRecord>,
+    /// 含义未知的字符串 ID 列表（下标 13）
+    pub ids: Vec<String>,
+    /// 含义未知的项（下标到值，包括接口新增的项）
+    pub unknown: BTreeMap<usize, serde_json::Value>,
+    /// 今日对战次数
     pub today_battles: usize,
 }
 
 /// 金币记录
-#[derive(Debug, Tabled)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GoldRecord {
-    #[tabled(rename = "时间")]
-    pub time: String,
-    #[tabled(rename = "金币变化")]
-    pub gold_change: String,
-    #[tabled(rename = "卡包变化")]
-    pub pack_change: String,
+    /// 时间
+    #[serde(deserialize_with = "deserialize_optional_time")]
+    pub time: Option<Timestamp>,
+    /// 金币变化
+    pub gold_change: i64,
+    /// 卡包变化
+    pub pack_change: i64,
 }
 
 /// 经验记录
-#[derive(Debug, Tabled)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExpRecord {
-    #[tabled(rename = "时间")]
-    pub time: String,
-    #[tabled(rename = "经验变化")]
-    pub exp_change: String,
-    #[tabled(rename = "等级")]
-    pub level: String,
-    #[tabled(rename = "总经验")]
-    pub total_exp: String,
-    #[tabled(rename = "当前等级经验")]
-    pub current_level_exp: String,
+    /// 时间
+    #[serde(deserialize_with = "deserialize_optional_time")]
+    pub time: Option<Timestamp>,
+    /// 经验变化
+    pub exp_change: i64,
+    /// 等级（从 0 开始，显示时需要 +1）
+    pub level: i64,
+    /// 总经验
+    pub total_exp: i64,
+    /// 当前等级经验
+    pub current_level_exp: i64,
 }
 
 /// 对战记录
-#[derive(Debug, Tabled)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BattleRecord {
-    #[tabled(rename = "时间")]
-    pub time: String,
-    #[tabled(rename = "结果")]
-    pub result: String,
-    #[tabled(rename = "经验")]
-    pub exp: String,
+    /// 对局开始时间
+    #[serde(deserialize_with = "deserialize_optional_time")]
+    pub time: Option<Timestamp>,
+    /// 对局结束时间（接口中为 0 时为空）
+    #[serde(default, deserialize_with = "deserialize_optional_time")]
+    pub end_time: Option<Timestamp>,
+    /// 对战结果
+    pub result: BattleResult,
+    /// 经验变化
+    pub exp: i64,
+}
+
+impl BattleRecord {
+    /// 对局时长，没有结束时间或时间不完整时返回 `None`
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        let (start, end) = (self.time?, self.end_time?);
+        (end > start).then(|| end - start)
+    }
+}
+
+/// 对战结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BattleResult {
+    /// 胜利
+    Win,
+    /// 失败
+    Loss,
+    /// 未知
+    Unknown,
+}
+
+impl BattleResult {
+    /// 根据接口中的结果代码获取对战结果
+    pub fn from_code(code: i64) -> Self {
+        match code {
+            BATTLE_WIN => Self::Win,
+            BATTLE_LOSS => Self::Loss,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// 反序列化 RFC 3339 格式的时间，并转换为北京时间
+pub(crate) fn deserialize_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Timestamp, D::Error> {
+    Ok(DateTime::<FixedOffset>::deserialize(deserializer)?.with_timezone(&Shanghai))
+}
+
+/// 反序列化可能为空的 RFC 3339 格式时间，并转换为北京时间
+pub(crate) fn deserialize_optional_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Timestamp>, D::Error> {
+    Ok(Option::<DateTime<FixedOffset>>::deserialize(deserializer)?
+        .map(|time| time.with_timezone(&Shanghai)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_status_from_flags() {
+        assert_eq!(OrderStatus::from_flags("0", "0"), OrderStatus::Running);
+        assert_eq!(OrderStatus::from_flags("1", "0"), OrderStatus::Finished);
+        assert_eq!(OrderStatus::from_flags("0", "1"), OrderStatus::Banned);
+        assert_eq!(OrderStatus::from_flags("2", "0"), OrderStatus::Unknown);
+    }
+
+    #[test]
+    fn test_battle_mode_code() {
+        for mode in BattleMode::ALL {
+            assert_eq!(BattleMode::from_code(mode.code().unwrap()), mode);
+        }
+        assert_eq!(BattleMode::from_code("9"), BattleMode::Unknown);
+    }
+
+    #[test]
+    fn test_hero_set_names() {
+        let heroes = HeroSet::from_bits_truncate(192);
+        assert_eq!(heroes, HeroSet::WARLOCK | HeroSet::MAGE);
+        assert_eq!(heroes.names(), vec!["术士", "法师"]);
+        assert_eq!(heroes.ids(), vec!["warlock", "mage"]);
+        assert_eq!(HeroSet::all().bits(), MAX_HERO_MASK);
+    }
+
+    #[test]
+    fn test_hero_set_from_name() {
+        assert_eq!(
+            HeroSet::from_hero_name("恶魔猎手"),
+            Some(HeroSet::DEMON_HUNTER)
+        );
+        assert_eq!(
+            HeroSet::from_hero_name("death_knight"),
+            Some(HeroSet::DEATH_KNIGHT)
+        );
+        assert_eq!(HeroSet::from_hero_name("神谕者"), None);
+    }
+
+    #[test]
+    fn test_hero_set_serialize() {
+        let json = serde_json::to_string(&(HeroSet::WARRIOR | HeroSet::PRIEST)).unwrap();
+        assert_eq!(json, r#"["warrior","priest"]"#);
+    }
+
+    #[test]
+    fn test_service_window() {
+        let time = |s: &str| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        let day = ServiceWindow {
+            start: time("08:00"),
+            end: time("16:00"),
+        };
+        assert!(day.contains(time("08:00")));
+        assert!(!day.contains(time("16:00")));
+        assert!(!day.contains(time("07:59")));
+        assert_eq!(day.to_string(), "08:00-16:00");
+
+        let night = ServiceWindow {
+            start: time("22:00"),
+            end: time("06:00"),
+        };
+        assert!(night.contains(time("23:30")));
+        assert!(night.contains(time("05:59")));
+        assert!(!night.contains(time("12:00")));
+
+        let at = crate::utils::parse_datetime("2025-11-13 02:00:00").unwrap();
+        assert_eq!(night.service_date(&at).to_string(), "2025-11-12");
+        assert_eq!(day.service_date(&at).to_string(), "2025-11-13");
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let record = BattleRecord {
+            time: crate::utils::to_shanghai_time(1762927893),
+            end_time: crate::utils::to_shanghai_time(1762928742),
+            result: BattleResult::Win,
+            exp: 14,
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(serde_json::from_str::<BattleRecord>(&json).unwrap(), record);
+
+        assert_eq!(record.duration().unwrap().num_seconds(), 849);
+
+        // 早期保存的历史记录没有对局结束时间
+        let old: BattleRecord =
+            serde_json::from_str(r#"{"time":"2025-11-12T14:11:33+08:00","result":"win","exp":14}"#)
+                .unwrap();
+        assert_eq!(old.end_time, None);
+        assert_eq!(old.duration(), None);
+
+        let heroes: HeroSet = serde_json::from_str(r#"["warrior","priest"]"#).unwrap();
+        assert_eq!(heroes, HeroSet::WARRIOR | HeroSet::PRIEST);
+    }
 }
@@ -0,0 +1,95 @@
+//! 订单编号缓存模块
+//!
+//! 将通过订单号查询得到的订单编号（oid）保存到本地数据目录，之后执行订单相关操作时无需再次查询。
+
+use crate::paths;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// 订单编号缓存
+#[derive(Debug, Default)]
+pub struct OidCache {
+    /// 缓存文件路径，为 `None` 时只在内存中缓存
+    path: Option<PathBuf>,
+    /// 订单号到订单编号的映射
+    oids: BTreeMap<String, String>,
+}
+
+impl OidCache {
+    /// 读取本地数据目录下的缓存文件，文件不存在时返回空缓存
+    pub fn load() -> Result<Self> {
+        Self::load_from(paths::data_dir()?.join("oids.json"))
+    }
+
+    /// 读取指定的缓存文件
+    fn load_from(path: PathBuf) -> Result<Self> {
+        let oids = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("解析订单编号缓存 {} 失败", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("读取订单编号缓存 {} 失败", path.display()));
+            }
+        };
+
+        Ok(Self {
+            path: Some(path),
+            oids,
+        })
+    }
+
+    /// 获取订单号对应的订单编号
+    pub fn get(&self, order_id: &str) -> Option<&str> {
+        self.oids.get(order_id).map(String::as_str)
+    }
+
+    /// 保存订单号对应的订单编号
+    pub fn insert(&mut self, order_id: &str, oid: &str) -> Result<()> {
+        if order_id == oid || self.get(order_id) == Some(oid) {
+            return Ok(());
+        }
+
+        self.oids.insert(order_id.to_string(), oid.to_string());
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("创建目录 {} 失败", dir.display()))?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.oids)?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("写入订单编号缓存 {} 失败", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oid_cache_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("hs-order-oids-{}", std::process::id()))
+            .join("oids.json");
+        let _ = fs::remove_file(&path);
+
+        let mut cache = OidCache::load_from(path.clone()).unwrap();
+        assert_eq!(cache.get("1234567890123456789"), None);
+        cache
+            .insert("1234567890123456789", "2025010100013333123456")
+            .unwrap();
+
+        let cache = OidCache::load_from(path.clone()).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(
+            cache.get("1234567890123456789"),
+            Some("2025010100013333123456")
+        );
+    }
+}
@@ -1,69 +1,207 @@
 //! 数据解析模块
 
 use crate::constants::*;
-use crate::models::{BattleRecord, DlData, ExpRecord, GoldRecord, OrderConfig};
-use crate::utils::{format_signed, format_timestamp};
-use anyhow::{Context, Result, anyhow, bail};
-use chrono::Local;
+use crate::error::{Error, Result};
+use crate::models::{
+    BattleMode, BattleRecord, BattleResult, DlData, ExpRecord, GoldRecord, HeroSet, Order,
+    OrderConfig, OrderData, OrderStatus, ServiceDetails, ServiceWindow,
+};
+use crate::schema::validate;
+use crate::utils::{parse_datetime, to_shanghai_time};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use chrono_tz::Asia::Shanghai;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// config字段的原始结构
+#[derive(Debug, Deserialize)]
+struct RawOrderConfig {
+    battlemode: Option<String>,
+    region: Option<String>,
+    pause: Option<String>,
+    battleheroes: Option<String>,
+    auto: Option<String>,
+}
+
+/// 解析订单数据（包括config字段和dldata字段）
+pub fn parse_order(data: OrderData) -> Result<Order> {
+    let config = parse_order_config(&data.config)?;
+    let dldata = parse_dldata(&data.dldata)?;
+
+    Ok(Order {
+        deadline: parse_datetime(&data.edate),
+        status: OrderStatus::from_flags(&data.finish, &data.banned),
+        gold: data.num1.parse().unwrap_or(0),
+        packs: data.num2.parse().unwrap_or(0),
+        reward_level: data.num3.parse().unwrap_or(0),
+        num7: data.num7.parse().unwrap_or(0),
+        num8: data.num8.parse().unwrap_or(0),
+        oid: data.oid,
+        service: parse_details(&data.details),
+        details: data.details,
+        remark: data.remark,
+        config,
+        dldata,
+        warnings: Vec::new(),
+    })
+}
+
+/// 校验并解析接口返回的未经解析的订单数据
+///
+/// 与 [`parse_order`] 相同，另外将数据格式与预期不一致的地方记录在 [`Order::warnings`] 中。
+pub fn parse_raw_order(data: Map<String, Value>) -> Result<Order> {
+    let warnings = validate(&data);
+    let data: OrderData = serde_json::from_value(Value::Object(data)).map_err(Error::Json)?;
+
+    Ok(Order {
+        warnings,
+        ..parse_order(data)?
+    })
+}
 
 /// 解析config字段（订单配置信息）
 pub fn parse_order_config(config_str: &str) -> Result<OrderConfig> {
-    let cfg: OrderConfig = serde_json::from_str(config_str)?;
-    Ok(cfg)
+    let raw: RawOrderConfig = serde_json::from_str(config_str).map_err(Error::ConfigFormat)?;
+
+    Ok(OrderConfig {
+        mode: raw
+            .battlemode
+            .as_deref()
+            .map(BattleMode::from_code)
+            .unwrap_or(BattleMode::Casual),
+        heroes: raw
+            .battleheroes
+            .as_deref()
+            .map(parse_battle_heroes)
+            .unwrap_or(HeroSet::all()),
+        auto_claim: raw.auto.as_deref() == Some("1"),
+        pause: raw.pause.as_deref() == Some("1"),
+        region: raw.region,
+    })
+}
+
+/// 解析details字段（每日上号时间段和最近一次上号时间）
+///
+/// 格式如`08:00-16:00  (2025/11/13 08:12:55)`，无法解析的部分为空。
+pub fn parse_details(details: &str) -> ServiceDetails {
+    let (window, last_login) = match details.split_once('(') {
+        Some((window, rest)) => (window, rest.trim_end().strip_suffix(')')),
+        None => (details, None),
+    };
+
+    ServiceDetails {
+        window: parse_service_window(window.trim()),
+        last_login: last_login
+            .and_then(|s| NaiveDateTime::parse_from_str(s.trim(), "%Y/%m/%d %H:%M:%S").ok())
+            .and_then(|naive| Shanghai.from_local_datetime(&naive).single()),
+    }
+}
+
+/// 解析上号时间段（如`08:00-16:00`）
+fn parse_service_window(s: &str) -> Option<ServiceWindow> {
+    let parse = |s: &str| match s.trim() {
+        "24:00" => Some(NaiveTime::MIN),
+        s => NaiveTime::parse_from_str(s, "%H:%M").ok(),
+    };
+    let (start, end) = s.split_once('-')?;
+
+    Some(ServiceWindow {
+        start: parse(start)?,
+        end: parse(end)?,
+    })
 }
 
 /// 解析dldata字段（游戏统计数据）
 pub fn parse_dldata(dldata_str: &str) -> Result<DlData> {
     let arr: Vec<serde_json::Value> =
-        serde_json::from_str(dldata_str).context("dldata格式不正确")?;
+        serde_json::from_str(dldata_str).map_err(Error::DlDataFormat)?;
 
     if arr.len() < 13 {
-        bail!("dldata数据不完整");
+        return Err(Error::DlDataIncomplete);
     }
 
-    let basic_info = arr.get(0..BASIC_INFO_SIZE).unwrap_or(&[]).to_vec();
-    let today_battles = calculate_today_battles(&arr);
-    let gold_records = parse_gold_records(&arr)?;
-    let exp_records = parse_exp_records(&arr)?;
-    let battle_records = parse_battle_records(&arr)?;
+    let last_date = arr
+        .get(DATE_INDEX)
+        .and_then(value_to_string)
+        .and_then(|s| NaiveDate::parse_from_str(&s, "%Y%m%d").ok());
+    let today_wins = arr.get(WINS_INDEX).and_then(value_to_i64).unwrap_or(0);
+    let today_losses = arr.get(LOSSES_INDEX).and_then(value_to_i64).unwrap_or(0);
+    let today_battles = calculate_today_battles(last_date, today_wins, today_losses);
 
     Ok(DlData {
-        basic_info,
-        gold_records,
-        exp_records,
-        battle_records,
+        last_date,
+        today_wins,
+        today_losses,
+        region: arr
+            .get(REGION_INDEX)
+            .and_then(value_to_string)
+            .filter(|s| !s.is_empty()),
+        last_login: arr
+            .get(LAST_LOGIN_INDEX)
+            .and_then(value_to_i64)
+            .and_then(to_shanghai_time),
+        gold_records: parse_gold_records(&arr)?,
+        exp_records: parse_exp_records(&arr)?,
+        battle_records: parse_battle_records(&arr)?,
+        ids: arr
+            .get(ID_LIST_INDEX)
+            .and_then(|v| v.as_array())
+            .map(|ids| ids.iter().filter_map(value_to_string).collect())
+            .unwrap_or_default(),
+        unknown: arr
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !is_known_index(*i))
+            .map(|(i, v)| (i, v.clone()))
+            .collect(),
         today_battles,
     })
 }
 
+/// dldata 数组的下标是否已被解析为具体字段
+fn is_known_index(index: usize) -> bool {
+    matches!(
+        index,
+        DATE_INDEX
+            | WINS_INDEX
+            | LOSSES_INDEX
+            | REGION_INDEX
+            | LAST_LOGIN_INDEX
+            | GOLD_RECORDS_INDEX
+            | EXP_RECORDS_INDEX
+            | BATTLE_RECORDS_INDEX
+            | ID_LIST_INDEX
+    )
+}
+
+/// 将字符串或数字转换为字符串
+fn value_to_string(v: &serde_json::Value) -> Option<String> {
+    v.as_str()
+        .map(|s| s.to_string())
+        .or_else(|| v.as_i64().map(|n| n.to_string()))
+}
+
+/// 将数字或整数字符串转换为整数
+fn value_to_i64(v: &serde_json::Value) -> Option<i64> {
+    v.as_i64().or_else(|| v.as_str()?.trim().parse().ok())
+}
+
 /// 计算今日对战次数
-fn calculate_today_battles(arr: &[serde_json::Value]) -> usize {
-    let today = Local::now()
-        .with_timezone(&Shanghai)
-        .format("%Y%m%d")
-        .to_string();
-
-    let date_str = arr.get(DATE_INDEX).and_then(|v| {
-        v.as_str()
-            .map(|s| s.to_string())
-            .or_else(|| v.as_i64().map(|n| n.to_string()))
-    });
-
-    if date_str.as_deref() != Some(&today) {
+///
+/// 最近一次的上号日期不是今天时为 0。
+fn calculate_today_battles(last_date: Option<NaiveDate>, wins: i64, losses: i64) -> usize {
+    let today = Local::now().with_timezone(&Shanghai).date_naive();
+
+    if last_date != Some(today) {
         return 0;
     }
 
-    let wins = arr.get(WINS_INDEX).and_then(|v| v.as_i64()).unwrap_or(0) as usize;
-    let losses = arr.get(LOSSES_INDEX).and_then(|v| v.as_i64()).unwrap_or(0) as usize;
-    wins + losses
+    (wins.max(0) + losses.max(0)) as usize
 }
 
 /// 解析金币记录
 fn parse_gold_records(arr: &[serde_json::Value]) -> Result<Vec<GoldRecord>> {
-    let gold_data = arr
-        .get(GOLD_RECORDS_INDEX)
-        .ok_or_else(|| anyhow!("金币记录索引超出范围"))?;
+    let gold_data = arr.get(GOLD_RECORDS_INDEX).ok_or(Error::DlDataIncomplete)?;
 
     if let Some(records_array) = gold_data.as_array() {
         Ok(records_array
@@ -80,15 +218,9 @@ fn parse_single_gold_record(record: &serde_json::Value) -> Option<GoldRecord> {
     let record_array = record.as_array()?;
     if record_array.len() >= 3 {
         Some(GoldRecord {
-            time: format_timestamp(record_array[0].as_i64().unwrap_or(0)),
-            gold_change: record_array[1]
-                .as_i64()
-                .map(format_signed)
-                .unwrap_or_else(|| "0".to_string()),
-            pack_change: record_array[2]
-                .as_i64()
-                .map(format_signed)
-                .unwrap_or_else(|| "0".to_string()),
+            time: to_shanghai_time(record_array[0].as_i64().unwrap_or(0)),
+            gold_change: record_array[1].as_i64().unwrap_or(0),
+            pack_change: record_array[2].as_i64().unwrap_or(0),
         })
     } else {
         None
@@ -97,9 +229,7 @@ fn parse_single_gold_record(record: &serde_json::Value) -> Option<GoldRecord> {
 
 /// 解析经验记录
 fn parse_exp_records(arr: &[serde_json::Value]) -> Result<Vec<ExpRecord>> {
-    let exp_data = arr
-        .get(EXP_RECORDS_INDEX)
-        .ok_or_else(|| anyhow!("经验记录索引超出范围"))?;
+    let exp_data = arr.get(EXP_RECORDS_INDEX).ok_or(Error::DlDataIncomplete)?;
 
     if let Some(records_array) = exp_data.as_array() {
         Ok(records_array
@@ -116,23 +246,11 @@ fn parse_single_exp_record(record: &serde_json::Value) -> Option<ExpRecord> {
     let record_array = record.as_array()?;
     if record_array.len() >= 5 {
         Some(ExpRecord {
-            time: format_timestamp(record_array[0].as_i64().unwrap_or(0)),
-            exp_change: record_array[1]
-                .as_i64()
-                .map(format_signed)
-                .unwrap_or_else(|| "0".to_string()),
-            level: record_array[2]
-                .as_i64()
-                .map(|n| n.to_string())
-                .unwrap_or_else(|| "0".to_string()),
-            total_exp: record_array[3]
-                .as_i64()
-                .map(|n| n.to_string())
-                .unwrap_or_else(|| "0".to_string()),
-            current_level_exp: record_array[4]
-                .as_i64()
-                .map(|n| n.to_string())
-                .unwrap_or_else(|| "0".to_string()),
+            time: to_shanghai_time(record_array[0].as_i64().unwrap_or(0)),
+            exp_change: record_array[1].as_i64().unwrap_or(0),
+            level: record_array[2].as_i64().unwrap_or(0),
+            total_exp: record_array[3].as_i64().unwrap_or(0),
+            current_level_exp: record_array[4].as_i64().unwrap_or(0),
         })
     } else {
         None
@@ -143,7 +261,7 @@ fn parse_single_exp_record(record: &serde_json::Value) -> Option<ExpRecord> {
 fn parse_battle_records(arr: &[serde_json::Value]) -> Result<Vec<BattleRecord>> {
     let battle_data = arr
         .get(BATTLE_RECORDS_INDEX)
-        .ok_or_else(|| anyhow!("对战记录索引超出范围"))?;
+        .ok_or(Error::DlDataIncomplete)?;
 
     if let Some(records_array) = battle_data.as_array() {
         Ok(records_array
@@ -160,43 +278,23 @@ fn parse_single_battle_record(record: &serde_json::Value) -> Option<BattleRecord
     let record_array = record.as_array()?;
     if record_array.len() >= 4 {
         let start_timestamp = record_array[3].as_i64().unwrap_or(0);
+        let end_timestamp = record_array[0].as_i64().unwrap_or(0);
         let result_code = record_array[1].as_i64().unwrap_or(0);
 
         Some(BattleRecord {
-            time: format_timestamp(start_timestamp),
-            result: get_battle_result_text(result_code),
-            exp: record_array[2]
-                .as_i64()
-                .map(format_signed)
-                .unwrap_or_else(|| "0".to_string()),
+            time: to_shanghai_time(start_timestamp),
+            end_time: to_shanghai_time(end_timestamp),
+            result: BattleResult::from_code(result_code),
+            exp: record_array[2].as_i64().unwrap_or(0),
         })
     } else {
         None
     }
 }
 
-/// 获取对战结果文本
-fn get_battle_result_text(result_code: i64) -> String {
-    match result_code {
-        BATTLE_WIN => "胜利".into(),
-        BATTLE_LOSS => "失败".into(),
-        BATTLE_UNKNOWN => "未知".into(),
-        _ => format!("未知 {}", result_code),
-    }
-}
-
 /// 解析对战英雄掩码
-pub fn parse_battle_heroes(battleheroes: &str) -> Vec<String> {
-    let mask = battleheroes.parse::<u32>().unwrap_or(0);
-    let mut heroes = Vec::new();
-
-    for (i, &hero_name) in HERO_NAMES.iter().enumerate() {
-        if mask & (1 << i) != 0 {
-            heroes.push(hero_name.to_string());
-        }
-    }
-
-    heroes
+pub fn parse_battle_heroes(battleheroes: &str) -> HeroSet {
+    HeroSet::from_bits_truncate(battleheroes.parse::<u32>().unwrap_or(0))
 }
 
 #[cfg(test)]
@@ -213,9 +311,72 @@ mod tests {
         });
         let s = cfg.to_string();
         let r = parse_order_config(&s).unwrap();
-        assert_eq!(r.battlemode.as_deref(), Some("3"));
-        assert_eq!(r.battleheroes.as_deref(), Some("2047"));
-        assert_eq!(r.auto.as_deref(), None);
+        assert_eq!(r.mode, BattleMode::Wild);
+        assert_eq!(r.heroes, HeroSet::all());
+        assert!(!r.auto_claim);
+        assert!(!r.pause);
+        assert_eq!(r.region.as_deref(), Some("CN"));
+    }
+
+    #[test]
+    fn test_parse_details() {
+        let details = parse_details("08:00-16:00  (2025/11/13 08:12:55)");
+        assert_eq!(details.window.unwrap().to_string(), "08:00-16:00");
+        assert_eq!(
+            details.last_login.unwrap().to_rfc3339(),
+            "2025-11-13T08:12:55+08:00"
+        );
+
+        let details = parse_details("20:00-24:00");
+        let window = details.window.unwrap();
+        assert!(window.crosses_midnight());
+        assert!(window.contains(NaiveTime::from_hms_opt(23, 59, 0).unwrap()));
+        assert_eq!(details.last_login, None);
+
+        assert_eq!(parse_details(""), ServiceDetails::default());
+        assert_eq!(parse_details("全天 ()").window, None);
+    }
+
+    #[test]
+    fn test_parse_order_config_defaults() {
+        let r = parse_order_config("{}").unwrap();
+        assert_eq!(r, OrderConfig::default());
+    }
+
+    #[test]
+    fn test_parse_order_ok() {
+        let data = OrderData {
+            am: String::new(),
+            oid: "2025010100013333123456".into(),
+            edate: "2025-12-18 20:05:30".into(),
+            config: r#"{"auto":"1","battleheroes":"192","battlemode":"5","pause":"1"}"#.into(),
+            details: "08:00-16:00  (2025/11/13 08:12:55)".into(),
+            finish: "0".into(),
+            banned: "0".into(),
+            dltype: "5".into(),
+            num1: "13040".into(),
+            num2: "1".into(),
+            num3: "44".into(),
+            num7: "0".into(),
+            num8: "428301".into(),
+            dldata: serde_json::to_string(&vec![json!(null); 13]).unwrap(),
+            remark: String::new(),
+        };
+        let order = parse_order(data).unwrap();
+        assert_eq!(order.status, OrderStatus::Running);
+        assert_eq!(
+            order.deadline.unwrap().to_rfc3339(),
+            "2025-12-18T20:05:30+08:00"
+        );
+        assert_eq!(order.gold, 13040);
+        assert_eq!(order.service.window.unwrap().to_string(), "08:00-16:00");
+        assert_eq!(order.packs, 1);
+        assert_eq!(order.reward_level, 44);
+        assert_eq!((order.num7, order.num8), (0, 428301));
+        assert_eq!(order.config.mode, BattleMode::Battlegrounds);
+        assert_eq!(order.config.heroes, HeroSet::WARLOCK | HeroSet::MAGE);
+        assert!(order.config.auto_claim);
+        assert!(order.config.pause);
     }
 
     #[test]
@@ -250,30 +411,59 @@ mod tests {
             [0, 0, 0, 1762925746],
             [1762925584, 1, 154, 1762924010],
         ]);
+        arr.push(json!(["9170774525", 9169980593_i64]));
+        arr.push(json!(0));
+        arr.push(json!(0));
+        arr.push(json!("new"));
         let s = serde_json::to_string(&arr).unwrap();
         let d = parse_dldata(&s).unwrap();
         assert_eq!(d.today_battles, 30);
+        assert_eq!(d.last_date, Some(now.date_naive()));
+        assert_eq!((d.today_wins, d.today_losses), (3, 27));
+        assert_eq!(d.region.as_deref(), Some("CHN"));
+        assert_eq!(d.last_login.unwrap().timestamp(), now.timestamp());
+        assert_eq!(d.ids, ["9170774525", "9169980593"]);
+        assert_eq!(
+            d.unknown.keys().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 6, 9, 14, 15, 16]
+        );
+        assert_eq!(d.unknown[&16], json!("new"));
         assert_eq!(d.gold_records.len(), 4);
-        assert_eq!(d.gold_records[0].time, "2025-10-08 12:45:00");
-        assert_eq!(d.gold_records[0].gold_change, "+50");
-        assert_eq!(d.gold_records[0].pack_change, "0");
+        assert_eq!(
+            d.gold_records[0].time.unwrap().to_rfc3339(),
+            "2025-10-08T12:45:00+08:00"
+        );
+        assert_eq!(d.gold_records[0].gold_change, 50);
+        assert_eq!(d.gold_records[0].pack_change, 0);
         assert_eq!(d.exp_records.len(), 3);
-        assert_eq!(d.exp_records[0].time, "2025-11-12 12:04:03");
-        assert_eq!(d.exp_records[0].exp_change, "+139");
-        assert_eq!(d.exp_records[0].level, "43");
-        assert_eq!(d.exp_records[0].total_exp, "30693");
-        assert_eq!(d.exp_records[0].current_level_exp, "93");
+        assert_eq!(
+            d.exp_records[0].time.unwrap().to_rfc3339(),
+            "2025-11-12T12:04:03+08:00"
+        );
+        assert_eq!(d.exp_records[0].exp_change, 139);
+        assert_eq!(d.exp_records[0].level, 43);
+        assert_eq!(d.exp_records[0].total_exp, 30693);
+        assert_eq!(d.exp_records[0].current_level_exp, 93);
         assert_eq!(d.battle_records.len(), 3);
-        assert_eq!(d.battle_records[0].time, "2025-11-12 14:11:33");
-        assert!(d.battle_records[0].result.contains("失败"));
-        assert_eq!(d.battle_records[0].exp, "+14");
-        assert!(d.battle_records[1].result.contains("未知"));
-        assert_eq!(d.battle_records[1].exp, "0");
+        assert_eq!(
+            d.battle_records[0].time.unwrap().to_rfc3339(),
+            "2025-11-12T14:11:33+08:00"
+        );
+        assert_eq!(
+            d.battle_records[0].end_time.unwrap().to_rfc3339(),
+            "2025-11-12T14:25:42+08:00"
+        );
+        assert_eq!(d.battle_records[0].result, BattleResult::Loss);
+        assert_eq!(d.battle_records[0].exp, 14);
+        assert_eq!(d.battle_records[1].result, BattleResult::Unknown);
+        assert_eq!(d.battle_records[1].exp, 0);
+        assert_eq!(d.battle_records[1].end_time, None);
+        assert_eq!(d.battle_records[2].result, BattleResult::Win);
     }
 
     #[test]
     fn test_parse_battle_heroes_mask() {
-        let v = parse_battle_heroes("2000");
+        let v = parse_battle_heroes("2000").names();
         assert!(v.len() == 6);
         assert_eq!(v[0], "猎人");
         assert_eq!(v[1], "术士");
@@ -0,0 +1,31 @@
+//! 本地文件路径模块
+
+use anyhow::{Context, Result};
+use std::env;
+use std::path::PathBuf;
+
+/// 数据目录环境变量
+const DATA_DIR_ENV: &str = "HS_ORDER_DATA_DIR";
+/// 配置文件路径环境变量
+const CONFIG_ENV: &str = "HS_ORDER_CONFIG";
+
+/// 本地数据目录
+///
+/// 设置了 `HS_ORDER_DATA_DIR` 环境变量时使用该目录，否则使用系统默认的数据目录（如 Linux 下为 `~/.local/share/hs-order-cli`）。
+pub fn data_dir() -> Result<PathBuf> {
+    match env::var_os(DATA_DIR_ENV) {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => dirs::data_dir()
+            .map(|dir| dir.join("hs-order-cli"))
+            .context("无法确定本地数据目录"),
+    }
+}
+
+/// 配置文件路径
+///
+/// 设置了 `HS_ORDER_CONFIG` 环境变量时使用该文件，否则使用系统默认的配置目录（如 Linux 下为 `~/.config/hs-order-cli/config.toml`）。
+pub fn config_path() -> Option<PathBuf> {
+    env::var_os(CONFIG_ENV)
+        .map(PathBuf::from)
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("hs-order-cli").join("config.toml")))
+}
@@ -0,0 +1,230 @@
+//! 进度预估模块
+//!
+//! 根据经验记录计算日均经验，预估订单截止时的通行证等级，并判断能否达到购买的目标等级。
+
+use crate::constants::EXP_PER_LEVEL;
+use crate::models::{Order, Timestamp};
+
+/// 计算日均经验时的最短统计时长（天），避免记录只覆盖几个小时时高估进度
+const MIN_SAMPLE_DAYS: f64 = 1.0;
+/// 一天的秒数
+const SECS_PER_DAY: f64 = 86400.0;
+
+/// 订单进度预估
+///
+/// 等级与 [`Order::reward_level`] 一致，从 0 开始，显示时需要 +1。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// 当前等级
+    pub level: i64,
+    /// 当前等级经验
+    pub level_exp: i64,
+    /// 日均经验
+    pub daily_exp: f64,
+    /// 经验记录覆盖的天数
+    pub sample_days: f64,
+    /// 距离截止时间的天数（已截止时为 0）
+    pub remaining_days: f64,
+    /// 预计截止时的等级
+    pub projected_level: i64,
+}
+
+impl Progress {
+    /// 根据订单的经验记录预估截止时的等级
+    ///
+    /// 订单没有截止时间或带时间的经验记录少于 2 条时返回 `None`。
+    pub fn estimate(order: &Order, now: &Timestamp) -> Option<Self> {
+        let deadline = order.deadline?;
+        let mut records: Vec<_> = order
+            .dldata
+            .exp_records
+            .iter()
+            .filter_map(|record| record.time.map(|time| (time, record)))
+            .collect();
+        records.sort_by_key(|(time, _)| *time);
+
+        let [(first, _), rest @ ..] = records.as_slice() else {
+            return None;
+        };
+        if rest.is_empty() {
+            return None;
+        }
+
+        // 最早一条记录的经验是在其时间之前获得的，不计入统计时长内的经验
+        let gained: i64 = rest.iter().map(|(_, record)| record.exp_change).sum();
+        let sample_days = (*now - *first).num_seconds() as f64 / SECS_PER_DAY;
+        let daily_exp = gained as f64 / sample_days.max(MIN_SAMPLE_DAYS);
+        let remaining_days = ((deadline - *now).num_seconds() as f64 / SECS_PER_DAY).max(0.0);
+
+        let level = order.reward_level;
+        let level_exp = current_level_exp(order);
+        let projected_exp = level_exp + (daily_exp * remaining_days) as i64;
+
+        Some(Self {
+            level,
+            level_exp,
+            daily_exp,
+            sample_days,
+            remaining_days,
+            projected_level: level + projected_exp / EXP_PER_LEVEL,
+        })
+    }
+
+    /// 达到等级 `level` 还需要的经验
+    pub fn exp_needed(&self, level: i64) -> i64 {
+        ((level - self.level) * EXP_PER_LEVEL - self.level_exp).max(0)
+    }
+
+    /// 按预估进度能否在截止时达到等级 `level`
+    pub fn reaches(&self, level: i64) -> bool {
+        self.projected_level >= level
+    }
+
+    /// 在截止时达到等级 `level` 所需的日均经验，已截止时返回 `None`
+    pub fn required_daily_exp(&self, level: i64) -> Option<f64> {
+        (self.remaining_days > 0.0).then(|| self.exp_needed(level) as f64 / self.remaining_days)
+    }
+}
+
+/// 订单当前等级的经验
+///
+/// 取时间最新的经验记录，其等级还停留在之前的等级（或没有带时间的经验记录）时按 0 计算。
+pub fn current_level_exp(order: &Order) -> i64 {
+    order
+        .dldata
+        .exp_records
+        .iter()
+        .filter(|record| record.time.is_some())
+        .max_by_key(|record| record.time)
+        .filter(|record| record.level == order.reward_level)
+        .map_or(0, |record| record.current_level_exp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DlData, ExpRecord, OrderConfig, OrderStatus, ServiceDetails};
+    use crate::utils::parse_datetime;
+
+    fn order(deadline: &str, records: &[(&str, i64, i64, i64)]) -> Order {
+        Order {
+            oid: "2025010100013333123456".into(),
+            deadline: parse_datetime(deadline),
+            status: OrderStatus::Running,
+            gold: 0,
+            packs: 0,
+            reward_level: records.first().map_or(0, |r| r.2),
+            num7: 0,
+            num8: 0,
+            details: String::new(),
+            service: ServiceDetails::default(),
+            remark: String::new(),
+            config: OrderConfig::default(),
+            dldata: DlData {
+                gold_records: vec![],
+                exp_records: records
+                    .iter()
+                    .map(|&(time, exp_change, level, current_level_exp)| ExpRecord {
+                        time: parse_datetime(time),
+                        exp_change,
+                        level,
+                        total_exp: 0,
+                        current_level_exp,
+                    })
+                    .collect(),
+                battle_records: vec![],
+                ..Default::default()
+            },
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_progress_estimate() {
+        // 记录按时间倒序，与接口一致；两天内获得 3000 经验
+        let order = order(
+            "2025-11-20 12:00:00",
+            &[
+                ("2025-11-12 10:00:00", 1000, 40, 1200),
+                ("2025-11-11 10:00:00", 2000, 39, 1700),
+                ("2025-11-10 12:00:00", 500, 38, 0),
+            ],
+        );
+        let now = parse_datetime("2025-11-12 12:00:00").unwrap();
+        let progress = Progress::estimate(&order, &now).unwrap();
+
+        assert_eq!(progress.level, 40);
+        assert_eq!(progress.level_exp, 1200);
+        assert_eq!(progress.sample_days, 2.0);
+        assert_eq!(progress.daily_exp, 1500.0);
+        assert_eq!(progress.remaining_days, 8.0);
+        // 1200 + 1500 * 8 = 13200，即 8 级多
+        assert_eq!(progress.projected_level, 48);
+
+        assert!(progress.reaches(48));
+        assert!(!progress.reaches(49));
+        assert_eq!(progress.exp_needed(49), 9 * 1500 - 1200);
+        assert_eq!(progress.required_daily_exp(49), Some(1537.5));
+        assert_eq!(progress.exp_needed(30), 0);
+    }
+
+    #[test]
+    fn test_progress_estimate_short_sample() {
+        // 记录只覆盖 2 小时时按 1 天计算，截止时间已过时不再增长
+        let records = [
+            ("2025-11-12 11:00:00", 300, 40, 300),
+            ("2025-11-12 10:00:00", 100, 39, 1400),
+        ];
+        let now = parse_datetime("2025-11-12 12:00:00").unwrap();
+
+        let progress = Progress::estimate(&order("2025-11-13 12:00:00", &records), &now).unwrap();
+        assert_eq!(progress.daily_exp, 300.0);
+        assert_eq!(progress.projected_level, 40);
+
+        let expired = Progress::estimate(&order("2025-11-01 00:00:00", &records), &now).unwrap();
+        assert_eq!(expired.remaining_days, 0.0);
+        assert_eq!(expired.required_daily_exp(41), None);
+    }
+
+    #[test]
+    fn test_progress_estimate_none() {
+        let now = parse_datetime("2025-11-12 12:00:00").unwrap();
+        let records = [("2025-11-12 11:00:00", 300, 40, 300)];
+        assert_eq!(
+            Progress::estimate(&order("2025-11-20 12:00:00", &records), &now),
+            None
+        );
+
+        let mut no_deadline = order(
+            "",
+            &[
+                ("2025-11-12 11:00:00", 300, 40, 300),
+                ("2025-11-12 10:00:00", 100, 39, 1400),
+            ],
+        );
+        assert_eq!(Progress::estimate(&no_deadline, &now), None);
+
+        no_deadline.deadline = parse_datetime("2025-11-20 12:00:00");
+        assert!(Progress::estimate(&no_deadline, &now).is_some());
+    }
+
+    #[test]
+    fn test_current_level_exp() {
+        // 按时间取最新的一条记录，与记录的排列顺序无关
+        let mut order = order(
+            "2025-11-20 12:00:00",
+            &[
+                ("2025-11-12 10:00:00", 100, 39, 1400),
+                ("2025-11-12 11:00:00", 300, 40, 300),
+            ],
+        );
+        order.reward_level = 40;
+        assert_eq!(current_level_exp(&order), 300);
+
+        order.reward_level = 41;
+        assert_eq!(current_level_exp(&order), 0);
+
+        order.dldata.exp_records.clear();
+        assert_eq!(current_level_exp(&order), 0);
+    }
+}
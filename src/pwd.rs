@@ -0,0 +1,298 @@
+//! 战网密码前4位来源模块
+//!
+//! 依次从命令行选项、环境变量、配置文件、系统密钥环、本地密码库和交互式输入中获取，所有来源都会经过相同的格式校验。
+
+use crate::config::OrderTarget;
+use crate::parse_pwd4;
+use crate::vault::Vault;
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{self, IsTerminal};
+
+/// 战网密码前4位环境变量
+pub const PWD_ENV: &str = "HS_ORDER_PWD";
+/// 密码库密码环境变量
+pub const VAULT_PASSWORD_ENV: &str = "HS_ORDER_VAULT_PASSWORD";
+
+/// 校验战网密码前4位，错误信息中包含来源
+pub fn validate(pwd4: &str, source: &str) -> Result<String> {
+    parse_pwd4(pwd4).map_err(|e| anyhow!("{}: {}", source, e))
+}
+
+/// 是否可以进行交互式输入
+pub fn can_prompt() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// 交互式输入战网密码前4位（输入内容不回显）
+pub fn prompt_pwd4(label: &str) -> Result<String> {
+    let pwd4 = rpassword::prompt_password(format!("请输入{}的战网密码前4位: ", label))
+        .context("读取输入失败")?;
+    validate(&pwd4, "输入的密码")
+}
+
+/// 获取密码库密码：优先使用环境变量，否则交互式输入
+///
+/// `confirm` 为 `true` 时（创建密码库）需要输入两次。
+pub fn vault_password(confirm: bool) -> Result<String> {
+    if let Ok(password) = env::var(VAULT_PASSWORD_ENV) {
+        return Ok(password);
+    }
+
+    if !can_prompt() {
+        bail!("需要密码库密码：请设置环境变量 {}", VAULT_PASSWORD_ENV);
+    }
+
+    let password = rpassword::prompt_password("请输入密码库密码: ").context("读取输入失败")?;
+
+    if password.is_empty() {
+        bail!("密码库密码不能为空");
+    }
+
+    if confirm
+        && rpassword::prompt_password("请再次输入密码库密码: ").context("读取输入失败")? != password
+    {
+        bail!("两次输入的密码库密码不一致");
+    }
+
+    Ok(password)
+}
+
+/// 系统密钥环（Secret Service、macOS 钥匙串、Windows 凭据管理器）
+#[cfg(feature = "keyring")]
+pub mod keyring_store {
+    use anyhow::{Context, Result};
+    use colored::Colorize;
+    use std::sync::Once;
+
+    /// 密钥环中的服务名
+    const SERVICE: &str = "hs-order-cli";
+
+    /// 读取订单的战网密码前4位
+    ///
+    /// 密钥环不可用（例如没有 Secret Service 或 D-Bus 会话的远程服务器）时与没有保存密码一样返回 `None`，
+    /// 只输出一次警告，以便继续尝试密码库和终端输入。
+    pub fn get(order_id: &str) -> Result<Option<String>> {
+        let result = keyring::Entry::new(SERVICE, order_id).and_then(|entry| entry.get_password());
+        match result {
+            Ok(pwd4) => Ok(Some(pwd4)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) if is_unavailable(&e) => {
+                static WARNED: Once = Once::new();
+                WARNED.call_once(|| {
+                    eprintln!(
+                        "{} 无法访问系统密钥环，已跳过: {}",
+                        "警告".bright_yellow().bold(),
+                        e
+                    );
+                });
+                Ok(None)
+            }
+            Err(e) => Err(e).context("读取系统密钥环失败"),
+        }
+    }
+
+    /// 是否为密钥环本身不可用的错误
+    pub(super) fn is_unavailable(e: &keyring::Error) -> bool {
+        matches!(
+            e,
+            keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+        )
+    }
+
+    /// 保存订单的战网密码前4位
+    pub fn set(order_id: &str, pwd4: &str) -> Result<()> {
+        keyring::Entry::new(SERVICE, order_id)?
+            .set_password(pwd4)
+            .context("写入系统密钥环失败")
+    }
+
+    /// 删除订单的战网密码前4位，返回是否存在
+    pub fn remove(order_id: &str) -> Result<bool> {
+        match keyring::Entry::new(SERVICE, order_id)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(e).context("删除系统密钥环中的密码失败"),
+        }
+    }
+}
+
+/// 系统密钥环（未启用 keyring 功能）
+#[cfg(not(feature = "keyring"))]
+pub mod keyring_store {
+    use anyhow::{Result, bail};
+
+    /// 未启用时总是没有保存的密码
+    pub fn get(_order_id: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// 未启用时无法保存
+    pub fn set(_order_id: &str, _pwd4: &str) -> Result<()> {
+        bail!("未启用系统密钥环功能，请使用 --features keyring 重新编译")
+    }
+
+    /// 未启用时无法删除
+    pub fn remove(_order_id: &str) -> Result<bool> {
+        bail!("未启用系统密钥环功能，请使用 --features keyring 重新编译")
+    }
+}
+
+/// 为各订单获取战网密码前4位
+pub struct PwdResolver {
+    /// 命令行中传入的密码
+    cli_pwd: Option<String>,
+    /// 环境变量中的密码
+    env_pwd: Option<String>,
+    /// 已解锁的密码库（`None` 表示尚未打开，`Some(None)` 表示密码库不存在）
+    vault: Option<Option<Vault>>,
+    /// 已获取的密码（订单号到密码的映射），同一订单只获取一次
+    resolved: BTreeMap<String, String>,
+}
+
+impl PwdResolver {
+    /// 创建获取器，`cli_pwd` 为命令行中传入的密码
+    pub fn new(cli_pwd: Option<String>) -> Self {
+        Self {
+            cli_pwd,
+            env_pwd: env::var(PWD_ENV).ok(),
+            vault: None,
+            resolved: BTreeMap::new(),
+        }
+    }
+
+    /// 获取订单的战网密码前4位
+    pub fn resolve(&mut self, target: &OrderTarget) -> Result<String> {
+        if let Some(pwd4) = self.resolved.get(&target.order_id) {
+            return Ok(pwd4.clone());
+        }
+
+        let pwd4 = self.lookup(target)?;
+        self.resolved.insert(target.order_id.clone(), pwd4.clone());
+        Ok(pwd4)
+    }
+
+    /// 依次从各个来源获取订单的战网密码前4位
+    fn lookup(&mut self, target: &OrderTarget) -> Result<String> {
+        if let Some(pwd4) = &self.cli_pwd {
+            return validate(pwd4, "--pwd");
+        }
+
+        if let Some(pwd4) = &self.env_pwd {
+            return validate(pwd4, &format!("环境变量 {}", PWD_ENV));
+        }
+
+        if let Some(pwd4) = &target.pwd {
+            return Ok(pwd4.clone());
+        }
+
+        if let Some(pwd4) = keyring_store::get(&target.order_id)? {
+            return validate(&pwd4, "系统密钥环");
+        }
+
+        if let Some(vault) = self.vault()?
+            && let Some(pwd4) = vault.get(&target.order_id)
+        {
+            return validate(pwd4, "密码库");
+        }
+
+        if can_prompt() {
+            return prompt_pwd4(&format!("订单 {} ", target.input));
+        }
+
+        bail!(
+            "订单 {} 必须提供战网密码前4位：可以通过环境变量 {}、配置文件、系统密钥环、密码库（pwd 子命令）或 --pwd 提供",
+            target.input,
+            PWD_ENV
+        )
+    }
+
+    /// 打开密码库（只打开一次）
+    fn vault(&mut self) -> Result<Option<&Vault>> {
+        if self.vault.is_none() {
+            let path = Vault::default_path()?;
+            self.vault = Some(if path.exists() {
+                Some(Vault::open(path, &vault_password(false)?)?)
+            } else {
+                None
+            });
+        }
+
+        Ok(self.vault.as_ref().and_then(Option::as_ref))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "keyring")]
+    #[test]
+    fn test_keyring_unavailable() {
+        let failure = || Box::<dyn std::error::Error + Send + Sync>::from("no dbus session");
+        assert!(keyring_store::is_unavailable(
+            &keyring::Error::PlatformFailure(failure())
+        ));
+        assert!(keyring_store::is_unavailable(
+            &keyring::Error::NoStorageAccess(failure())
+        ));
+        assert!(!keyring_store::is_unavailable(&keyring::Error::NoEntry));
+    }
+
+    fn target(pwd: Option<&str>) -> OrderTarget {
+        OrderTarget {
+            input: "@main".into(),
+            order_id: "1234567890123456789".into(),
+            oid: None,
+            pwd: pwd.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_pwd_resolver_priority() {
+        let mut resolver = PwdResolver {
+            cli_pwd: None,
+            env_pwd: Some("envp".into()),
+            vault: Some(None),
+            resolved: BTreeMap::new(),
+        };
+        assert_eq!(resolver.lookup(&target(Some("conf"))).unwrap(), "envp");
+
+        resolver.cli_pwd = Some("clip".into());
+        assert_eq!(resolver.lookup(&target(Some("conf"))).unwrap(), "clip");
+
+        resolver.cli_pwd = None;
+        resolver.env_pwd = None;
+        assert_eq!(resolver.lookup(&target(Some("conf"))).unwrap(), "conf");
+    }
+
+    #[test]
+    fn test_pwd_resolver_caches() {
+        let mut resolver = PwdResolver {
+            cli_pwd: None,
+            env_pwd: Some("envp".into()),
+            vault: Some(None),
+            resolved: BTreeMap::new(),
+        };
+        assert_eq!(resolver.resolve(&target(None)).unwrap(), "envp");
+
+        resolver.env_pwd = Some("zw".into());
+        assert_eq!(resolver.resolve(&target(None)).unwrap(), "envp");
+    }
+
+    #[test]
+    fn test_pwd_resolver_validates_env() {
+        let mut resolver = PwdResolver {
+            cli_pwd: None,
+            env_pwd: Some("zw".into()),
+            vault: Some(None),
+            resolved: BTreeMap::new(),
+        };
+        let err = resolver.resolve(&target(None)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "环境变量 HS_ORDER_PWD: 战网密码前4位必须为4个字符"
+        );
+    }
+}
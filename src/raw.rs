@@ -0,0 +1,282 @@
+//! 原始数据注释模块
+//!
+//! 将接口返回的未经解析的订单数据逐项展开（`config` 按字段、`dldata` 按下标），附上已知的含义，
+//! 并与上一次保存的原始数据比较，便于在接口变化时分析新字段的含义。
+
+use crate::constants::DLDATA_LEN;
+use crate::models::{Timestamp, deserialize_time};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// data 中各字段的含义
+pub const DATA_FIELDS: &[(&str, &str)] = &[
+    ("am", "含义未知，目前固定为空字符串"),
+    ("oid", "订单编号"),
+    ("edate", "到期时间"),
+    ("details", "每日上号时间段和最近一次上号时间"),
+    ("finish", "订单完成状态，\"1\" 为已完成"),
+    ("banned", "订单封禁状态，\"1\" 为已终止"),
+    ("dltype", "含义未知，目前固定为 \"5\""),
+    ("num1", "金币数量"),
+    ("num2", "卡包数量"),
+    ("num3", "奖励等级，显示时需要 +1"),
+    ("num7", "含义未知，目前固定为 \"0\""),
+    ("num8", "含义未知，随着时间推移会变大"),
+    ("remark", "备注"),
+    ("config", "配置信息（JSON 字符串）"),
+    ("dldata", "游戏统计数据（JSON 字符串）"),
+];
+
+/// config 中各字段的含义
+pub const CONFIG_FIELDS: &[(&str, &str)] = &[
+    (
+        "battlemode",
+        "对战模式，1~5 依次为休闲、标准、狂野、幻变、酒馆战棋",
+    ),
+    ("battleheroes", "对战英雄掩码"),
+    ("auto", "是否自动领取奖励"),
+    ("pause", "是否暂停上号"),
+    ("region", "服务器区域"),
+];
+
+/// dldata 各下标的含义
+pub const DLDATA_FIELDS: [&str; DLDATA_LEN] = [
+    "含义未知，目前值总是 8560",
+    "含义未知，目前值总是 9",
+    "含义未知，目前值总是 50",
+    "最近一次的上号日期（YYYYMMDD）",
+    "最近一次上号当天获胜的次数",
+    "最近一次上号当天失败的次数",
+    "含义未知，目前值总是 0",
+    "服务器区域",
+    "最近一次上号时间（秒级时间戳）",
+    "含义未知，目前值总是 0",
+    "金币记录 [时间, 金币变化, 卡包变化]",
+    "经验记录 [时间, 经验变化, 等级, 总经验, 当前等级经验]",
+    "对战记录 [结束时间, 结果, 经验变化, 开始时间]",
+    "含义未知的字符串 ID 列表",
+    "含义未知，目前值总是 0",
+    "含义未知，目前值总是 0",
+];
+
+/// 没有记录含义的字段
+const UNKNOWN_FIELD: &str = "未知字段";
+
+/// 保存的原始订单数据
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RawSnapshot {
+    /// 保存时间
+    #[serde(deserialize_with = "deserialize_time")]
+    pub recorded_at: Timestamp,
+    /// 接口返回的订单数据
+    pub data: Map<String, Value>,
+}
+
+/// 原始数据中的一项
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RawField {
+    /// 字段路径，如 `num8`、`config.battlemode`、`dldata[8]`
+    pub path: String,
+    /// 已知的含义
+    pub meaning: &'static str,
+    /// 当前的值，字段已被移除时为 null
+    pub value: Value,
+    /// 与上一次保存的原始数据相比的变化
+    pub change: Change,
+}
+
+/// 字段与上一次保存的原始数据相比的变化
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "previous", rename_all = "snake_case")]
+pub enum Change {
+    /// 没有保存过原始数据，无法比较
+    NoBaseline,
+    /// 未变化
+    Unchanged,
+    /// 值有变化（附上一次的值）
+    Changed(Value),
+    /// 新出现的字段
+    Added,
+    /// 已被移除的字段（附上一次的值）
+    Removed(Value),
+}
+
+impl RawField {
+    /// 与上一次保存的原始数据相比是否有变化
+    pub fn is_changed(&self) -> bool {
+        matches!(
+            self.change,
+            Change::Changed(_) | Change::Added | Change::Removed(_)
+        )
+    }
+}
+
+/// 逐项展开原始订单数据并与上一次保存的原始数据比较
+///
+/// 按已知字段的顺序排列，未知字段排在已知字段之后，已被移除的字段排在最后。
+pub fn annotate(data: &Map<String, Value>, previous: Option<&Map<String, Value>>) -> Vec<RawField> {
+    let previous = previous.map(flatten);
+    let find = |path: &str| {
+        previous
+            .as_ref()
+            .map(|previous| previous.iter().find(|(p, _, _)| p == path))
+    };
+
+    let mut fields: Vec<RawField> = flatten(data)
+        .into_iter()
+        .map(|(path, meaning, value)| {
+            let change = match find(&path) {
+                None => Change::NoBaseline,
+                Some(None) => Change::Added,
+                Some(Some((_, _, old))) if *old == value => Change::Unchanged,
+                Some(Some((_, _, old))) => Change::Changed(old.clone()),
+            };
+            RawField {
+                path,
+                meaning,
+                value,
+                change,
+            }
+        })
+        .collect();
+
+    for (path, meaning, old) in previous.into_iter().flatten() {
+        if !fields.iter().any(|field| field.path == path) {
+            fields.push(RawField {
+                path,
+                meaning,
+                value: Value::Null,
+                change: Change::Removed(old),
+            });
+        }
+    }
+
+    fields
+}
+
+/// 字段路径对应的含义
+fn meaning_of(path: &str) -> &'static str {
+    if let Some(key) = path.strip_prefix("config.") {
+        return lookup(CONFIG_FIELDS, key);
+    }
+    if let Some(index) = path
+        .strip_prefix("dldata[")
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return index
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| DLDATA_FIELDS.get(i).copied())
+            .unwrap_or(UNKNOWN_FIELD);
+    }
+    lookup(DATA_FIELDS, path)
+}
+
+/// 在字段含义表中查找
+fn lookup(table: &[(&str, &'static str)], key: &str) -> &'static str {
+    table
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map_or(UNKNOWN_FIELD, |(_, meaning)| meaning)
+}
+
+/// 按已知字段的顺序排列对象中的键
+fn ordered_keys<'a>(
+    object: &'a Map<String, Value>,
+    table: &'a [(&str, &str)],
+) -> impl Iterator<Item = &'a String> {
+    let known = table
+        .iter()
+        .filter_map(move |(name, _)| object.get_key_value(*name).map(|(key, _)| key));
+    let unknown = object
+        .keys()
+        .filter(move |key| !table.iter().any(|(name, _)| name == key));
+    known.chain(unknown)
+}
+
+/// 逐项展开订单数据，`config` 和 `dldata` 能解析为 JSON 时按字段或下标展开
+fn flatten(data: &Map<String, Value>) -> Vec<(String, &'static str, Value)> {
+    let mut fields = Vec::new();
+
+    for key in ordered_keys(data, DATA_FIELDS) {
+        let value = &data[key];
+        let nested = value
+            .as_str()
+            .and_then(|s| serde_json::from_str::<Value>(s).ok());
+
+        match (key.as_str(), nested) {
+            ("config", Some(Value::Object(config))) => {
+                for name in ordered_keys(&config, CONFIG_FIELDS) {
+                    let path = format!("config.{}", name);
+                    fields.push((path.clone(), meaning_of(&path), config[name].clone()));
+                }
+            }
+            ("dldata", Some(Value::Array(dldata))) => {
+                for (i, item) in dldata.into_iter().enumerate() {
+                    let path = format!("dldata[{}]", i);
+                    fields.push((path.clone(), meaning_of(&path), item));
+                }
+            }
+            _ => fields.push((key.clone(), meaning_of(key), value.clone())),
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data(num8: &str, dldata: Value) -> Map<String, Value> {
+        json!({
+            "oid": "2025010100013333123456",
+            "num8": num8,
+            "config": r#"{"battlemode":"5","auto":"0"}"#,
+            "dldata": dldata.to_string(),
+        })
+        .as_object()
+        .unwrap()
+        .clone()
+    }
+
+    #[test]
+    fn test_annotate_without_baseline() {
+        let fields = annotate(&data("428301", json!([8560, 9])), None);
+        let paths: Vec<&str> = fields.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "oid",
+                "num8",
+                "config.battlemode",
+                "config.auto",
+                "dldata[0]",
+                "dldata[1]"
+            ]
+        );
+        assert_eq!(fields[2].meaning, CONFIG_FIELDS[0].1);
+        assert_eq!(fields[5].value, json!(9));
+        assert!(fields.iter().all(|f| f.change == Change::NoBaseline));
+    }
+
+    #[test]
+    fn test_annotate_changes() {
+        let previous = data("428301", json!([8560, 9, 50]));
+        let mut current = data("428399", json!([8560, 10]));
+        current.insert("num9".into(), json!("1"));
+
+        let fields = annotate(&current, Some(&previous));
+        let field = |path: &str| fields.iter().find(|f| f.path == path).unwrap();
+
+        assert_eq!(field("oid").change, Change::Unchanged);
+        assert_eq!(field("num8").change, Change::Changed(json!("428301")));
+        assert_eq!(field("dldata[1]").change, Change::Changed(json!(9)));
+        assert_eq!(field("num9").change, Change::Added);
+        assert_eq!(field("num9").meaning, UNKNOWN_FIELD);
+        assert_eq!(fields.last().unwrap().path, "dldata[2]");
+        assert_eq!(fields.last().unwrap().change, Change::Removed(json!(50)));
+        assert_eq!(fields.iter().filter(|f| f.is_changed()).count(), 4);
+    }
+}
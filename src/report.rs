@@ -0,0 +1,496 @@
+//! 报表模块
+//!
+//! 将金币、经验和对战记录按北京时间的自然日汇总，把连续的对局合并为游戏时段，并按约定的每日上号时间段核对对战记录，
+//! 用于核对代练每天的上号情况。
+
+use crate::models::{BattleRecord, BattleResult, ExpRecord, GoldRecord, ServiceWindow, Timestamp};
+use crate::stats::calculate_win_rate;
+use chrono::{Days, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 一天的汇总统计
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyStats {
+    /// 日期（北京时间）
+    pub date: NaiveDate,
+    /// 金币变化
+    pub gold: i64,
+    /// 卡包变化
+    pub packs: i64,
+    /// 经验变化
+    pub exp: i64,
+    /// 对战场次
+    pub battles: usize,
+    /// 胜利场次
+    pub wins: usize,
+    /// 失败场次
+    pub losses: usize,
+    /// 结果未知的场次
+    pub unknowns: usize,
+    /// 胜率（百分比，未知结果的对战不计入）
+    pub win_rate: u8,
+    /// 游戏时长（秒），由对局开始和结束时间计算，没有结束时间的对局不计入
+    pub play_secs: i64,
+    /// 每小时经验，没有游戏时长时为空
+    pub exp_per_hour: Option<f64>,
+    /// 当天第一局的开始时间
+    pub first_start: Option<Timestamp>,
+    /// 当天最后一局的结束时间
+    pub last_end: Option<Timestamp>,
+}
+
+impl DailyStats {
+    /// 创建某一天的空统计
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            gold: 0,
+            packs: 0,
+            exp: 0,
+            battles: 0,
+            wins: 0,
+            losses: 0,
+            unknowns: 0,
+            win_rate: 0,
+            play_secs: 0,
+            exp_per_hour: None,
+            first_start: None,
+            last_end: None,
+        }
+    }
+
+    /// 计入一局对战
+    fn add_battle(&mut self, record: &BattleRecord) {
+        self.battles += 1;
+
+        match record.result {
+            BattleResult::Win => self.wins += 1,
+            BattleResult::Loss => self.losses += 1,
+            BattleResult::Unknown => self.unknowns += 1,
+        }
+
+        if let Some(duration) = record.duration() {
+            self.play_secs += duration.num_seconds();
+        }
+
+        self.first_start = self.first_start.into_iter().chain(record.time).min();
+        self.last_end = self.last_end.max(record.end_time);
+    }
+}
+
+/// 按日期（北京时间）汇总记录，按日期倒序返回
+///
+/// 对战按对局开始时间计入当天，跨越零点的对局不拆分；没有时间的记录不计入。
+pub fn daily_stats(
+    gold_records: &[GoldRecord],
+    exp_records: &[ExpRecord],
+    battle_records: &[BattleRecord],
+) -> Vec<DailyStats> {
+    let mut days = BTreeMap::new();
+
+    for record in gold_records {
+        if let Some(stats) = day_entry(&mut days, record.time) {
+            stats.gold += record.gold_change;
+            stats.packs += record.pack_change;
+        }
+    }
+
+    for record in exp_records {
+        if let Some(stats) = day_entry(&mut days, record.time) {
+            stats.exp += record.exp_change;
+        }
+    }
+
+    for record in battle_records {
+        if let Some(stats) = day_entry(&mut days, record.time) {
+            stats.add_battle(record);
+        }
+    }
+
+    days.into_values()
+        .rev()
+        .map(|mut stats| {
+            stats.win_rate = calculate_win_rate(stats.wins, stats.losses);
+            stats.exp_per_hour =
+                (stats.play_secs > 0).then(|| stats.exp as f64 * 3600.0 / stats.play_secs as f64);
+            stats
+        })
+        .collect()
+}
+
+/// 游戏时段：间隔不超过阈值的连续对局
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Session {
+    /// 第一局的开始时间
+    pub start: Timestamp,
+    /// 最后一局的结束时间（尚未结束时为开始时间）
+    pub end: Timestamp,
+    /// 对局数
+    pub battles: usize,
+    /// 胜利场次
+    pub wins: usize,
+    /// 失败场次
+    pub losses: usize,
+    /// 对局时长合计（秒），不包括对局之间的间隔
+    pub play_secs: i64,
+    /// 对战经验
+    pub exp: i64,
+}
+
+impl Session {
+    /// 从一局对战开始新的游戏时段
+    fn new(start: Timestamp, record: &BattleRecord) -> Self {
+        let mut session = Self {
+            start,
+            end: start,
+            battles: 0,
+            wins: 0,
+            losses: 0,
+            play_secs: 0,
+            exp: 0,
+        };
+        session.add_battle(record);
+        session
+    }
+
+    /// 计入一局对战
+    fn add_battle(&mut self, record: &BattleRecord) {
+        self.battles += 1;
+
+        match record.result {
+            BattleResult::Win => self.wins += 1,
+            BattleResult::Loss => self.losses += 1,
+            BattleResult::Unknown => {}
+        }
+
+        self.play_secs += record.duration().map_or(0, |d| d.num_seconds());
+        self.exp += record.exp;
+        self.end = self
+            .end
+            .max(record.end_time.or(record.time).unwrap_or(self.end));
+    }
+
+    /// 从第一局开始到最后一局结束的时长（秒）
+    pub fn span_secs(&self) -> i64 {
+        (self.end - self.start).num_seconds()
+    }
+}
+
+/// 将对战记录合并为游戏时段，按时间倒序返回
+///
+/// 一局的开始时间与上一局的结束时间间隔不超过 `gap` 时属于同一个时段；没有开始时间的记录不计入。
+pub fn sessions(battle_records: &[BattleRecord], gap: Duration) -> Vec<Session> {
+    let mut records: Vec<_> = battle_records
+        .iter()
+        .filter_map(|record| record.time.map(|time| (time, record)))
+        .collect();
+    records.sort_by_key(|(time, _)| *time);
+
+    let mut sessions: Vec<Session> = Vec::new();
+
+    for (start, record) in records {
+        match sessions.last_mut() {
+            Some(session) if start - session.end <= gap => session.add_battle(record),
+            _ => sessions.push(Session::new(start, record)),
+        }
+    }
+
+    sessions.reverse();
+    sessions
+}
+
+/// 按上号时间段核对对战记录的结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServiceCheck {
+    /// 约定的每日上号时间段
+    pub window: ServiceWindow,
+    /// 在时间段之外开始的对战
+    pub outside: Vec<BattleRecord>,
+    /// 没有任何对战的上号日
+    pub idle_days: Vec<NaiveDate>,
+}
+
+impl ServiceCheck {
+    /// 是否没有发现异常
+    pub fn is_ok(&self) -> bool {
+        self.outside.is_empty() && self.idle_days.is_empty()
+    }
+}
+
+/// 按约定的每日上号时间段核对对战记录
+///
+/// 没有任何对战的上号日从最早的对战记录所在的上号日开始计算，到 `now` 的前一个上号日为止（当天尚未结束，不计入），
+/// 且不晚于截止时间。跨越零点的时间段中，零点之后的对战属于前一个上号日。
+pub fn check_service(
+    window: ServiceWindow,
+    battle_records: &[BattleRecord],
+    now: &Timestamp,
+    deadline: Option<&Timestamp>,
+) -> ServiceCheck {
+    let outside = battle_records
+        .iter()
+        .filter(|record| {
+            record
+                .time
+                .is_some_and(|time| !window.contains(time.time()))
+        })
+        .cloned()
+        .collect();
+
+    let played: BTreeSet<NaiveDate> = battle_records
+        .iter()
+        .filter_map(|record| record.time.as_ref())
+        .map(|time| window.service_date(time))
+        .collect();
+
+    let mut idle_days = Vec::new();
+    let last = window
+        .service_date(now)
+        .checked_sub_days(Days::new(1))
+        .into_iter()
+        .chain(deadline.map(|deadline| window.service_date(deadline)))
+        .min();
+
+    if let (Some(&first), Some(last)) = (played.first(), last) {
+        idle_days.extend(
+            first
+                .iter_days()
+                .take_while(|date| *date <= last)
+                .filter(|date| !played.contains(date)),
+        );
+    }
+
+    ServiceCheck {
+        window,
+        outside,
+        idle_days,
+    }
+}
+
+/// 取出记录所在日期的统计，记录没有时间时返回 `None`
+fn day_entry(
+    days: &mut BTreeMap<NaiveDate, DailyStats>,
+    time: Option<Timestamp>,
+) -> Option<&mut DailyStats> {
+    let date = time?.date_naive();
+    Some(days.entry(date).or_insert_with(|| DailyStats::new(date)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_datetime;
+
+    fn battle(start: &str, end: &str, result: BattleResult, exp: i64) -> BattleRecord {
+        BattleRecord {
+            time: parse_datetime(start),
+            end_time: parse_datetime(end),
+            result,
+            exp,
+        }
+    }
+
+    fn exp(time: &str, exp_change: i64) -> ExpRecord {
+        ExpRecord {
+            time: parse_datetime(time),
+            exp_change,
+            level: 43,
+            total_exp: 0,
+            current_level_exp: 0,
+        }
+    }
+
+    #[test]
+    fn test_daily_stats() {
+        let gold = [
+            GoldRecord {
+                time: parse_datetime("2025-11-12 09:00:00"),
+                gold_change: 50,
+                pack_change: 0,
+            },
+            GoldRecord {
+                time: parse_datetime("2025-11-11 23:59:59"),
+                gold_change: 100,
+                pack_change: 1,
+            },
+        ];
+        let exps = [
+            exp("2025-11-12 08:30:00", 120),
+            exp("2025-11-12 08:10:00", 60),
+            exp("2025-11-11 23:50:00", 30),
+        ];
+        let battles = [
+            battle(
+                "2025-11-12 08:20:00",
+                "2025-11-12 08:30:00",
+                BattleResult::Loss,
+                120,
+            ),
+            // 没有结束时间的对局只计入场次
+            battle("2025-11-12 08:40:00", "", BattleResult::Unknown, 0),
+            battle(
+                "2025-11-12 08:00:00",
+                "2025-11-12 08:10:00",
+                BattleResult::Win,
+                60,
+            ),
+            // 跨越零点的对局计入开始的那一天
+            battle(
+                "2025-11-11 23:40:00",
+                "2025-11-12 00:10:00",
+                BattleResult::Win,
+                30,
+            ),
+        ];
+
+        let days = daily_stats(&gold, &exps, &battles);
+        assert_eq!(days.len(), 2);
+
+        let today = &days[0];
+        assert_eq!(today.date.to_string(), "2025-11-12");
+        assert_eq!((today.gold, today.packs, today.exp), (50, 0, 180));
+        assert_eq!(
+            (today.battles, today.wins, today.losses, today.unknowns),
+            (3, 1, 1, 1)
+        );
+        assert_eq!(today.win_rate, 50);
+        assert_eq!(today.play_secs, 1200);
+        assert_eq!(today.exp_per_hour, Some(540.0));
+        assert_eq!(today.first_start, parse_datetime("2025-11-12 08:00:00"));
+        assert_eq!(today.last_end, parse_datetime("2025-11-12 08:30:00"));
+
+        let yesterday = &days[1];
+        assert_eq!(yesterday.date.to_string(), "2025-11-11");
+        assert_eq!((yesterday.gold, yesterday.packs), (100, 1));
+        assert_eq!(yesterday.play_secs, 1800);
+        assert_eq!(yesterday.last_end, parse_datetime("2025-11-12 00:10:00"));
+    }
+
+    #[test]
+    fn test_sessions() {
+        let battles = [
+            battle(
+                "2025-11-12 14:00:00",
+                "2025-11-12 14:12:00",
+                BattleResult::Win,
+                150,
+            ),
+            // 对局尚未结束
+            battle("2025-11-12 14:20:00", "", BattleResult::Unknown, 0),
+            battle(
+                "2025-11-12 09:12:00",
+                "2025-11-12 09:30:00",
+                BattleResult::Loss,
+                40,
+            ),
+            battle(
+                "2025-11-12 09:00:00",
+                "2025-11-12 09:10:00",
+                BattleResult::Win,
+                120,
+            ),
+        ];
+
+        let result = sessions(&battles, Duration::minutes(15));
+        assert_eq!(result.len(), 2);
+
+        let afternoon = &result[0];
+        assert_eq!(
+            afternoon.start,
+            parse_datetime("2025-11-12 14:00:00").unwrap()
+        );
+        assert_eq!(
+            afternoon.end,
+            parse_datetime("2025-11-12 14:20:00").unwrap()
+        );
+        assert_eq!((afternoon.battles, afternoon.wins), (2, 1));
+        assert_eq!(afternoon.play_secs, 720);
+
+        let morning = &result[1];
+        assert_eq!(morning.battles, 2);
+        assert_eq!((morning.wins, morning.losses), (1, 1));
+        assert_eq!(morning.exp, 160);
+        assert_eq!(morning.play_secs, 1680);
+        assert_eq!(morning.span_secs(), 1800);
+
+        // 间隔阈值小于 2 分钟时拆分为 4 个时段
+        assert_eq!(sessions(&battles, Duration::minutes(1)).len(), 4);
+        assert!(sessions(&[], Duration::minutes(15)).is_empty());
+    }
+
+    #[test]
+    fn test_check_service() {
+        let window = ServiceWindow {
+            start: chrono::NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            end: chrono::NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+        };
+        let battles = [
+            battle(
+                "2025-11-14 16:30:00",
+                "2025-11-14 16:40:00",
+                BattleResult::Win,
+                60,
+            ),
+            battle(
+                "2025-11-14 09:00:00",
+                "2025-11-14 09:10:00",
+                BattleResult::Win,
+                60,
+            ),
+            battle(
+                "2025-11-11 07:50:00",
+                "2025-11-11 08:05:00",
+                BattleResult::Loss,
+                30,
+            ),
+        ];
+        let now = parse_datetime("2025-11-16 10:00:00").unwrap();
+
+        let check = check_service(window, &battles, &now, None);
+        assert!(!check.is_ok());
+        assert_eq!(
+            check.outside.iter().map(|r| r.time).collect::<Vec<_>>(),
+            vec![
+                parse_datetime("2025-11-14 16:30:00"),
+                parse_datetime("2025-11-11 07:50:00")
+            ]
+        );
+        // 11-16 当天尚未结束，不计入
+        let idle: Vec<_> = check.idle_days.iter().map(|d| d.to_string()).collect();
+        assert_eq!(idle, ["2025-11-12", "2025-11-13", "2025-11-15"]);
+
+        // 不晚于截止时间
+        let deadline = parse_datetime("2025-11-13 20:00:00").unwrap();
+        let check = check_service(window, &battles[..2], &now, Some(&deadline));
+        assert!(check.idle_days.is_empty());
+    }
+
+    #[test]
+    fn test_check_service_overnight() {
+        // 22:00-06:00，零点之后的对战属于前一个上号日
+        let window = ServiceWindow {
+            start: chrono::NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            end: chrono::NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+        };
+        let battles = [
+            battle("2025-11-13 02:00:00", "", BattleResult::Unknown, 0),
+            battle("2025-11-11 23:00:00", "", BattleResult::Unknown, 0),
+        ];
+        let now = parse_datetime("2025-11-14 03:00:00").unwrap();
+
+        let check = check_service(window, &battles, &now, None);
+        assert!(check.outside.is_empty());
+        assert!(check.idle_days.is_empty());
+    }
+
+    #[test]
+    fn test_daily_stats_empty() {
+        assert!(daily_stats(&[], &[], &[]).is_empty());
+
+        // 没有游戏时长时不计算每小时经验
+        let days = daily_stats(&[], &[exp("2025-11-12 08:30:00", 120)], &[]);
+        assert_eq!(days[0].exp_per_hour, None);
+        assert_eq!(days[0].battles, 0);
+    }
+}
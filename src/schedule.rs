@@ -0,0 +1,418 @@
+//! 定时规则模块
+//!
+//! 解析 cron 表达式（`分 时 日 月 周`，按北京时间计算），并计算各规则的执行时间和错过的执行。
+
+use chrono::{Datelike, Days, Duration, NaiveDate, TimeZone, Timelike};
+use chrono_tz::Asia::Shanghai;
+use hs_order::models::Timestamp;
+
+/// 最短的补执行时限（分钟），避免到点唤醒时的少量延迟被当作错过执行
+const MIN_GRACE_MINUTES: i64 = 1;
+/// 查找下一次执行时间时最多向后查找的天数（覆盖 2 月 29 日这类最长 8 年一次的规则）
+const MAX_SEARCH_DAYS: u64 = 366 * 8;
+
+/// cron 表达式中的一个字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    /// 允许的取值（第 n 位表示取值 n）
+    mask: u64,
+    /// 是否以 `*` 开头（用于日和周同时指定时的判断）
+    any: bool,
+}
+
+impl Field {
+    /// 解析字段，取值范围为 `min..=max`
+    fn parse(s: &str, min: u32, max: u32, name: &str) -> Result<Self, String> {
+        let invalid = || format!("cron 表达式的{}字段不正确: {}", name, s);
+        let mut mask = 0;
+
+        for part in s.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+                None => (part, 1),
+            };
+
+            let (start, end) = if range == "*" {
+                (min, max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (
+                    start.parse().map_err(|_| invalid())?,
+                    end.parse().map_err(|_| invalid())?,
+                )
+            } else {
+                let value = range.parse().map_err(|_| invalid())?;
+                // 与常见的 cron 实现一致，`n/step` 表示从 n 到最大值
+                (value, if part.contains('/') { max } else { value })
+            };
+
+            if step == 0 || start < min || end > max || start > end {
+                return Err(invalid());
+            }
+
+            for value in (start..=end).step_by(step as usize) {
+                mask |= 1 << value;
+            }
+        }
+
+        Ok(Self {
+            mask,
+            any: s.starts_with('*'),
+        })
+    }
+
+    /// 是否允许取值 `value`
+    fn contains(&self, value: u32) -> bool {
+        self.mask & (1 << value) != 0
+    }
+}
+
+/// cron 表达式（`分 时 日 月 周`）
+///
+/// 每个字段支持 `*`、数值、范围（`1-5`）、列表（`1,3,5`）和步长（`*/15`、`8-20/2`）；
+/// 周的取值为 0-7，0 和 7 都表示周日。日和周都不以 `*` 开头时，满足其中之一即可。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    source: String,
+    minutes: Field,
+    hours: Field,
+    days: Field,
+    months: Field,
+    weekdays: Field,
+}
+
+impl CronExpr {
+    /// 解析 cron 表达式
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let fields: Vec<_> = s.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(format!(
+                "cron 表达式必须包含 5 个字段（分 时 日 月 周）: {}",
+                s
+            ));
+        };
+
+        let mut weekdays = Field::parse(weekdays, 0, 7, "周")?;
+        if weekdays.contains(7) {
+            weekdays.mask = (weekdays.mask & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            source: fields.join(" "),
+            minutes: Field::parse(minutes, 0, 59, "分")?,
+            hours: Field::parse(hours, 0, 23, "时")?,
+            days: Field::parse(days, 1, 31, "日")?,
+            months: Field::parse(months, 1, 12, "月")?,
+            weekdays,
+        })
+    }
+
+    /// 表达式文本
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// 日期是否满足日、月和周字段
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !self.months.contains(date.month()) {
+            return false;
+        }
+
+        let day = self.days.contains(date.day());
+        let weekday = self
+            .weekdays
+            .contains(date.weekday().num_days_from_sunday());
+
+        if self.days.any || self.weekdays.any {
+            day && weekday
+        } else {
+            day || weekday
+        }
+    }
+
+    /// 计算晚于 `after` 的下一次执行时间，找不到时返回 `None`
+    pub fn next_after(&self, after: &Timestamp) -> Option<Timestamp> {
+        let start = after
+            .naive_local()
+            .with_second(0)?
+            .with_nanosecond(0)?
+            .checked_add_signed(Duration::minutes(1))?;
+        let mut date = start.date();
+
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                for hour in (0..24).filter(|h| self.hours.contains(*h)) {
+                    for minute in (0..60).filter(|m| self.minutes.contains(*m)) {
+                        let time = date.and_hms_opt(hour, minute, 0)?;
+                        if time >= start {
+                            return Shanghai.from_local_datetime(&time).single();
+                        }
+                    }
+                }
+            }
+
+            date = date.checked_add_days(Days::new(1))?;
+        }
+
+        None
+    }
+}
+
+/// 到期的规则
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Due {
+    /// 应当执行（包括在补执行时限内错过的执行）
+    Run {
+        /// 规则序号
+        rule: usize,
+        /// 计划执行时间
+        at: Timestamp,
+    },
+    /// 错过且不再执行（超过补执行时限，或之后还有更近的一次执行）
+    Missed {
+        /// 规则序号
+        rule: usize,
+        /// 最早错过的计划执行时间
+        at: Timestamp,
+        /// 错过的次数
+        count: usize,
+    },
+}
+
+/// 规则调度器
+///
+/// 错过计划执行时间（例如程序启动前、电脑休眠期间）不超过补执行时限的执行会立即补上，超过时限的跳过。
+#[derive(Debug)]
+pub struct Scheduler {
+    crons: Vec<CronExpr>,
+    next: Vec<Option<Timestamp>>,
+    grace: Duration,
+}
+
+impl Scheduler {
+    /// 创建调度器，`now` 之前不超过 `grace` 的计划执行时间也会被补执行
+    ///
+    /// 补执行时限至少为 1 分钟。
+    pub fn new(crons: Vec<CronExpr>, grace: Duration, now: &Timestamp) -> Self {
+        let grace = grace.max(Duration::minutes(MIN_GRACE_MINUTES));
+        let since = *now - grace - Duration::seconds(1);
+        let next = crons.iter().map(|cron| cron.next_after(&since)).collect();
+
+        Self { crons, next, grace }
+    }
+
+    /// 规则的下一次计划执行时间
+    pub fn next_run(&self, rule: usize) -> Option<Timestamp> {
+        self.next.get(rule).copied().flatten()
+    }
+
+    /// 最近的一次计划执行时间
+    pub fn next_wakeup(&self) -> Option<Timestamp> {
+        self.next.iter().flatten().min().copied()
+    }
+
+    /// 取出到 `now` 为止到期的规则，并计算它们的下一次执行时间
+    ///
+    /// 同一规则错过多次时只考虑到 `now` 为止的最后一次：不超过补执行时限时执行，更早的各次报告为错过。
+    pub fn due(&mut self, now: &Timestamp) -> Vec<Due> {
+        let mut due = Vec::new();
+
+        for (rule, next) in self.next.iter_mut().enumerate() {
+            let Some(first) = *next else {
+                continue;
+            };
+
+            if first > *now {
+                continue;
+            }
+
+            let cron = &self.crons[rule];
+            let (mut latest, mut older) = (first, 0);
+            while let Some(at) = cron.next_after(&latest).filter(|at| at <= now) {
+                latest = at;
+                older += 1;
+            }
+
+            if *now - latest <= self.grace {
+                if older > 0 {
+                    due.push(Due::Missed {
+                        rule,
+                        at: first,
+                        count: older,
+                    });
+                }
+                due.push(Due::Run { rule, at: latest });
+            } else {
+                due.push(Due::Missed {
+                    rule,
+                    at: first,
+                    count: older + 1,
+                });
+            }
+            *next = cron.next_after(now);
+        }
+
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> Timestamp {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .unwrap()
+            .with_timezone(&Shanghai)
+    }
+
+    #[test]
+    fn test_cron_parse_err() {
+        assert!(CronExpr::parse("0 8 * *").is_err());
+        assert!(CronExpr::parse("60 8 * * *").is_err());
+        assert!(CronExpr::parse("0 8 0 * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+        assert!(CronExpr::parse("0 20-8 * * *").is_err());
+        assert!(CronExpr::parse("0 8 * * mon").is_err());
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let cron = CronExpr::parse("0 8,20 * * *").unwrap();
+        let now = time("2025-11-12T07:59:30+08:00");
+        assert_eq!(
+            cron.next_after(&now).unwrap().to_rfc3339(),
+            "2025-11-12T08:00:00+08:00"
+        );
+        let now = time("2025-11-12T08:00:00+08:00");
+        assert_eq!(
+            cron.next_after(&now).unwrap().to_rfc3339(),
+            "2025-11-12T20:00:00+08:00"
+        );
+
+        // 2025-11-12 是周三，下一个周日是 11-16
+        let sunday = CronExpr::parse("30 9 * * 7").unwrap();
+        assert_eq!(
+            sunday.next_after(&now).unwrap().to_rfc3339(),
+            "2025-11-16T09:30:00+08:00"
+        );
+
+        let step = CronExpr::parse("*/20 9-10 * * 1-5").unwrap();
+        assert_eq!(
+            step.next_after(&time("2025-11-14T10:45:00+08:00"))
+                .unwrap()
+                .to_rfc3339(),
+            "2025-11-17T09:00:00+08:00"
+        );
+
+        let leap = CronExpr::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap.next_after(&now).unwrap().to_rfc3339(),
+            "2028-02-29T00:00:00+08:00"
+        );
+    }
+
+    #[test]
+    fn test_cron_day_or_weekday() {
+        // 日和周都指定时满足其一即可：每月 15 日或每个周一
+        let cron = CronExpr::parse("0 0 15 * 1").unwrap();
+        assert_eq!(
+            cron.next_after(&time("2025-11-12T00:00:00+08:00"))
+                .unwrap()
+                .to_rfc3339(),
+            "2025-11-15T00:00:00+08:00"
+        );
+        assert_eq!(
+            cron.next_after(&time("2025-11-15T00:00:00+08:00"))
+                .unwrap()
+                .to_rfc3339(),
+            "2025-11-17T00:00:00+08:00"
+        );
+    }
+
+    #[test]
+    fn test_scheduler_due() {
+        let crons = vec![
+            CronExpr::parse("0 8 * * *").unwrap(),
+            CronExpr::parse("0 20 * * *").unwrap(),
+        ];
+        let grace = Duration::minutes(10);
+
+        // 08:05 启动时，08:00 的执行在补执行时限内
+        let mut scheduler = Scheduler::new(crons, grace, &time("2025-11-12T08:05:00+08:00"));
+        assert_eq!(
+            scheduler.next_wakeup().unwrap().to_rfc3339(),
+            "2025-11-12T08:00:00+08:00"
+        );
+
+        let due = scheduler.due(&time("2025-11-12T08:05:00+08:00"));
+        assert_eq!(
+            due,
+            vec![Due::Run {
+                rule: 0,
+                at: time("2025-11-12T08:00:00+08:00")
+            }]
+        );
+        assert_eq!(
+            scheduler.next_run(0).unwrap().to_rfc3339(),
+            "2025-11-13T08:00:00+08:00"
+        );
+
+        // 休眠到 20:30 才恢复，超过补执行时限
+        let due = scheduler.due(&time("2025-11-12T20:30:00+08:00"));
+        assert_eq!(
+            due,
+            vec![Due::Missed {
+                rule: 1,
+                at: time("2025-11-12T20:00:00+08:00"),
+                count: 1,
+            }]
+        );
+        assert_eq!(
+            scheduler.next_run(1).unwrap().to_rfc3339(),
+            "2025-11-13T20:00:00+08:00"
+        );
+        assert!(scheduler.due(&time("2025-11-12T20:31:00+08:00")).is_empty());
+
+        // 每小时执行：休眠期间错过 10:00 和 11:00，12:05 恢复时 12:00 的执行仍在补执行时限内
+        let crons = vec![CronExpr::parse("0 * * * *").unwrap()];
+        let mut scheduler = Scheduler::new(crons, grace, &time("2025-11-12T09:30:00+08:00"));
+        assert!(scheduler.due(&time("2025-11-12T09:30:00+08:00")).is_empty());
+        assert_eq!(
+            scheduler.due(&time("2025-11-12T12:05:00+08:00")),
+            vec![
+                Due::Missed {
+                    rule: 0,
+                    at: time("2025-11-12T10:00:00+08:00"),
+                    count: 2,
+                },
+                Due::Run {
+                    rule: 0,
+                    at: time("2025-11-12T12:00:00+08:00")
+                },
+            ]
+        );
+        assert_eq!(
+            scheduler.next_run(0).unwrap().to_rfc3339(),
+            "2025-11-12T13:00:00+08:00"
+        );
+
+        // 恢复时最后一次也超过补执行时限，全部报告为错过
+        assert_eq!(
+            scheduler.due(&time("2025-11-12T15:30:00+08:00")),
+            vec![Due::Missed {
+                rule: 0,
+                at: time("2025-11-12T13:00:00+08:00"),
+                count: 3,
+            }]
+        );
+
+        // 补执行时限为 0 时，到点唤醒的少量延迟仍然正常执行
+        let crons = vec![CronExpr::parse("0 8 * * *").unwrap()];
+        let mut scheduler =
+            Scheduler::new(crons, Duration::zero(), &time("2025-11-12T07:59:00+08:00"));
+        assert!(matches!(
+            scheduler.due(&time("2025-11-12T08:00:01+08:00"))[..],
+            [Due::Run { rule: 0, .. }]
+        ));
+    }
+}
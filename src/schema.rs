@@ -0,0 +1,373 @@
+//! 接口数据格式校验模块
+//!
+//! 解析订单数据时会忽略未知的字段、按 0 处理类型不正确的值、跳过无法解析的记录。本模块将这些情况逐项整理为警告，
+//! 以便在店铺后台的接口发生变化时及时发现，而不是只看到空白的表格。
+
+use crate::constants::{
+    BATTLE_RECORDS_INDEX, DLDATA_LEN, EXP_RECORDS_INDEX, GOLD_RECORDS_INDEX, ID_LIST_INDEX,
+};
+use crate::raw::{CONFIG_FIELDS, DATA_FIELDS};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// JSON 值的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueKind {
+    /// null
+    Null,
+    /// 布尔值
+    Bool,
+    /// 整数
+    Integer,
+    /// 小数
+    Float,
+    /// 字符串
+    String,
+    /// 数组
+    Array,
+    /// 对象
+    Object,
+}
+
+impl ValueKind {
+    /// 获取 JSON 值的类型
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Number(n) if n.is_i64() || n.is_u64() => Self::Integer,
+            Value::Number(_) => Self::Float,
+            Value::String(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+
+    /// 中文名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool => "布尔值",
+            Self::Integer => "整数",
+            Self::Float => "小数",
+            Self::String => "字符串",
+            Self::Array => "数组",
+            Self::Object => "对象",
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// dldata 各下标预期的类型
+const DLDATA_KINDS: [ValueKind; DLDATA_LEN] = [
+    ValueKind::Integer,
+    ValueKind::Integer,
+    ValueKind::Integer,
+    ValueKind::String,
+    ValueKind::Integer,
+    ValueKind::Integer,
+    ValueKind::Integer,
+    ValueKind::String,
+    ValueKind::Integer,
+    ValueKind::Integer,
+    ValueKind::Array,
+    ValueKind::Array,
+    ValueKind::Array,
+    ValueKind::Array,
+    ValueKind::Integer,
+    ValueKind::Integer,
+];
+
+/// 各类记录的下标和每条记录至少包含的项数
+const RECORD_LISTS: [(usize, usize); 3] = [
+    (GOLD_RECORDS_INDEX, 3),
+    (EXP_RECORDS_INDEX, 5),
+    (BATTLE_RECORDS_INDEX, 4),
+];
+
+/// 接口数据格式警告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SchemaWarning {
+    /// 未知的字段（解析时被忽略）
+    UnknownField {
+        /// 字段路径，如 `num9`、`config.mode`
+        path: String,
+    },
+    /// dldata 的长度与预期不一致
+    DlDataLength {
+        /// 预期的长度
+        expected: usize,
+        /// 实际的长度
+        actual: usize,
+    },
+    /// 值的类型与预期不一致（解析时按默认值处理）
+    WrongType {
+        /// 字段路径，如 `dldata[8]`，`[*]` 表示列表中的每一项
+        path: String,
+        /// 预期的类型
+        expected: ValueKind,
+        /// 实际的类型
+        actual: ValueKind,
+    },
+    /// 格式不正确而被忽略的记录
+    DroppedRecords {
+        /// 记录列表的路径，如 `dldata[12]`
+        path: String,
+        /// 被忽略的记录条数
+        dropped: usize,
+        /// 记录总条数
+        total: usize,
+    },
+}
+
+impl fmt::Display for SchemaWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField { path } => write!(f, "未知字段 {}", path),
+            Self::DlDataLength { expected, actual } => {
+                write!(f, "dldata 长度为 {}，预期为 {}", actual, expected)
+            }
+            Self::WrongType {
+                path,
+                expected,
+                actual,
+            } => write!(f, "{} 的类型为{}，预期为{}", path, actual, expected),
+            Self::DroppedRecords {
+                path,
+                dropped,
+                total,
+            } => write!(
+                f,
+                "{} 中有 {}/{} 条记录格式不正确，已被忽略",
+                path, dropped, total
+            ),
+        }
+    }
+}
+
+/// 校验接口返回的订单数据的格式
+///
+/// `config` 或 `dldata` 不是合法的 JSON 时不做进一步校验（解析时会直接报错）。
+pub fn validate(data: &Map<String, Value>) -> Vec<SchemaWarning> {
+    let mut warnings = Vec::new();
+
+    for (key, value) in data {
+        if !DATA_FIELDS.iter().any(|(name, _)| name == key) {
+            warnings.push(SchemaWarning::UnknownField { path: key.clone() });
+        } else {
+            check_kind(&mut warnings, key.clone(), value, ValueKind::String);
+        }
+    }
+
+    let nested = |key: &str| {
+        data.get(key)
+            .and_then(|value| value.as_str())
+            .and_then(|s| serde_json::from_str::<Value>(s).ok())
+    };
+
+    if let Some(Value::Object(config)) = nested("config") {
+        validate_config(&mut warnings, &config);
+    }
+    if let Some(Value::Array(dldata)) = nested("dldata") {
+        validate_dldata(&mut warnings, &dldata);
+    }
+
+    warnings
+}
+
+/// 校验 config 字段
+fn validate_config(warnings: &mut Vec<SchemaWarning>, config: &Map<String, Value>) {
+    for (key, value) in config {
+        let path = format!("config.{}", key);
+        if CONFIG_FIELDS.iter().any(|(name, _)| name == key) {
+            check_kind(warnings, path, value, ValueKind::String);
+        } else {
+            warnings.push(SchemaWarning::UnknownField { path });
+        }
+    }
+}
+
+/// 校验 dldata 字段
+fn validate_dldata(warnings: &mut Vec<SchemaWarning>, dldata: &[Value]) {
+    if dldata.len() != DLDATA_LEN {
+        warnings.push(SchemaWarning::DlDataLength {
+            expected: DLDATA_LEN,
+            actual: dldata.len(),
+        });
+    }
+
+    for (i, (value, expected)) in dldata.iter().zip(DLDATA_KINDS).enumerate() {
+        check_kind(warnings, format!("dldata[{}]", i), value, expected);
+    }
+
+    for (index, min_len) in RECORD_LISTS {
+        if let Some(Value::Array(records)) = dldata.get(index) {
+            validate_records(warnings, index, records, min_len);
+        }
+    }
+
+    if let Some(Value::Array(ids)) = dldata.get(ID_LIST_INDEX)
+        && let Some(id) = ids.iter().find(|id| ValueKind::of(id) != ValueKind::String)
+    {
+        check_kind(
+            warnings,
+            format!("dldata[{}][*]", ID_LIST_INDEX),
+            id,
+            ValueKind::String,
+        );
+    }
+}
+
+/// 校验记录列表：统计被忽略的记录，每一列的类型不正确时只报告一次
+fn validate_records(
+    warnings: &mut Vec<SchemaWarning>,
+    index: usize,
+    records: &[Value],
+    min_len: usize,
+) {
+    let mut dropped = 0;
+    let mut wrong_columns = vec![None; min_len];
+
+    for record in records {
+        match record.as_array() {
+            Some(items) if items.len() >= min_len => {
+                for (column, item) in items.iter().take(min_len).enumerate() {
+                    if wrong_columns[column].is_none() && !item.is_i64() {
+                        wrong_columns[column] = Some(ValueKind::of(item));
+                    }
+                }
+            }
+            _ => dropped += 1,
+        }
+    }
+
+    for (column, actual) in wrong_columns.into_iter().enumerate() {
+        if let Some(actual) = actual {
+            warnings.push(SchemaWarning::WrongType {
+                path: format!("dldata[{}][*][{}]", index, column),
+                expected: ValueKind::Integer,
+                actual,
+            });
+        }
+    }
+
+    if dropped > 0 {
+        warnings.push(SchemaWarning::DroppedRecords {
+            path: format!("dldata[{}]", index),
+            dropped,
+            total: records.len(),
+        });
+    }
+}
+
+/// 检查值的类型
+fn check_kind(warnings: &mut Vec<SchemaWarning>, path: String, value: &Value, expected: ValueKind) {
+    let actual = ValueKind::of(value);
+    if actual != expected {
+        warnings.push(SchemaWarning::WrongType {
+            path,
+            expected,
+            actual,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data(config: Value, dldata: Value) -> Map<String, Value> {
+        json!({
+            "oid": "2025010100013333123456",
+            "num1": "13040",
+            "config": config.to_string(),
+            "dldata": dldata.to_string(),
+        })
+        .as_object()
+        .unwrap()
+        .clone()
+    }
+
+    fn dldata() -> Vec<Value> {
+        let mut arr = vec![json!(0); DLDATA_LEN];
+        arr[3] = json!("20251112");
+        arr[7] = json!("CHN");
+        arr[10] = json!([[1762920279, 50, 0]]);
+        arr[11] = json!([[1762928742, 14, 43, 31043, 443]]);
+        arr[12] = json!([[1762928742, -1, 14, 1762927893]]);
+        arr[13] = json!(["9170774525"]);
+        arr
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        let data = data(json!({"battlemode": "2", "region": "CN"}), json!(dldata()));
+        assert_eq!(validate(&data), []);
+    }
+
+    #[test]
+    fn test_validate_warnings() {
+        let mut arr = dldata();
+        arr[8] = json!("1762906878");
+        arr[12] = json!([
+            [1762928742, -1, 14, 1762927893],
+            [1762928742, "-1", 14, 1762927893],
+            [1762928742, "1", 14, 1762927893],
+            [1762928742, 1],
+            null
+        ]);
+        arr.push(json!(0));
+
+        let mut data = data(json!({"battlemode": 2, "mode": "2"}), json!(arr));
+        data.insert("num9".into(), json!("1"));
+
+        assert_eq!(
+            validate(&data),
+            [
+                SchemaWarning::UnknownField {
+                    path: "num9".into()
+                },
+                SchemaWarning::WrongType {
+                    path: "config.battlemode".into(),
+                    expected: ValueKind::String,
+                    actual: ValueKind::Integer
+                },
+                SchemaWarning::UnknownField {
+                    path: "config.mode".into()
+                },
+                SchemaWarning::DlDataLength {
+                    expected: 16,
+                    actual: 17
+                },
+                SchemaWarning::WrongType {
+                    path: "dldata[8]".into(),
+                    expected: ValueKind::Integer,
+                    actual: ValueKind::String
+                },
+                SchemaWarning::WrongType {
+                    path: "dldata[12][*][1]".into(),
+                    expected: ValueKind::Integer,
+                    actual: ValueKind::String
+                },
+                SchemaWarning::DroppedRecords {
+                    path: "dldata[12]".into(),
+                    dropped: 2,
+                    total: 5
+                },
+            ]
+        );
+        assert_eq!(
+            validate(&data)[6].to_string(),
+            "dldata[12] 中有 2/5 条记录格式不正确，已被忽略"
+        );
+    }
+}
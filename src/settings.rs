@@ -0,0 +1,319 @@
+//! 订单设置模块
+//!
+//! 描述订单相关操作要设置的配置（包括期望状态文件和定时规则中的写法），计算设置前后的差异，并与重新查询得到的实际配置比较。
+
+use crate::display::{battle_heroes_name, battle_mode_name};
+use crate::{ModeArg, parse_hero_inputs, parse_hero_item};
+use clap::ValueEnum;
+use hs_order::models::{BattleMode, HeroSet, OrderConfig};
+use serde::Deserialize;
+
+/// 订单相关操作要设置的配置（`None` 表示不修改）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// 对战模式
+    pub mode: Option<BattleMode>,
+    /// 对战英雄
+    pub heroes: Option<HeroSet>,
+    /// 是否自动领取奖励
+    pub auto_claim: Option<bool>,
+    /// 是否暂停上号
+    pub pause: Option<bool>,
+}
+
+/// 订单的期望状态（未写出的项保持不变）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DesiredState {
+    /// 对战模式，与命令行选项 --mode 的取值相同
+    pub mode: Option<String>,
+    /// 对战英雄，与命令行选项 --hero 的取值相同
+    pub heroes: Option<Heroes>,
+    /// 是否自动领取奖励
+    pub auto: Option<bool>,
+    /// 是否暂停上号
+    pub pause: Option<bool>,
+}
+
+/// 对战英雄：英雄名称列表、单个取值或掩码数值
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Heroes {
+    /// 掩码数值
+    Mask(u32),
+    /// 单个英雄名称、掩码或全部/ALL
+    One(String),
+    /// 英雄名称列表
+    Many(Vec<String>),
+}
+
+impl DesiredState {
+    /// 转换为要设置的配置
+    pub fn to_change(&self) -> std::result::Result<ConfigChange, String> {
+        let mode = self
+            .mode
+            .as_deref()
+            .map(|mode| {
+                ModeArg::from_str(mode, true).map_err(|_| format!("未知对战模式: {}", mode))
+            })
+            .transpose()?
+            .map(BattleMode::from);
+
+        let heroes = match &self.heroes {
+            Some(Heroes::Mask(mask)) => Some(vec![parse_hero_item(&mask.to_string())?]),
+            Some(Heroes::One(item)) => Some(vec![parse_hero_item(item)?]),
+            Some(Heroes::Many(items)) if items.is_empty() => {
+                return Err("对战英雄不能为空".to_string());
+            }
+            Some(Heroes::Many(items)) => Some(
+                items
+                    .iter()
+                    .map(|item| parse_hero_item(item))
+                    .collect::<std::result::Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        }
+        .map(|items| parse_hero_inputs(&items));
+
+        Ok(ConfigChange {
+            mode,
+            heroes,
+            auto_claim: self.auto,
+            pause: self.pause,
+        })
+    }
+}
+
+/// 配置项的差异
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// 配置项名称
+    pub name: &'static str,
+    /// 原来的值
+    pub before: String,
+    /// 新的值
+    pub after: String,
+}
+
+impl From<&OrderConfig> for ConfigChange {
+    /// 将配置中可以设置的各项全部作为要设置的项
+    fn from(config: &OrderConfig) -> Self {
+        Self {
+            mode: Some(config.mode),
+            heroes: Some(config.heroes),
+            auto_claim: Some(config.auto_claim),
+            pause: Some(config.pause),
+        }
+    }
+}
+
+impl ConfigChange {
+    /// 是否没有要设置的项
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 计算设置后的配置
+    pub fn apply(&self, config: &OrderConfig) -> OrderConfig {
+        OrderConfig {
+            mode: self.mode.unwrap_or(config.mode),
+            heroes: self.heroes.unwrap_or(config.heroes),
+            auto_claim: self.auto_claim.unwrap_or(config.auto_claim),
+            pause: self.pause.unwrap_or(config.pause),
+            region: config.region.clone(),
+        }
+    }
+
+    /// 去掉与当前配置相同的项，得到需要修改的最少配置项
+    pub fn changes_from(&self, current: &OrderConfig) -> ConfigChange {
+        ConfigChange {
+            mode: self.mode.filter(|mode| *mode != current.mode),
+            heroes: self.heroes.filter(|heroes| *heroes != current.heroes),
+            auto_claim: self.auto_claim.filter(|on| *on != current.auto_claim),
+            pause: self.pause.filter(|on| *on != current.pause),
+        }
+    }
+
+    /// 要设置的项的文本描述
+    pub fn describe(&self) -> String {
+        let items: Vec<_> = [
+            self.mode
+                .map(|mode| format!("对战模式 {}", mode_text(mode))),
+            self.heroes
+                .map(|heroes| format!("对战英雄 {}", heroes_text(heroes))),
+            self.auto_claim
+                .map(|on| format!("自动领取 {}", switch_text(on))),
+            self.pause.map(|on| format!("暂停上号 {}", switch_text(on))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if items.is_empty() {
+            "不修改".to_string()
+        } else {
+            items.join("，")
+        }
+    }
+
+    /// 找出实际配置中与要设置的值不一致的项（`before` 为实际值，`after` 为期望值）
+    pub fn mismatches(&self, actual: &OrderConfig) -> Vec<FieldDiff> {
+        diff_configs(actual, &self.apply(actual))
+    }
+}
+
+/// 比较两份配置中可以设置的项
+pub fn diff_configs(before: &OrderConfig, after: &OrderConfig) -> Vec<FieldDiff> {
+    [
+        ("对战模式", mode_text(before.mode), mode_text(after.mode)),
+        (
+            "对战英雄",
+            heroes_text(before.heroes),
+            heroes_text(after.heroes),
+        ),
+        (
+            "自动领取",
+            switch_text(before.auto_claim),
+            switch_text(after.auto_claim),
+        ),
+        (
+            "暂停上号",
+            switch_text(before.pause),
+            switch_text(after.pause),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, before, after)| before != after)
+    .map(|(name, before, after)| FieldDiff {
+        name,
+        before,
+        after,
+    })
+    .collect()
+}
+
+/// 对战模式文本（包括接口中的 battlemode 代码）
+fn mode_text(mode: BattleMode) -> String {
+    match mode.code() {
+        Some(code) => format!("{} ({})", battle_mode_name(mode), code),
+        None => battle_mode_name(mode).to_string(),
+    }
+}
+
+/// 对战英雄文本（包括接口中的 battleheroes 掩码）
+fn heroes_text(heroes: HeroSet) -> String {
+    format!("{} ({})", battle_heroes_name(heroes), heroes.bits())
+}
+
+/// 开关类配置项的文本（包括接口中的取值）
+fn switch_text(on: bool) -> String {
+    if on { "开启 (1)" } else { "关闭 (0)" }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_change_apply() {
+        let config = OrderConfig {
+            heroes: HeroSet::MAGE,
+            region: Some("CN".into()),
+            ..OrderConfig::default()
+        };
+        let change = ConfigChange {
+            mode: Some(BattleMode::Wild),
+            auto_claim: Some(true),
+            ..ConfigChange::default()
+        };
+
+        let after = change.apply(&config);
+        assert_eq!(after.mode, BattleMode::Wild);
+        assert_eq!(after.heroes, HeroSet::MAGE);
+        assert!(after.auto_claim);
+        assert_eq!(after.region.as_deref(), Some("CN"));
+        assert_eq!(ConfigChange::default().apply(&config), config);
+    }
+
+    #[test]
+    fn test_config_change_changes_from() {
+        let current = OrderConfig {
+            mode: BattleMode::Battlegrounds,
+            auto_claim: true,
+            ..OrderConfig::default()
+        };
+        let desired = ConfigChange {
+            mode: Some(BattleMode::Battlegrounds),
+            heroes: Some(HeroSet::all()),
+            auto_claim: Some(false),
+            pause: Some(false),
+        };
+
+        assert_eq!(
+            desired.changes_from(&current),
+            ConfigChange {
+                auto_claim: Some(false),
+                ..ConfigChange::default()
+            }
+        );
+        assert!(desired.changes_from(&desired.apply(&current)).is_empty());
+    }
+
+    #[test]
+    fn test_config_change_mismatches() {
+        let actual = OrderConfig {
+            mode: BattleMode::Standard,
+            heroes: HeroSet::MAGE,
+            auto_claim: true,
+            ..OrderConfig::default()
+        };
+
+        let change = ConfigChange {
+            mode: Some(BattleMode::Battlegrounds),
+            heroes: Some(HeroSet::MAGE),
+            auto_claim: Some(true),
+            pause: Some(true),
+        };
+        assert_eq!(
+            change.mismatches(&actual),
+            vec![
+                FieldDiff {
+                    name: "对战模式",
+                    before: "标准模式 (2)".into(),
+                    after: "酒馆战棋 (5)".into(),
+                },
+                FieldDiff {
+                    name: "暂停上号",
+                    before: "关闭 (0)".into(),
+                    after: "开启 (1)".into(),
+                },
+            ]
+        );
+
+        assert!(ConfigChange::default().is_empty());
+        assert_eq!(
+            change.describe(),
+            "对战模式 酒馆战棋 (5)，对战英雄 法师 (128)，自动领取 开启 (1)，暂停上号 开启 (1)"
+        );
+        assert_eq!(ConfigChange::default().describe(), "不修改");
+        assert!(ConfigChange::default().mismatches(&actual).is_empty());
+    }
+
+    #[test]
+    fn test_diff_configs_heroes_mask() {
+        let before = OrderConfig::default();
+        let after = OrderConfig {
+            heroes: HeroSet::MAGE | HeroSet::WARLOCK,
+            ..OrderConfig::default()
+        };
+
+        assert_eq!(
+            diff_configs(&before, &after),
+            vec![FieldDiff {
+                name: "对战英雄",
+                before: "全部 (2047)".into(),
+                after: "术士, 法师 (192)".into(),
+            }]
+        );
+    }
+}
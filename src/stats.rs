@@ -0,0 +1,185 @@
+//! 统计计算模块
+
+use crate::models::{BattleRecord, BattleResult, DlData, ExpRecord, GoldRecord};
+use serde::Serialize;
+
+/// 游戏数据统计结果
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Statistics {
+    /// 总金币变化
+    pub total_gold: i64,
+    /// 总卡包变化
+    pub total_packs: i64,
+    /// 总经验变化
+    pub total_exp: i64,
+    /// 胜利场次
+    pub wins: usize,
+    /// 失败场次
+    pub losses: usize,
+    /// 胜率（百分比，未知结果的对战不计入）
+    pub win_rate: u8,
+    /// 对战经验
+    pub battle_exp: i64,
+}
+
+impl Statistics {
+    /// 根据游戏统计数据计算统计结果
+    pub fn from_dldata(dldata: &DlData) -> Self {
+        let (total_gold, total_packs) = calculate_gold_totals(&dldata.gold_records);
+        let total_exp = calculate_exp_total(&dldata.exp_records);
+        let (wins, losses, battle_exp) = calculate_battle_stats(&dldata.battle_records);
+
+        Self {
+            total_gold,
+            total_packs,
+            total_exp,
+            wins,
+            losses,
+            win_rate: calculate_win_rate(wins, losses),
+            battle_exp,
+        }
+    }
+}
+
+/// 计算金币总计
+pub fn calculate_gold_totals(gold_records: &[GoldRecord]) -> (i64, i64) {
+    let mut total_gold = 0;
+    let mut total_packs = 0;
+
+    for record in gold_records {
+        total_gold += record.gold_change;
+        total_packs += record.pack_change;
+    }
+
+    (total_gold, total_packs)
+}
+
+/// 计算经验总计
+pub fn calculate_exp_total(exp_records: &[ExpRecord]) -> i64 {
+    exp_records.iter().map(|record| record.exp_change).sum()
+}
+
+/// 计算对战统计，返回（胜利场次，失败场次，对战经验）
+pub fn calculate_battle_stats(battle_records: &[BattleRecord]) -> (usize, usize, i64) {
+    let mut wins = 0;
+    let mut losses = 0;
+    let mut total_exp = 0;
+
+    for record in battle_records {
+        match record.result {
+            BattleResult::Win => wins += 1,
+            BattleResult::Loss => losses += 1,
+            BattleResult::Unknown => {}
+        }
+        total_exp += record.exp;
+    }
+
+    (wins, losses, total_exp)
+}
+
+/// 计算胜率（百分比）
+pub fn calculate_win_rate(wins: usize, losses: usize) -> u8 {
+    let total_battles = wins + losses;
+
+    if total_battles > 0 {
+        (wins as f64 / total_battles as f64 * 100.0) as u8
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_gold_totals() {
+        let items = vec![
+            GoldRecord {
+                time: None,
+                gold_change: 50,
+                pack_change: 0,
+            },
+            GoldRecord {
+                time: None,
+                gold_change: 100,
+                pack_change: 1,
+            },
+        ];
+        let (g, p) = calculate_gold_totals(&items);
+        assert_eq!(g, 150);
+        assert_eq!(p, 1);
+    }
+
+    #[test]
+    fn test_calculate_gold_totals_negative() {
+        let items = vec![
+            GoldRecord {
+                time: None,
+                gold_change: 100,
+                pack_change: 2,
+            },
+            GoldRecord {
+                time: None,
+                gold_change: -30,
+                pack_change: -1,
+            },
+        ];
+        assert_eq!(calculate_gold_totals(&items), (70, 1));
+    }
+
+    #[test]
+    fn test_calculate_exp_total() {
+        let items = vec![
+            ExpRecord {
+                time: None,
+                exp_change: 161,
+                level: 0,
+                total_exp: 0,
+                current_level_exp: 0,
+            },
+            ExpRecord {
+                time: None,
+                exp_change: 78,
+                level: 0,
+                total_exp: 0,
+                current_level_exp: 0,
+            },
+        ];
+        assert_eq!(calculate_exp_total(&items), 239);
+    }
+
+    #[test]
+    fn test_calculate_battle_stats() {
+        let items = vec![
+            BattleRecord {
+                time: None,
+                end_time: None,
+                result: BattleResult::Win,
+                exp: 156,
+            },
+            BattleRecord {
+                time: None,
+                end_time: None,
+                result: BattleResult::Loss,
+                exp: 43,
+            },
+            BattleRecord {
+                time: None,
+                end_time: None,
+                result: BattleResult::Unknown,
+                exp: 0,
+            },
+        ];
+        let (w, l, e) = calculate_battle_stats(&items);
+        assert_eq!(w, 1);
+        assert_eq!(l, 1);
+        assert_eq!(e, 199);
+    }
+
+    #[test]
+    fn test_calculate_win_rate() {
+        assert_eq!(calculate_win_rate(1, 3), 25);
+        assert_eq!(calculate_win_rate(0, 0), 0);
+    }
+}
@@ -0,0 +1,151 @@
+//! 订单摘要模块
+//!
+//! 将已解析的订单整理为字段名稳定的英文结构，用于 JSON/YAML 等机器可读格式的输出。
+
+use crate::models::{
+    BattleRecord, ExpRecord, GoldRecord, Order, OrderConfig, OrderStatus, ServiceDetails, Timestamp,
+};
+use crate::schema::SchemaWarning;
+use crate::stats::Statistics;
+use serde::Serialize;
+
+/// 订单摘要
+#[derive(Debug, Serialize)]
+pub struct OrderSummary<'a> {
+    /// 订单编号
+    pub oid: &'a str,
+    /// 到期时间
+    pub deadline: Option<Timestamp>,
+    /// 订单状态
+    pub status: OrderStatus,
+    /// 金币数量
+    pub gold: i64,
+    /// 卡包数量
+    pub packs: i64,
+    /// 奖励等级（与接口和 `records.exp[].level` 一致，从 0 开始，显示时需要 +1）
+    pub reward_level: i64,
+    /// 今日对战次数
+    pub today_battles: usize,
+    /// 每日上号时间段和最近一次上号时间
+    pub details: &'a str,
+    /// 由 details 解析得到的上号信息
+    pub service: &'a ServiceDetails,
+    /// 备注
+    pub remark: &'a str,
+    /// 订单配置
+    pub config: &'a OrderConfig,
+    /// 游戏记录
+    pub records: RecordsSummary<'a>,
+    /// 统计结果
+    pub statistics: Statistics,
+    /// 接口数据格式警告
+    pub warnings: &'a [SchemaWarning],
+}
+
+/// 游戏记录摘要
+#[derive(Debug, Serialize)]
+pub struct RecordsSummary<'a> {
+    /// 金币记录
+    pub gold: &'a [GoldRecord],
+    /// 经验记录
+    pub exp: &'a [ExpRecord],
+    /// 对战记录
+    pub battle: &'a [BattleRecord],
+}
+
+impl<'a> OrderSummary<'a> {
+    /// 根据已解析的订单生成摘要
+    pub fn new(order: &'a Order) -> Self {
+        let dldata = &order.dldata;
+
+        Self {
+            oid: &order.oid,
+            deadline: order.deadline,
+            status: order.status,
+            gold: order.gold,
+            packs: order.packs,
+            reward_level: order.reward_level,
+            today_battles: dldata.today_battles,
+            details: &order.details,
+            service: &order.service,
+            remark: &order.remark,
+            config: &order.config,
+            records: RecordsSummary {
+                gold: &dldata.gold_records,
+                exp: &dldata.exp_records,
+                battle: &dldata.battle_records,
+            },
+            statistics: Statistics::from_dldata(dldata),
+            warnings: &order.warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BattleMode, BattleResult, DlData, HeroSet, ServiceDetails};
+    use crate::utils::to_shanghai_time;
+    use serde_json::json;
+
+    #[test]
+    fn test_order_summary_json() {
+        let order = Order {
+            oid: "2025010100013333123456".into(),
+            deadline: to_shanghai_time(1766059530),
+            status: OrderStatus::Running,
+            gold: 13040,
+            packs: 1,
+            reward_level: 44,
+            num7: 0,
+            num8: 0,
+            details: String::new(),
+            service: ServiceDetails::default(),
+            remark: String::new(),
+            config: OrderConfig {
+                mode: BattleMode::Battlegrounds,
+                heroes: HeroSet::WARLOCK | HeroSet::MAGE,
+                auto_claim: true,
+                pause: false,
+                region: Some("CN".into()),
+            },
+            dldata: DlData {
+                gold_records: vec![],
+                exp_records: vec![],
+                battle_records: vec![BattleRecord {
+                    time: to_shanghai_time(1762927893),
+                    end_time: to_shanghai_time(1762928742),
+                    result: BattleResult::Loss,
+                    exp: 14,
+                }],
+                ..Default::default()
+            },
+            warnings: vec![],
+        };
+        let v = serde_json::to_value(OrderSummary::new(&order)).unwrap();
+        assert_eq!(v["deadline"], "2025-12-18T20:05:30+08:00");
+        assert_eq!(v["status"], "running");
+        assert_eq!(v["reward_level"], 44);
+        assert_eq!(v["service"], json!({"window": null, "last_login": null}));
+        assert_eq!(
+            v["config"],
+            json!({
+                "mode": "battlegrounds",
+                "heroes": ["warlock", "mage"],
+                "auto_claim": true,
+                "pause": false,
+                "region": "CN",
+            })
+        );
+        assert_eq!(
+            v["records"]["battle"][0],
+            json!({
+                "time": "2025-11-12T14:11:33+08:00",
+                "end_time": "2025-11-12T14:25:42+08:00",
+                "result": "loss",
+                "exp": 14
+            })
+        );
+        assert_eq!(v["statistics"]["losses"], 1);
+    }
+}
@@ -0,0 +1,916 @@
+//! 终端界面模块
+//!
+//! 在一个界面中显示订单信息、等级进度和可以滚动的金币、经验、对战记录表格，并通过快捷键修改订单配置。
+//! 本模块只负责界面状态、按键处理和绘制，查询和设置请求由 tui 子命令执行。
+
+use crate::display::{battle_heroes_name, battle_mode_name, battle_result_name, order_status_name};
+use crate::settings::{ConfigChange, diff_configs};
+use hs_order::constants::{EXP_PER_LEVEL, HERO_NAMES};
+use hs_order::models::{BattleMode, HeroSet, Order, OrderConfig, OrderStatus, Timestamp};
+use hs_order::progress::current_level_exp;
+use hs_order::utils::{format_signed, format_time};
+use ratatui::Frame;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Clear, Gauge, List, ListState, Paragraph, Row, Table, TableState, Tabs,
+};
+use std::time::Duration;
+
+/// 翻页时滚动的行数
+const PAGE_ROWS: isize = 10;
+
+/// 记录表格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordTab {
+    /// 金币记录
+    Gold,
+    /// 经验记录
+    Exp,
+    /// 对战记录
+    Battle,
+}
+
+impl RecordTab {
+    /// 所有表格（按显示顺序）
+    const ALL: [Self; 3] = [Self::Gold, Self::Exp, Self::Battle];
+
+    /// 表格标题
+    fn title(self) -> &'static str {
+        match self {
+            Self::Gold => "金币记录",
+            Self::Exp => "经验记录",
+            Self::Battle => "对战记录",
+        }
+    }
+
+    /// 表格在 [`RecordTab::ALL`] 中的下标
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// 下一个表格
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    /// 上一个表格
+    fn prev(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// 需要由调用方执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// 退出
+    Quit,
+    /// 立即刷新
+    Refresh,
+    /// 修改订单配置
+    Apply(ConfigChange),
+}
+
+/// 底部显示的状态消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    /// 普通消息
+    Info(String),
+    /// 错误消息
+    Error(String),
+}
+
+/// 对战英雄选择框
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HeroPicker {
+    /// 已选择的英雄
+    selected: HeroSet,
+    /// 光标所在的英雄下标
+    cursor: usize,
+}
+
+/// 终端界面状态
+///
+/// 修改订单配置的按键不会立即发送设置请求，而是先暂存修改并显示确认框，按 y 或回车确认后才执行。
+pub struct App {
+    /// 最近一次查询得到的订单数据
+    order: Order,
+    /// 只读模式下不能修改订单配置
+    read_only: bool,
+    /// 自动刷新间隔
+    interval: Duration,
+    /// 最近一次刷新的时间
+    refreshed_at: Timestamp,
+    /// 当前显示的表格
+    tab: RecordTab,
+    /// 各表格的选中行
+    tables: [TableState; 3],
+    /// 打开的对战英雄选择框
+    picker: Option<HeroPicker>,
+    /// 等待确认的配置修改
+    pending: Option<ConfigChange>,
+    /// 状态消息
+    status: Option<Status>,
+}
+
+impl App {
+    /// 创建界面状态，各表格默认选中第一行
+    pub fn new(order: Order, read_only: bool, interval: Duration, refreshed_at: Timestamp) -> Self {
+        let mut app = Self {
+            order,
+            read_only,
+            interval,
+            refreshed_at,
+            tab: RecordTab::Gold,
+            tables: Default::default(),
+            picker: None,
+            pending: None,
+            status: None,
+        };
+        app.clamp_selection();
+        app
+    }
+
+    /// 最近一次查询得到的订单数据
+    pub fn order(&self) -> &Order {
+        &self.order
+    }
+
+    /// 使用重新查询得到的订单数据更新界面
+    pub fn update(&mut self, order: Order, refreshed_at: Timestamp) {
+        self.order = order;
+        self.refreshed_at = refreshed_at;
+        self.clamp_selection();
+    }
+
+    /// 显示普通消息
+    pub fn set_info(&mut self, message: impl Into<String>) {
+        self.status = Some(Status::Info(message.into()));
+    }
+
+    /// 显示错误消息
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        self.status = Some(Status::Error(message.into()));
+    }
+
+    /// 清除状态消息
+    pub fn clear_status(&mut self) {
+        self.status = None;
+    }
+
+    /// 处理按键，返回需要由调用方执行的操作
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+
+        if let Some(pending) = &mut self.pending {
+            return match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    let change = pending.changes_from(&self.order.config);
+                    self.pending = None;
+                    if change == ConfigChange::default() {
+                        self.set_info("配置没有变化，无需修改");
+                        None
+                    } else {
+                        Some(Action::Apply(change))
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => {
+                    self.pending = None;
+                    self.set_info("已取消修改");
+                    None
+                }
+                KeyCode::Char('m') => {
+                    if let Some(mode) = &mut pending.mode {
+                        *mode = next_mode(*mode);
+                    }
+                    None
+                }
+                _ => None,
+            };
+        }
+
+        if let Some(picker) = &mut self.picker {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.picker = None;
+                    None
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    picker.cursor = picker.cursor.saturating_sub(1);
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    picker.cursor = (picker.cursor + 1).min(HERO_NAMES.len() - 1);
+                    None
+                }
+                KeyCode::Char(' ') => {
+                    picker
+                        .selected
+                        .toggle(HeroSet::from_bits_retain(1 << picker.cursor));
+                    None
+                }
+                KeyCode::Char('a') => {
+                    picker.selected = if picker.selected.is_all() {
+                        HeroSet::empty()
+                    } else {
+                        HeroSet::all()
+                    };
+                    None
+                }
+                KeyCode::Enter => {
+                    let heroes = picker.selected;
+                    if heroes.is_empty() {
+                        self.set_error("至少需要选择一个英雄");
+                        return None;
+                    }
+                    self.picker = None;
+                    self.stage(ConfigChange {
+                        heroes: Some(heroes),
+                        ..Default::default()
+                    })
+                }
+                _ => None,
+            };
+        }
+
+        let config = &self.order.config;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Char('r') => Some(Action::Refresh),
+            KeyCode::Tab | KeyCode::Right => {
+                self.tab = self.tab.next();
+                None
+            }
+            KeyCode::BackTab | KeyCode::Left => {
+                self.tab = self.tab.prev();
+                None
+            }
+            KeyCode::Char(c @ '1'..='3') => {
+                self.tab = RecordTab::ALL[c as usize - '1' as usize];
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.scroll(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll(-1),
+            KeyCode::PageDown => self.scroll(PAGE_ROWS),
+            KeyCode::PageUp => self.scroll(-PAGE_ROWS),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.scroll(isize::MAX),
+            KeyCode::Char('m') => self.stage(ConfigChange {
+                mode: Some(next_mode(config.mode)),
+                ..Default::default()
+            }),
+            KeyCode::Char('h') => {
+                if self.read_only {
+                    self.set_error("只读模式下不能修改订单配置");
+                } else {
+                    self.picker = Some(HeroPicker {
+                        selected: config.heroes,
+                        cursor: 0,
+                    });
+                }
+                None
+            }
+            KeyCode::Char('a') => self.stage(ConfigChange {
+                auto_claim: Some(!config.auto_claim),
+                ..Default::default()
+            }),
+            KeyCode::Char('p') => self.stage(ConfigChange {
+                pause: Some(!config.pause),
+                ..Default::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// 暂存配置修改并等待确认，只读模式下只显示错误消息
+    fn stage(&mut self, change: ConfigChange) -> Option<Action> {
+        if self.read_only {
+            self.set_error("只读模式下不能修改订单配置");
+        } else {
+            self.pending = Some(change);
+        }
+        None
+    }
+
+    /// 滚动当前表格
+    fn scroll(&mut self, delta: isize) -> Option<Action> {
+        let len = self.row_count(self.tab);
+        let state = &mut self.tables[self.tab.index()];
+        if len > 0 {
+            let current = state.selected().unwrap_or(0) as isize;
+            let next = current.saturating_add(delta).clamp(0, len as isize - 1);
+            state.select(Some(next as usize));
+        }
+        None
+    }
+
+    /// 记录条数变化后调整各表格的选中行
+    fn clamp_selection(&mut self) {
+        for tab in RecordTab::ALL {
+            let len = self.row_count(tab);
+            let state = &mut self.tables[tab.index()];
+            state.select(match len {
+                0 => None,
+                _ => Some(state.selected().unwrap_or(0).min(len - 1)),
+            });
+        }
+    }
+
+    /// 表格的记录条数
+    fn row_count(&self, tab: RecordTab) -> usize {
+        let dldata = &self.order.dldata;
+        match tab {
+            RecordTab::Gold => dldata.gold_records.len(),
+            RecordTab::Exp => dldata.exp_records.len(),
+            RecordTab::Battle => dldata.battle_records.len(),
+        }
+    }
+
+    /// 绘制界面
+    pub fn render(&mut self, frame: &mut Frame) {
+        let (left, right) = self.info_lines();
+        let info_height = left.len().max(right.len()) as u16 + 2;
+        let [info, gauge, tabs, table, footer] = Layout::vertical([
+            Constraint::Length(info_height),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+
+        let block = Block::bordered().title(" 订单信息 ");
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(block.inner(info));
+        frame.render_widget(block, info);
+        frame.render_widget(Paragraph::new(left), left_area);
+        frame.render_widget(Paragraph::new(right), right_area);
+
+        self.render_gauge(frame, gauge);
+        self.render_tabs(frame, tabs);
+        self.render_table(frame, table);
+        self.render_footer(frame, footer);
+
+        if let Some(picker) = &self.picker {
+            render_picker(frame, picker);
+        }
+        if let Some(pending) = &self.pending {
+            render_confirm(frame, &self.order.config, pending);
+        }
+    }
+
+    /// 订单信息的左右两栏
+    fn info_lines(&self) -> (Vec<Line<'static>>, Vec<Line<'static>>) {
+        let order = &self.order;
+        let config = &order.config;
+
+        let mut left = vec![
+            field("订单编号", order.oid.clone(), Color::Cyan),
+            field(
+                "截止时间",
+                format_time(order.deadline.as_ref()),
+                Color::White,
+            ),
+            field(
+                "订单状态",
+                order_status_name(order.status),
+                status_color(order.status),
+            ),
+        ];
+        match (&order.service.window, order.details.trim()) {
+            (Some(window), _) => left.push(field("上号时间", window.to_string(), Color::White)),
+            (None, "") => {}
+            (None, details) => left.push(field("上号时间", details.to_string(), Color::White)),
+        }
+        if let Some(last_login) = &order.service.last_login {
+            left.push(field(
+                "最近上号",
+                format_time(Some(last_login)),
+                Color::White,
+            ));
+        }
+        left.push(field(
+            "今日对战",
+            format!("{} 场", order.dldata.today_battles),
+            Color::Cyan,
+        ));
+        if !order.remark.is_empty() {
+            left.push(field("备注信息", order.remark.clone(), Color::White));
+        }
+        if !order.warnings.is_empty() {
+            left.push(field(
+                "格式警告",
+                format!(
+                    "{} 项，可以通过 inspect --raw 查看原始数据",
+                    order.warnings.len()
+                ),
+                Color::Yellow,
+            ));
+        }
+
+        let right = vec![
+            field(
+                "对战模式",
+                battle_mode_name(config.mode),
+                match config.mode {
+                    BattleMode::Unknown => Color::Magenta,
+                    _ => Color::Yellow,
+                },
+            ),
+            field("对战英雄", battle_heroes_name(config.heroes), Color::White),
+            field(
+                "自动领取",
+                if config.auto_claim {
+                    "开启"
+                } else {
+                    "关闭"
+                },
+                if config.auto_claim {
+                    Color::Green
+                } else {
+                    Color::Yellow
+                },
+            ),
+            field(
+                "暂停上号",
+                if config.pause { "是" } else { "否" },
+                if config.pause {
+                    Color::Red
+                } else {
+                    Color::Green
+                },
+            ),
+            field("金币数量", format!("{} 枚", order.gold), Color::Yellow),
+            field("卡包数量", format!("{} 包", order.packs), Color::Blue),
+            field(
+                "奖励等级",
+                format!("{} 级", order.reward_level + 1),
+                Color::Magenta,
+            ),
+        ];
+
+        (left, right)
+    }
+
+    /// 绘制当前等级的经验进度条
+    fn render_gauge(&self, frame: &mut Frame, area: Rect) {
+        let exp = current_level_exp(&self.order).clamp(0, EXP_PER_LEVEL);
+        let gauge = Gauge::default()
+            .block(Block::bordered().title(" 等级进度 "))
+            .gauge_style(Style::new().fg(Color::Magenta))
+            .ratio(exp as f64 / EXP_PER_LEVEL as f64)
+            .label(format!(
+                "等级 {} · {}/{}",
+                self.order.reward_level + 1,
+                exp,
+                EXP_PER_LEVEL
+            ));
+        frame.render_widget(gauge, area);
+    }
+
+    /// 绘制表格标签
+    fn render_tabs(&self, frame: &mut Frame, area: Rect) {
+        let titles = RecordTab::ALL.map(|tab| {
+            format!(
+                "{} {} ({})",
+                tab.index() + 1,
+                tab.title(),
+                self.row_count(tab)
+            )
+        });
+        let tabs = Tabs::new(titles)
+            .select(self.tab.index())
+            .highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        frame.render_widget(tabs, area);
+    }
+
+    /// 绘制当前表格
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let dldata = &self.order.dldata;
+        let (header, widths, rows): (&[&str], Vec<Constraint>, Vec<Row>) = match self.tab {
+            RecordTab::Gold => (
+                &["时间", "金币变化", "卡包变化"],
+                vec![
+                    Constraint::Length(20),
+                    Constraint::Length(10),
+                    Constraint::Length(10),
+                ],
+                dldata
+                    .gold_records
+                    .iter()
+                    .map(|r| {
+                        Row::new([
+                            format_time(r.time.as_ref()),
+                            format_signed(r.gold_change),
+                            format_signed(r.pack_change),
+                        ])
+                    })
+                    .collect(),
+            ),
+            RecordTab::Exp => (
+                &["时间", "经验变化", "等级", "总经验", "当前等级经验"],
+                vec![
+                    Constraint::Length(20),
+                    Constraint::Length(10),
+                    Constraint::Length(6),
+                    Constraint::Length(10),
+                    Constraint::Length(14),
+                ],
+                dldata
+                    .exp_records
+                    .iter()
+                    .map(|r| {
+                        Row::new([
+                            format_time(r.time.as_ref()),
+                            format_signed(r.exp_change),
+                            (r.level + 1).to_string(),
+                            r.total_exp.to_string(),
+                            r.current_level_exp.to_string(),
+                        ])
+                    })
+                    .collect(),
+            ),
+            RecordTab::Battle => (
+                &["时间", "结束时间", "时长", "结果", "经验"],
+                vec![
+                    Constraint::Length(20),
+                    Constraint::Length(20),
+                    Constraint::Length(12),
+                    Constraint::Length(6),
+                    Constraint::Length(6),
+                ],
+                dldata
+                    .battle_records
+                    .iter()
+                    .map(|r| {
+                        Row::new([
+                            format_time(r.time.as_ref()),
+                            r.end_time
+                                .as_ref()
+                                .map_or_else(|| "-".to_string(), |t| format_time(Some(t))),
+                            r.duration().map_or_else(
+                                || "-".to_string(),
+                                |d| {
+                                    format!("{} 分 {:02} 秒", d.num_minutes(), d.num_seconds() % 60)
+                                },
+                            ),
+                            battle_result_name(r.result).to_string(),
+                            format_signed(r.exp),
+                        ])
+                    })
+                    .collect(),
+            ),
+        };
+
+        let state = &mut self.tables[self.tab.index()];
+        let position = match state.selected() {
+            Some(i) => format!(" {}/{} ", i + 1, rows.len()),
+            None => " 暂无记录 ".to_string(),
+        };
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(header.iter().copied()).style(Style::new().add_modifier(Modifier::BOLD)),
+            )
+            .block(
+                Block::bordered()
+                    .title(format!(" {} ", self.tab.title()))
+                    .title_bottom(Line::from(position).right_aligned()),
+            )
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, area, state);
+    }
+
+    /// 绘制快捷键说明和状态消息
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let keys = if self.read_only {
+            "q 退出 · r 刷新 · Tab/1-3 切换表格 · ↑↓/PgUp/PgDn 滚动 · 只读模式"
+        } else {
+            "q 退出 · r 刷新 · Tab/1-3 切换表格 · ↑↓/PgUp/PgDn 滚动 · m 模式 · h 英雄 · a 自动领取 · p 暂停"
+        };
+        let status = match &self.status {
+            Some(Status::Info(message)) => Line::styled(message.clone(), Color::Green),
+            Some(Status::Error(message)) => Line::styled(message.clone(), Color::Red),
+            None => Line::styled(
+                format!(
+                    "上次刷新 {}，每 {} 秒自动刷新",
+                    self.refreshed_at.format("%H:%M:%S"),
+                    self.interval.as_secs()
+                ),
+                Color::DarkGray,
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(vec![Line::styled(keys, Color::DarkGray), status]),
+            area,
+        );
+    }
+}
+
+/// 下一个对战模式（未知模式的下一个为第一个模式）
+fn next_mode(mode: BattleMode) -> BattleMode {
+    let next = BattleMode::ALL
+        .iter()
+        .position(|&m| m == mode)
+        .map_or(0, |i| (i + 1) % BattleMode::ALL.len());
+    BattleMode::ALL[next]
+}
+
+/// 订单状态的颜色
+fn status_color(status: OrderStatus) -> Color {
+    match status {
+        OrderStatus::Finished => Color::Green,
+        OrderStatus::Running => Color::Blue,
+        OrderStatus::Banned => Color::Red,
+        OrderStatus::Unknown => Color::Magenta,
+    }
+}
+
+/// “名称: 值”形式的一行
+fn field(name: &str, value: impl Into<String>, color: Color) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(format!("{}: ", name)),
+        Span::styled(value.into(), color),
+    ])
+}
+
+/// 绘制对战英雄选择框
+fn render_picker(frame: &mut Frame, picker: &HeroPicker) {
+    let items: Vec<String> = HERO_NAMES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let checked = picker.selected.bits() & (1 << i) != 0;
+            format!("[{}] {}", if checked { "x" } else { " " }, name)
+        })
+        .collect();
+
+    let area = centered(frame.area(), 36, items.len() as u16 + 2);
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(" 对战英雄 ")
+                .title_bottom(" 空格选择 · a 全选 · 回车确认 "),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(picker.cursor));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// 绘制配置修改的确认框，列出各项修改前后的值
+fn render_confirm(frame: &mut Frame, config: &OrderConfig, pending: &ConfigChange) {
+    let diffs = diff_configs(config, &pending.apply(config));
+    let mut lines: Vec<Line> = diffs
+        .iter()
+        .map(|diff| {
+            Line::from(vec![
+                Span::raw(format!("{}: {} → ", diff.name, diff.before)),
+                Span::styled(diff.after.clone(), Color::Yellow),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::styled("与当前配置相同", Color::DarkGray));
+    }
+
+    let hint = if pending.mode.is_some() {
+        " m 切换模式 · y 确认 · n 取消 "
+    } else {
+        " y 确认 · n 取消 "
+    };
+    let width = lines
+        .iter()
+        .map(Line::width)
+        .max()
+        .unwrap_or(0)
+        .max(hint.len()) as u16
+        + 4;
+    let area = centered(frame.area(), width, lines.len() as u16 + 2);
+    let paragraph = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(" 确认修改订单配置 ")
+            .title_bottom(hint),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// 在区域中居中的矩形
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Asia::Shanghai;
+    use hs_order::models::{DlData, ExpRecord, GoldRecord, ServiceDetails};
+    use hs_order::utils::to_shanghai_time;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn order() -> Order {
+        Order {
+            oid: "2025010100013333123456".into(),
+            deadline: None,
+            status: OrderStatus::Running,
+            gold: 13040,
+            packs: 2,
+            reward_level: 43,
+            num7: 0,
+            num8: 0,
+            details: String::new(),
+            service: ServiceDetails::default(),
+            remark: String::new(),
+            config: OrderConfig::default(),
+            dldata: DlData {
+                gold_records: (0..30)
+                    .map(|i| GoldRecord {
+                        time: to_shanghai_time(1762920279 - i * 3600),
+                        gold_change: 50,
+                        pack_change: 0,
+                    })
+                    .collect(),
+                exp_records: vec![ExpRecord {
+                    time: to_shanghai_time(1762928742),
+                    exp_change: 14,
+                    level: 43,
+                    total_exp: 31043,
+                    current_level_exp: 443,
+                }],
+                battle_records: vec![],
+                ..Default::default()
+            },
+            warnings: vec![],
+        }
+    }
+
+    fn app(read_only: bool) -> App {
+        let now = Shanghai.with_ymd_and_hms(2025, 11, 12, 14, 30, 0).unwrap();
+        App::new(order(), read_only, Duration::from_secs(60), now)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn test_next_mode() {
+        assert_eq!(next_mode(BattleMode::Casual), BattleMode::Standard);
+        assert_eq!(next_mode(BattleMode::Battlegrounds), BattleMode::Casual);
+        assert_eq!(next_mode(BattleMode::Unknown), BattleMode::ALL[0]);
+    }
+
+    #[test]
+    fn test_scroll_and_tabs() {
+        let mut app = app(false);
+        assert_eq!(app.tables[0].selected(), Some(0));
+        assert_eq!(app.tables[2].selected(), None);
+
+        app.handle_key(key(KeyCode::PageDown));
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.tables[0].selected(), Some(11));
+        app.handle_key(key(KeyCode::End));
+        assert_eq!(app.tables[0].selected(), Some(29));
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.tables[0].selected(), Some(29));
+
+        app.handle_key(key(KeyCode::Tab));
+        assert_eq!(app.tab, RecordTab::Exp);
+        app.handle_key(key(KeyCode::Char('3')));
+        assert_eq!(app.tab, RecordTab::Battle);
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.tables[2].selected(), None);
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!(app.tab, RecordTab::Gold);
+
+        // 记录变少后选中行不超过最后一行
+        let mut order = order();
+        order.dldata.gold_records.truncate(5);
+        app.update(order, app.refreshed_at);
+        assert_eq!(app.tables[0].selected(), Some(4));
+    }
+
+    #[test]
+    fn test_setting_keys() {
+        let mut app = app(false);
+        app.order.config.mode = BattleMode::Standard;
+        let config = app.order().config.clone();
+
+        // 修改先暂存，确认后才发送
+        assert_eq!(app.handle_key(key(KeyCode::Char('a'))), None);
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('y'))),
+            Some(Action::Apply(ConfigChange {
+                auto_claim: Some(!config.auto_claim),
+                ..Default::default()
+            }))
+        );
+        assert!(app.pending.is_none());
+
+        assert_eq!(app.handle_key(key(KeyCode::Char('p'))), None);
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), None);
+        assert!(app.pending.is_none());
+
+        // 连续按 m 只切换暂存的模式，确认时才发送一次
+        assert_eq!(app.handle_key(key(KeyCode::Char('m'))), None);
+        assert_eq!(app.handle_key(key(KeyCode::Char('m'))), None);
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::Apply(ConfigChange {
+                mode: Some(next_mode(next_mode(config.mode))),
+                ..Default::default()
+            }))
+        );
+
+        // 切换一圈回到当前模式时不发送
+        for _ in 0..BattleMode::ALL.len() {
+            app.handle_key(key(KeyCode::Char('m')));
+        }
+        assert_eq!(app.handle_key(key(KeyCode::Char('y'))), None);
+
+        // 英雄选择框：不能确认空选择，清空后只选择第二个英雄
+        assert_eq!(app.handle_key(key(KeyCode::Char('h'))), None);
+        app.picker.as_mut().unwrap().selected = HeroSet::all();
+        app.handle_key(key(KeyCode::Char('a')));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+        assert!(app.picker.is_some());
+        assert!(matches!(app.status, Some(Status::Error(_))));
+        app.handle_key(key(KeyCode::Down));
+        app.handle_key(key(KeyCode::Char(' ')));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+        assert!(app.picker.is_none());
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('y'))),
+            Some(Action::Apply(ConfigChange {
+                heroes: HeroSet::from_hero_name("萨满祭司"),
+                ..Default::default()
+            }))
+        );
+
+        assert_eq!(
+            app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn test_read_only() {
+        let mut app = app(true);
+        assert_eq!(app.handle_key(key(KeyCode::Char('p'))), None);
+        assert_eq!(app.handle_key(key(KeyCode::Char('h'))), None);
+        assert!(app.picker.is_none());
+        assert!(matches!(app.status, Some(Status::Error(_))));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('r'))),
+            Some(Action::Refresh)
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let mut app = app(false);
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+            .replace(' ', "");
+        assert!(text.contains("订单信息"));
+        assert!(text.contains("2025010100013333123456"));
+        assert!(text.contains("等级44·443/1500"));
+        assert!(text.contains("1/30"));
+
+        app.handle_key(key(KeyCode::Char('p')));
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+            .replace(' ', "");
+        assert!(text.contains("确认修改订单配置"));
+        assert!(text.contains("暂停上号:关闭(0)→开启(1)"));
+    }
+}
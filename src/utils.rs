@@ -1,17 +1,31 @@
 //! 工具函数模块
 
-use chrono::TimeZone;
+use crate::models::Timestamp;
+use chrono::{NaiveDateTime, TimeZone};
 use chrono_tz::Asia::Shanghai;
 
-/// 格式化时间戳为可读格式（使用北京时间 UTC+8）
-pub fn format_timestamp(timestamp: i64) -> String {
+/// 将秒级时间戳转换为北京时间（UTC+8）
+///
+/// 时间戳为 0 或超出范围时返回 `None`
+pub fn to_shanghai_time(timestamp: i64) -> Option<Timestamp> {
     if timestamp == 0 {
-        return "未知时间".to_string();
+        return None;
     }
 
-    match Shanghai.timestamp_opt(timestamp, 0) {
-        chrono::LocalResult::Single(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
-        _ => "时间格式错误".to_string(),
+    Shanghai.timestamp_opt(timestamp, 0).single()
+}
+
+/// 将`YYYY-MM-DD HH:MM:SS`格式的字符串解析为北京时间（UTC+8）
+pub fn parse_datetime(s: &str) -> Option<Timestamp> {
+    let naive = NaiveDateTime::parse_from_str(s.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+    Shanghai.from_local_datetime(&naive).single()
+}
+
+/// 格式化时间为可读格式
+pub fn format_time(time: Option<&Timestamp>) -> String {
+    match time {
+        Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => "未知时间".to_string(),
     }
 }
 
@@ -21,52 +35,48 @@ pub fn format_signed<T: std::fmt::Display>(num: T) -> String {
     if s == "+0" { "0".to_string() } else { s }
 }
 
-/// 解析无符号整数字符串
-///
-/// 如果是大于等于0的整数，正常解析；否则解析为0
-pub fn parse_unsigned_int(s: &str) -> usize {
-    s.parse().unwrap_or(0).max(0)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_format_timestamp_zero() {
-        assert_eq!(format_timestamp(0), "未知时间");
+    fn test_to_shanghai_time_zero() {
+        assert_eq!(to_shanghai_time(0), None);
     }
 
     #[test]
-    fn test_format_timestamp_invalid() {
-        assert_eq!(format_timestamp(i64::MAX), "时间格式错误");
+    fn test_to_shanghai_time_invalid() {
+        assert_eq!(to_shanghai_time(i64::MAX), None);
     }
 
     #[test]
-    fn test_format_signed_number() {
-        assert_eq!(format_signed(3usize), "+3");
-        assert_eq!(format_signed(3i64), "+3");
-        assert_eq!(format_signed(-3i64), "-3");
+    fn test_to_shanghai_time_ok() {
+        let dt = to_shanghai_time(1759898700).unwrap();
+        assert_eq!(format_time(Some(&dt)), "2025-10-08 12:45:00");
     }
 
     #[test]
-    fn test_format_signed_number_zero() {
-        assert_eq!(format_signed(0usize), "0");
-        assert_eq!(format_signed(0i64), "0");
+    fn test_parse_datetime() {
+        let dt = parse_datetime("2025-12-18 20:05:30").unwrap();
+        assert_eq!(dt.timestamp(), 1766059530);
+        assert_eq!(parse_datetime("2025/12/18"), None);
     }
 
     #[test]
-    fn test_parse_signed_number_positive() {
-        assert_eq!(parse_unsigned_int("+5"), 5);
+    fn test_format_time_none() {
+        assert_eq!(format_time(None), "未知时间");
     }
 
     #[test]
-    fn test_parse_signed_number_negative() {
-        assert_eq!(parse_unsigned_int("-3"), 0);
+    fn test_format_signed_number() {
+        assert_eq!(format_signed(3usize), "+3");
+        assert_eq!(format_signed(3i64), "+3");
+        assert_eq!(format_signed(-3i64), "-3");
     }
 
     #[test]
-    fn test_parse_signed_number_invalid() {
-        assert_eq!(parse_unsigned_int("abc"), 0);
+    fn test_format_signed_number_zero() {
+        assert_eq!(format_signed(0usize), "0");
+        assert_eq!(format_signed(0i64), "0");
     }
 }
@@ -0,0 +1,189 @@
+//! 密码库模块
+//!
+//! 将各订单的战网密码前4位加密保存在本地数据目录的 `vault.json` 中。
+//! 加密密钥由密码库密码通过 Argon2id 派生，使用 XChaCha20-Poly1305 加密。
+
+use crate::paths;
+use anyhow::{Context, Result, anyhow, bail};
+use argon2::Argon2;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 密码库文件格式版本
+const VAULT_VERSION: u32 = 1;
+
+/// 密码库文件内容
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// 已解锁的密码库
+pub struct Vault {
+    path: PathBuf,
+    password: String,
+    /// 订单号到战网密码前4位的映射
+    entries: BTreeMap<String, String>,
+}
+
+impl Vault {
+    /// 本地数据目录下的密码库文件路径
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(paths::data_dir()?.join("vault.json"))
+    }
+
+    /// 使用密码库密码打开密码库，文件不存在时返回空密码库
+    pub fn open(path: PathBuf, password: &str) -> Result<Self> {
+        let entries = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("读取密码库 {} 失败", path.display()))?;
+            decrypt(&content, password)?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path,
+            password: password.to_string(),
+            entries,
+        })
+    }
+
+    /// 获取订单的战网密码前4位
+    pub fn get(&self, order_id: &str) -> Option<&str> {
+        self.entries.get(order_id).map(String::as_str)
+    }
+
+    /// 设置订单的战网密码前4位
+    pub fn set(&mut self, order_id: &str, pwd4: &str) {
+        self.entries.insert(order_id.to_string(), pwd4.to_string());
+    }
+
+    /// 删除订单的战网密码前4位，返回是否存在
+    pub fn remove(&mut self, order_id: &str) -> bool {
+        self.entries.remove(order_id).is_some()
+    }
+
+    /// 已保存的订单号
+    pub fn order_ids(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// 加密并保存密码库（每次保存都会重新生成盐和随机数）
+    pub fn save(&self) -> Result<()> {
+        let content = encrypt(&self.entries, &self.password)?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("创建目录 {} 失败", dir.display()))?;
+        }
+
+        write_private(&self.path, content.as_bytes())
+            .with_context(|| format!("写入密码库 {} 失败", self.path.display()))
+    }
+}
+
+/// 由密码库密码派生加密算法实例
+fn cipher(password: &str, salt: &[u8]) -> Result<XChaCha20Poly1305> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("派生密码库密钥失败: {}", e))?;
+    Ok(XChaCha20Poly1305::new(&key.into()))
+}
+
+/// 加密密码库内容
+fn encrypt(entries: &BTreeMap<String, String>, password: &str) -> Result<String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    rand::rng().fill(&mut salt);
+    rand::rng().fill(&mut nonce);
+
+    let plaintext = serde_json::to_vec(entries)?;
+    let ciphertext = cipher(password, &salt)?
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| anyhow!("加密密码库失败"))?;
+
+    let file = VaultFile {
+        version: VAULT_VERSION,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    Ok(serde_json::to_string_pretty(&file)? + "\n")
+}
+
+/// 解密密码库内容
+fn decrypt(content: &str, password: &str) -> Result<BTreeMap<String, String>> {
+    let file: VaultFile = serde_json::from_str(content).context("密码库格式不正确")?;
+
+    if file.version != VAULT_VERSION {
+        bail!("不支持的密码库版本: {}", file.version);
+    }
+
+    let decode = |s: &str| BASE64.decode(s).context("密码库格式不正确");
+    let salt = decode(&file.salt)?;
+    let nonce = decode(&file.nonce)?;
+    let ciphertext = decode(&file.ciphertext)?;
+
+    if nonce.len() != 24 {
+        bail!("密码库格式不正确");
+    }
+
+    let plaintext = cipher(password, &salt)?
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("密码库密码错误或文件已损坏"))?;
+    serde_json::from_slice(&plaintext).context("密码库格式不正确")
+}
+
+/// 写入只有当前用户可以读写的文件
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)?.write_all(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_round_trip() {
+        let path = std::env::temp_dir().join(format!("hs-order-vault-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut vault = Vault::open(path.clone(), "secret").unwrap();
+        vault.set("1234567890123456789", "zwmm");
+        vault.save().unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("zwmm"));
+
+        let vault = Vault::open(path.clone(), "secret").unwrap();
+        let wrong = Vault::open(path.clone(), "wrong");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(vault.get("1234567890123456789"), Some("zwmm"));
+        assert_eq!(
+            wrong.err().unwrap().to_string(),
+            "密码库密码错误或文件已损坏"
+        );
+    }
+}
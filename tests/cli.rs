@@ -1,4 +1,5 @@
 use assert_cmd::cargo::*;
+use hs_order::mock::{self, MockOrder, MockServer};
 use predicates::prelude::*;
 
 #[test]
@@ -60,3 +61,989 @@ fn query_conflicts_with_settings() {
         .assert()
         .failure();
 }
+
+#[test]
+fn pause_requires_pwd() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["1234567890123456789", "--pause", "on"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn output_requires_query() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args([
+        "1234567890123456789",
+        "--output",
+        "json",
+        "--auto",
+        "on",
+        "-p",
+        "zwmm",
+    ])
+    .assert()
+    .failure();
+}
+
+#[test]
+fn output_rejects_unknown_format() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["1234567890123456789", "--query", "--output", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("xml"));
+}
+
+#[test]
+fn from_file_missing_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["--query", "--from-file", "/nonexistent/orders.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("读取订单号文件"));
+}
+
+#[test]
+fn from_stdin_rejects_non_numeric_order_id() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["--query", "--from-file", "-"])
+        .write_stdin("1234567890123456789\nabc123\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("订单号必须为纯数字"));
+}
+
+#[test]
+fn requires_order_id() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.arg("--query").assert().failure();
+}
+
+#[test]
+fn watch_rejects_multiple_orders() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args([
+        "--query",
+        "--watch",
+        "1234567890123456789",
+        "1234567890123456780",
+    ])
+    .assert()
+    .code(1)
+    .stderr(predicate::str::contains("只能监控一个订单"));
+}
+
+#[test]
+fn watch_rejects_short_interval() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["--query", "--watch=5s", "1234567890123456789"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("监控间隔不能小于"));
+}
+
+#[test]
+fn history_reads_local_records() {
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-history-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("history")).unwrap();
+    std::fs::write(
+        dir.join("history").join("2025010100013333123456.jsonl"),
+        r#"{"kind":"battle","time":"2025-11-12T14:11:33+08:00","result":"loss","exp":14}
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    let assert = cmd
+        .env("HS_ORDER_DATA_DIR", &dir)
+        .args([
+            "history",
+            "--skip-query",
+            "2025010100013333123456",
+            "-o",
+            "json",
+        ])
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains(r#""result": "loss""#));
+}
+
+#[test]
+fn history_missing_fails() {
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-empty-{}", std::process::id()));
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.env("HS_ORDER_DATA_DIR", &dir)
+        .args(["history", "-s", "2025010100013333123456"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("本地没有订单编号"));
+}
+
+#[test]
+fn unknown_alias_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.env(
+        "HS_ORDER_CONFIG",
+        std::env::temp_dir().join("hs-order-cli-none.toml"),
+    )
+    .args(["--query", "@main"])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("配置文件中没有订单别名: @main"));
+}
+
+#[test]
+fn alias_without_pwd_fails_before_query() {
+    let config = std::env::temp_dir().join(format!("hs-order-cli-{}.toml", std::process::id()));
+    std::fs::write(
+        &config,
+        "[profiles.main]\norder_id = \"1234567890123456789\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    let assert = cmd
+        .env("HS_ORDER_CONFIG", &config)
+        .args(["@main", "--mode", "wild"])
+        .assert();
+    std::fs::remove_file(&config).unwrap();
+
+    assert
+        .failure()
+        .stderr(predicate::str::contains("必须提供战网密码前4位"));
+}
+
+#[test]
+fn history_alias_uses_profile_oid() {
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-alias-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("history")).unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        "[profiles.alt]\noid = \"2025010100013333123456\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("history").join("2025010100013333123456.jsonl"),
+        r#"{"kind":"gold","time":"2025-11-12T14:15:00+08:00","gold_change":10,"pack_change":0}
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    let assert = cmd
+        .env("HS_ORDER_DATA_DIR", &dir)
+        .env("HS_ORDER_CONFIG", dir.join("config.toml"))
+        .args(["history", "-s", "@alt", "-o", "json"])
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains(r#""gold_change": 10"#));
+}
+
+#[test]
+fn env_pwd_is_validated() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.env("HS_ORDER_PWD", "zw")
+        .args(["1234567890123456789", "--auto", "on"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "环境变量 HS_ORDER_PWD: 战网密码前4位必须为4个字符",
+        ));
+}
+
+#[test]
+fn pwd_vault_set_and_list() {
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-vault-{}", std::process::id()));
+    let run = |args: &[&str], pwd: Option<&str>| {
+        let mut cmd = cargo_bin_cmd!("hs-order-cli");
+        cmd.env("HS_ORDER_DATA_DIR", &dir)
+            .env("HS_ORDER_VAULT_PASSWORD", "secret")
+            .env_remove("HS_ORDER_PWD");
+        if let Some(pwd) = pwd {
+            cmd.env("HS_ORDER_PWD", pwd);
+        }
+        cmd.args(args).assert()
+    };
+
+    let set = run(&["pwd", "set", "1234567890123456789"], Some("zwmm"));
+    let list = run(&["pwd", "list"], None);
+    let vault = std::fs::read_to_string(dir.join("vault.json")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    set.success();
+    list.success()
+        .stdout(predicate::str::contains("1234567890123456789"));
+    assert!(!vault.contains("zwmm"));
+}
+
+#[test]
+fn invalid_endpoint_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args([
+        "--query",
+        "1234567890123456789",
+        "--endpoint",
+        "ftp://example.com/hs.php",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("接口地址不正确"));
+}
+
+#[test]
+fn invalid_timeout_env_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.env("HS_ORDER_TIMEOUT", "0")
+        .args(["--query", "1234567890123456789"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("请求超时时间必须大于 0"));
+}
+
+#[test]
+fn invalid_connect_timeout_env_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.env("HS_ORDER_CONNECT_TIMEOUT", "abc")
+        .args(["--query", "1234567890123456789"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--connect-timeout"));
+}
+
+#[test]
+fn too_many_retries_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["--query", "1234567890123456789", "--retries", "40"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--retries"));
+}
+
+/// 启动模拟服务器，返回服务器和隔离的数据目录
+fn mock_server(name: &str) -> (MockServer, std::path::PathBuf) {
+    let server = MockServer::start(vec![MockOrder::sample()]).unwrap();
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-{}-{}", name, std::process::id()));
+    (server, dir)
+}
+
+/// 使用模拟服务器和隔离的数据目录运行命令
+fn mock_cmd(server: &MockServer, dir: &std::path::Path) -> assert_cmd::Command {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.env("HS_ORDER_DATA_DIR", dir)
+        .env("HS_ORDER_CONFIG", dir.join("config.toml"))
+        .env("HS_ORDER_ENDPOINT", server.endpoint())
+        .env("HS_ORDER_RETRIES", "0")
+        .env_remove("HS_ORDER_PWD");
+    cmd
+}
+
+/// 没有可用的系统密钥环（没有 D-Bus 会话）时跳过密钥环，继续从密码库获取密码
+#[cfg(feature = "keyring")]
+#[test]
+fn keyring_unavailable_falls_back_to_vault() {
+    let (server, dir) = mock_server("mock-keyring");
+    let run = |args: &[&str], pwd: Option<&str>| {
+        let mut cmd = mock_cmd(&server, &dir);
+        cmd.env("HS_ORDER_VAULT_PASSWORD", "secret")
+            .env_remove("DBUS_SESSION_BUS_ADDRESS")
+            .env("XDG_RUNTIME_DIR", &dir);
+        if let Some(pwd) = pwd {
+            cmd.env("HS_ORDER_PWD", pwd);
+        }
+        cmd.args(args).assert()
+    };
+
+    let set = run(&["pwd", "set", "1234567890123456789"], Some("zwmm"));
+    let apply = run(&["1234567890123456789", "--auto", "on"], None);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    set.success();
+    apply
+        .success()
+        .stderr(predicate::str::contains("无法访问系统密钥环"));
+}
+
+#[test]
+fn mock_query_saves_history() {
+    let (server, dir) = mock_server("mock-query");
+
+    let query = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "-o", "json"])
+        .assert();
+    let history = mock_cmd(&server, &dir)
+        .args(["history", "-s", "2025010100013333123456", "-o", "json"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    query
+        .success()
+        .stdout(predicate::str::contains(
+            r#""oid": "2025010100013333123456""#,
+        ))
+        .stdout(predicate::str::contains(r#""gold": 13040"#));
+    history
+        .success()
+        .stdout(predicate::str::contains(r#""result": "win""#));
+}
+
+#[test]
+fn mock_query_unknown_order_fails() {
+    let (server, dir) = mock_server("mock-unknown");
+
+    let assert = mock_cmd(&server, &dir)
+        .args(["--query", "1111111111111111111", "--no-history"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert
+        .code(1)
+        .stdout(predicate::str::contains(mock::NOT_FOUND_MESSAGE));
+}
+
+#[test]
+fn mock_settings_apply() {
+    let (server, dir) = mock_server("mock-settings");
+
+    let assert = mock_cmd(&server, &dir)
+        .env("HS_ORDER_PWD", "zwmm")
+        .args([
+            "1234567890123456789",
+            "--mode",
+            "酒馆",
+            "--hero",
+            "法师,术士",
+            "--auto",
+            "on",
+            "--pause",
+            "on",
+        ])
+        .assert();
+    let oids = std::fs::read_to_string(dir.join("oids.json")).unwrap_or_default();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("已设置对战模式为"))
+        .stdout(predicate::str::contains(
+            "对战模式: 标准模式 (2) → 酒馆战棋 (5)",
+        ))
+        .stdout(predicate::str::contains(
+            "对战英雄: 全部 (2047) → 术士, 法师 (192)",
+        ))
+        .stdout(predicate::str::contains("已重新查询确认设置生效"));
+    let config = server.order("1234567890123456789").unwrap().config();
+    assert_eq!(config["battlemode"], "5");
+    assert_eq!(config["battleheroes"], "192");
+    assert_eq!(config["auto"], "1");
+    assert_eq!(config["pause"], "1");
+    assert!(oids.contains("2025010100013333123456"));
+}
+
+#[test]
+fn mock_settings_wrong_pwd_fails() {
+    let (server, dir) = mock_server("mock-wrong-pwd");
+
+    let assert = mock_cmd(&server, &dir)
+        .args(["1234567890123456789", "--mode", "wild", "--pwd", "xxxx"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert
+        .code(1)
+        .stdout(predicate::str::contains("设置对战模式失败"));
+    let config = server.order("1234567890123456789").unwrap().config();
+    assert_eq!(config["battlemode"], "2");
+}
+
+#[test]
+fn mock_query_reports_schema_drift() {
+    let mut order = MockOrder::sample();
+    order.data["num9"] = "1".into();
+    let mut dldata: Vec<serde_json::Value> =
+        serde_json::from_str(order.data["dldata"].as_str().unwrap()).unwrap();
+    dldata.push(0.into());
+    order.data["dldata"] = serde_json::to_string(&dldata).unwrap().into();
+
+    let server = MockServer::start(vec![order]).unwrap();
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-mock-drift-{}", std::process::id()));
+
+    let lenient = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "--no-history"])
+        .assert();
+    let json = mock_cmd(&server, &dir)
+        .args([
+            "--query",
+            "1234567890123456789",
+            "--no-history",
+            "-o",
+            "json",
+        ])
+        .assert();
+    let strict = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "--no-history", "--strict"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    lenient
+        .success()
+        .stdout(predicate::str::contains("接口数据格式警告"))
+        .stdout(predicate::str::contains("- 未知字段 num9"))
+        .stdout(predicate::str::contains("- dldata 长度为 17，预期为 16"));
+    json.success()
+        .stdout(predicate::str::contains(r#""kind": "unknown_field""#));
+    strict.code(1).stdout(predicate::str::contains(
+        "接口数据格式与预期不一致: 未知字段 num9",
+    ));
+}
+
+#[test]
+fn mock_query_sample_has_no_schema_warnings() {
+    let (server, dir) = mock_server("mock-schema");
+
+    let assert = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "--strict", "-o", "json"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert
+        .success()
+        .stdout(predicate::str::contains(r#""warnings": []"#));
+}
+
+#[test]
+fn mock_settings_not_applied_fails() {
+    let server = MockServer::start(vec![MockOrder {
+        ignore_settings: true,
+        ..MockOrder::sample()
+    }])
+    .unwrap();
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-mock-noop-{}", std::process::id()));
+
+    let assert = mock_cmd(&server, &dir)
+        .args([
+            "1234567890123456789",
+            "--mode",
+            "战棋",
+            "--auto",
+            "off",
+            "--pwd",
+            "zwmm",
+        ])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert
+        .code(1)
+        .stdout(predicate::str::contains(
+            "设置未生效 对战模式: 期望 酒馆战棋 (5)，实际 标准模式 (2)",
+        ))
+        .stdout(predicate::str::contains("1 项设置未生效"));
+}
+
+#[test]
+fn mock_settings_no_verify_skips_query() {
+    let server = MockServer::start(vec![MockOrder {
+        ignore_settings: true,
+        ..MockOrder::sample()
+    }])
+    .unwrap();
+    let dir = std::env::temp_dir().join(format!("hs-order-cli-mock-nv-{}", std::process::id()));
+
+    let assert = mock_cmd(&server, &dir)
+        .args([
+            "2025010100013333123456",
+            "--skip-query",
+            "--no-verify",
+            "--pause",
+            "on",
+            "--pwd",
+            "zwmm",
+        ])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert.success();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn mock_dry_run_sends_no_writes() {
+    let (server, dir) = mock_server("mock-dry-run");
+
+    let assert = mock_cmd(&server, &dir)
+        .args([
+            "1234567890123456789",
+            "--dry-run",
+            "--mode",
+            "b",
+            "--hero",
+            "法师,术士",
+            "--auto",
+            "off",
+        ])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("不会发送设置请求"))
+        .stdout(predicate::str::contains(
+            "对战模式: 标准模式 (2) → 酒馆战棋 (5)",
+        ))
+        .stdout(predicate::str::contains(
+            "对战英雄: 全部 (2047) → 术士, 法师 (192)",
+        ))
+        .stdout(predicate::str::contains("自动领取").not());
+    assert!(
+        server
+            .requests()
+            .iter()
+            .all(|form| form.iter().any(|(key, _)| key == "key"))
+    );
+    assert_eq!(
+        server.order("1234567890123456789").unwrap().config()["battlemode"],
+        "2"
+    );
+}
+
+#[test]
+fn mock_apply_is_idempotent() {
+    let (server, dir) = mock_server("mock-apply");
+    std::fs::create_dir_all(&dir).unwrap();
+    let state = dir.join("state.toml");
+    std::fs::write(
+        &state,
+        "[orders.\"1234567890123456789\"]\nmode = \"酒馆战棋\"\nheroes = [\"法师\", \"术士\"]\nauto = false\n",
+    )
+    .unwrap();
+
+    let apply = |server: &MockServer| {
+        mock_cmd(server, &dir)
+            .env("HS_ORDER_PWD", "zwmm")
+            .args(["apply", "-f"])
+            .arg(&state)
+            .assert()
+    };
+
+    let first = apply(&server);
+    let writes = |server: &MockServer| {
+        server
+            .requests()
+            .iter()
+            .filter(|form| form.iter().all(|(key, _)| key != "key"))
+            .count()
+    };
+    let first_writes = writes(&server);
+    let second = apply(&server);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    first
+        .success()
+        .stdout(predicate::str::contains(
+            "对战模式: 标准模式 (2) → 酒馆战棋 (5)",
+        ))
+        .stdout(predicate::str::contains("已重新查询确认设置生效"));
+    second
+        .success()
+        .stdout(predicate::str::contains("已是期望状态，无需修改"));
+    // 只发送了不一致的两项（auto 本来就是关闭）
+    assert_eq!(first_writes, 2);
+    assert_eq!(writes(&server), 2);
+}
+
+#[test]
+fn apply_rejects_unknown_mode() {
+    let path = std::env::temp_dir().join(format!("hs-order-cli-state-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        "[orders.\"1234567890123456789\"]\nmode = \"arena\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    let assert = cmd.args(["apply", "-f"]).arg(&path).assert();
+    std::fs::remove_file(&path).unwrap();
+
+    assert
+        .failure()
+        .stderr(predicate::str::contains("未知对战模式: arena"));
+}
+
+#[test]
+fn schedule_list_shows_next_run() {
+    let path = std::env::temp_dir().join(format!("hs-order-cli-sched-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        "[[rules]]\nname = \"晚上打战棋\"\ncron = \"0 20 * * *\"\norders = [\"1234567890123456789\"]\nset = { mode = \"酒馆战棋\" }\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    let assert = cmd.args(["schedule", "--list", "-f"]).arg(&path).assert();
+    std::fs::remove_file(&path).unwrap();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("晚上打战棋"))
+        .stdout(predicate::str::contains("0 20 * * *"))
+        .stdout(predicate::str::contains("对战模式 酒馆战棋 (5)"))
+        .stdout(predicate::str::is_match(r"下次执行: \d{4}-\d{2}-\d{2} 20:00:00").unwrap());
+}
+
+#[test]
+fn schedule_rejects_bad_cron() {
+    let path = std::env::temp_dir().join(format!("hs-order-cli-cron-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        "[[rules]]\ncron = \"0 25 * * *\"\norders = [\"1234567890123456789\"]\nset = { auto = true }\n",
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    let assert = cmd.args(["schedule", "-f"]).arg(&path).assert();
+    std::fs::remove_file(&path).unwrap();
+
+    assert.failure().stderr(predicate::str::contains(
+        "规则 1: cron 表达式的时字段不正确: 25",
+    ));
+}
+
+#[test]
+fn mock_schedule_runs_missed_rule_within_grace() {
+    let (server, dir) = mock_server("mock-schedule");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("schedule.toml");
+    // 每分钟执行，启动时补执行时限内刚错过的一次会立即执行
+    std::fs::write(
+        &path,
+        "grace = \"5m\"\n\n[[rules]]\ncron = \"* * * * *\"\norders = [\"1234567890123456789\"]\nset = { mode = \"酒馆战棋\" }\n",
+    )
+    .unwrap();
+
+    let _ = mock_cmd(&server, &dir)
+        .env("HS_ORDER_PWD", "zwmm")
+        .args(["schedule", "-f"])
+        .arg(&path)
+        .timeout(std::time::Duration::from_secs(5))
+        .output();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let config = server.order("1234567890123456789").unwrap().config();
+    assert_eq!(config["battlemode"], "5");
+}
+
+#[test]
+fn mock_query_warns_below_target_level() {
+    let (server, dir) = mock_server("mock-target");
+
+    let assert = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "--target-level", "100"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // 样例订单已过截止时间，预计等级即为当前等级
+    assert
+        .success()
+        .stdout(predicate::str::contains("进度预估"))
+        .stdout(predicate::str::contains("剩余时间: 0.0 天"))
+        .stdout(predicate::str::contains(
+            "按当前进度预计无法在截止时间前达到目标等级",
+        ));
+}
+
+#[test]
+fn target_level_requires_query() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args([
+        "1234567890123456789",
+        "--mode",
+        "wild",
+        "--target-level",
+        "60",
+    ])
+    .assert()
+    .failure();
+}
+
+#[test]
+fn mock_report_daily() {
+    let (server, dir) = mock_server("mock-report");
+
+    let text = mock_cmd(&server, &dir)
+        .args(["report", "1234567890123456789", "--daily"])
+        .assert();
+    let json = mock_cmd(&server, &dir)
+        .args([
+            "report",
+            "2025010100013333123456",
+            "--daily",
+            "-s",
+            "-o",
+            "json",
+        ])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    text.success()
+        .stdout(predicate::str::contains("每日统计"))
+        .stdout(predicate::str::contains("2025-11-12"))
+        .stdout(predicate::str::contains("1/1/0"));
+    json.success()
+        .stdout(predicate::str::contains(r#""date": "2025-11-12""#))
+        .stdout(predicate::str::contains(r#""battles": 2"#))
+        .stdout(predicate::str::contains(r#""play_secs": 2423"#));
+}
+
+#[test]
+fn mock_report_sessions() {
+    let (server, dir) = mock_server("mock-sessions");
+
+    let text = mock_cmd(&server, &dir)
+        .args(["report", "1234567890123456789", "--sessions"])
+        .assert();
+    let json = mock_cmd(&server, &dir)
+        .args([
+            "report",
+            "2025010100013333123456",
+            "--sessions",
+            "--gap",
+            "1h",
+            "-s",
+            "-o",
+            "json",
+        ])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    text.success()
+        .stdout(predicate::str::contains("游戏时段"))
+        .stdout(predicate::str::contains("共 2 个时段"));
+    json.success()
+        .stdout(predicate::str::contains(r#""sessions": ["#))
+        .stdout(predicate::str::contains(r#""battles": 2"#))
+        .stdout(predicate::str::contains(r#""play_secs": 2423"#))
+        .stdout(predicate::str::contains(r#""days""#).not());
+}
+
+#[test]
+fn report_gap_requires_sessions() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["report", "1234567890123456789", "--daily", "--gap", "1h"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn report_gap_overflow_fails() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args([
+        "report",
+        "1",
+        "--sessions",
+        "--gap",
+        "9999999999999999h",
+        "-s",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("合并游戏时段的最大间隔超出范围"));
+}
+
+#[test]
+fn mock_export_records() {
+    let (server, dir) = mock_server("mock-export");
+    let out = dir.join("export");
+
+    let separate = mock_cmd(&server, &dir)
+        .args(["export", "1234567890123456789", "-d"])
+        .arg(&out)
+        .assert();
+    let combined = mock_cmd(&server, &dir)
+        .args([
+            "export",
+            "2025010100013333123456",
+            "-s",
+            "--combined",
+            "-F",
+            "tsv",
+            "-d",
+        ])
+        .arg(&out)
+        .assert();
+    let battle = std::fs::read_to_string(out.join("2025010100013333123456-battle.csv"));
+    let records = std::fs::read_to_string(out.join("2025010100013333123456-records.tsv"));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    separate
+        .success()
+        .stdout(predicate::str::contains("已导出对战记录 2 条"));
+    combined
+        .success()
+        .stdout(predicate::str::contains("已导出全部记录 6 条"));
+    assert!(
+        battle
+            .unwrap()
+            .contains("2025-11-12T14:11:33+08:00,2025-11-12T14:25:42+08:00,849,loss,14\r\n")
+    );
+    let records = records.unwrap();
+    assert!(records.starts_with("\u{feff}type\ttime\t"));
+    assert_eq!(records.lines().count(), 7);
+}
+
+#[test]
+fn report_requires_view() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["report", "1234567890123456789"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn mock_inspect_raw_highlights_changes() {
+    let (server, dir) = mock_server("mock-inspect");
+
+    let first = mock_cmd(&server, &dir)
+        .args(["inspect", "1234567890123456789", "--raw"])
+        .assert();
+    mock_cmd(&server, &dir)
+        .args([
+            "1234567890123456789",
+            "--mode",
+            "battlegrounds",
+            "--pwd",
+            "zwmm",
+        ])
+        .assert()
+        .success();
+    let second = mock_cmd(&server, &dir)
+        .args(["inspect", "1234567890123456789", "--raw", "-o", "json"])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    first
+        .success()
+        .stdout(predicate::str::contains("dldata[8]"))
+        .stdout(predicate::str::contains("最近一次上号时间"))
+        .stdout(predicate::str::contains("（2025-11-12 08:21:18）"));
+    second
+        .success()
+        .stdout(predicate::str::contains(r#""path": "config.battlemode""#))
+        .stdout(predicate::str::contains(r#""kind": "changed""#))
+        .stdout(predicate::str::contains(r#""previous": "2""#));
+}
+
+#[test]
+fn inspect_requires_view() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["inspect", "1234567890123456789"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn mock_query_checks_service_window() {
+    let (server, dir) = mock_server("mock-service");
+
+    let text = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "-t", "0"])
+        .assert();
+    let json = mock_cmd(&server, &dir)
+        .args(["--query", "1234567890123456789", "-o", "json"])
+        .assert();
+    let report = mock_cmd(&server, &dir)
+        .args([
+            "report",
+            "2025010100013333123456",
+            "--daily",
+            "-s",
+            "-o",
+            "json",
+        ])
+        .assert();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // 样例订单的对战都在 08:00-16:00 内，之后到截止时间都没有上号
+    text.success()
+        .stdout(predicate::str::contains("上号时间: 08:00-16:00"))
+        .stdout(predicate::str::contains("最近上号: 2025-11-12 08:01:18"))
+        .stdout(predicate::str::contains("约定时间外的对战").not())
+        .stdout(predicate::str::contains(
+            "没有上号的日期（共 36 天）: 2025-11-13 ~ 2025-12-18",
+        ));
+    json.success().stdout(predicate::str::contains(
+        r#""last_login": "2025-11-12T08:01:18+08:00""#,
+    ));
+    report
+        .success()
+        .stdout(predicate::str::contains(r#""start": "08:00:00""#))
+        .stdout(predicate::str::contains(r#""outside": []"#));
+}
+
+#[test]
+fn tui_rejects_short_interval() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["tui", "1234567890123456789", "--interval", "5s"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("监控间隔不能小于 10 秒"));
+}
+
+#[test]
+fn mock_tui_query_failure_exits_before_ui() {
+    let (server, dir) = mock_server("mock-tui");
+
+    mock_cmd(&server, &dir)
+        .args(["tui", "9999999999999999999", "--read-only"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("查询失败"));
+}
+
+#[test]
+fn mock_chart_renders_curves() {
+    let (server, dir) = mock_server("mock-chart");
+
+    mock_cmd(&server, &dir)
+        .args([
+            "chart",
+            "1234567890123456789",
+            "--width",
+            "60",
+            "--height",
+            "4",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("总经验: 31029 → 31043（+14）"))
+        .stdout(predicate::str::contains("31043 ┤"))
+        .stdout(predicate::str::contains("累计金币变化: +150 枚"))
+        .stdout(predicate::str::contains("最多 2 场/天"));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["chart", "1234567890123456789", "--height", "1"])
+        .assert()
+        .failure();
+
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["chart", "1234567890123456789", "--days", "0", "-s"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn configure_requires_terminal() {
+    let mut cmd = cargo_bin_cmd!("hs-order-cli");
+    cmd.args(["configure", "1234567890123456789"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("需要在终端中交互式运行"));
+}